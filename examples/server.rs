@@ -0,0 +1,8 @@
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let addr = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "127.0.0.1:3000".to_string());
+    println!("listening on {addr}");
+    fvd_rs::server::serve(addr).await
+}
@@ -0,0 +1,120 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{analysis::AnalysisSample, track::Track, TrackSpline};
+
+// Meters between the samples segments are found at
+const SAMPLE_STEP: f64 = 0.1;
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct AirtimeOptions {
+    // G of vertical the rider counts as airtime under
+    pub airtime_below: f64,
+    // G of vertical held long enough to grey out, see `sustained_for`
+    pub sustained_above: f64,
+    // Seconds over `sustained_above` before it's reported
+    pub sustained_for: f64,
+}
+
+impl Default for AirtimeOptions {
+    fn default() -> Self {
+        Self {
+            airtime_below: 0.0,
+            sustained_above: 3.5,
+            sustained_for: 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ForceSegment {
+    // Meters along the track
+    pub start: f64,
+    pub end: f64,
+    pub start_time: f64,
+    pub end_time: f64,
+    pub duration: f64,
+    // The lowest vertical g for airtime, the highest for sustained
+    pub peak: f64,
+}
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct AirtimeReport {
+    pub airtime: Vec<ForceSegment>,
+    pub sustained: Vec<ForceSegment>,
+    // Seconds of airtime over the whole ride
+    pub total_airtime: f64,
+}
+
+// Runs of consecutive samples where `inside` holds. `peak` is the vertical of the sample
+// that `better` prefers over the rest
+fn segments(
+    samples: &[AnalysisSample],
+    inside: impl Fn(f64) -> bool,
+    better: impl Fn(f64, f64) -> bool,
+) -> Vec<ForceSegment> {
+    let mut segments = Vec::new();
+    let mut open: Option<ForceSegment> = None;
+    for sample in samples {
+        match (&mut open, inside(sample.vert)) {
+            (Some(segment), true) => {
+                segment.end = sample.distance;
+                segment.end_time = sample.time;
+                if better(sample.vert, segment.peak) {
+                    segment.peak = sample.vert;
+                }
+            }
+            (None, true) => {
+                open = Some(ForceSegment {
+                    start: sample.distance,
+                    end: sample.distance,
+                    start_time: sample.time,
+                    end_time: sample.time,
+                    duration: 0.0,
+                    peak: sample.vert,
+                })
+            }
+            (_, false) => segments.extend(open.take()),
+        }
+    }
+    segments.extend(open);
+    for segment in &mut segments {
+        segment.duration = segment.end_time - segment.start_time;
+    }
+    segments
+}
+
+fn report(samples: &[AnalysisSample], options: &AirtimeOptions) -> AirtimeReport {
+    let airtime = segments(samples, |vert| vert < options.airtime_below, |a, b| a < b);
+    let sustained = segments(samples, |vert| vert > options.sustained_above, |a, b| a > b)
+        .into_iter()
+        .filter(|segment| segment.duration >= options.sustained_for)
+        .collect();
+    AirtimeReport {
+        total_airtime: airtime.iter().map(|segment| segment.duration).sum(),
+        airtime,
+        sustained,
+    }
+}
+
+impl TrackSpline {
+    pub fn airtime(&self, options: &AirtimeOptions) -> AirtimeReport {
+        report(&self.analysis(SAMPLE_STEP), options)
+    }
+}
+
+impl Track {
+    // With the track's roughness applied to the samples first
+    pub fn airtime(&self, options: &AirtimeOptions) -> AirtimeReport {
+        report(&self.analysis(SAMPLE_STEP), options)
+    }
+}
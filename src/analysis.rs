@@ -0,0 +1,95 @@
+use glam::DVec3;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{pair_forces, pair_long, pair_roll_rate, TrackSpline};
+
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct AnalysisSample {
+    pub distance: f64,
+    pub time: f64,
+    pub speed: f64,
+    pub vert: f64,
+    pub lat: f64,
+    // G along the train, positive pressing the rider back into the seat
+    pub long: f64,
+    // Degrees the felt force leans from straight down into the seat, back (positive) or
+    // forward in the rider's sagittal plane and right (positive) or left in the coronal
+    // plane. Past ±90 the restraints are doing the holding, ±180 is hanging straight out
+    pub sagittal: f64,
+    pub coronal: f64,
+    // Degrees per second about the train's forward axis
+    pub roll_rate: f64,
+    // 1 / m
+    pub curvature: f64,
+    pub height: f64,
+    // Degrees, unwrapped
+    pub bank: f64,
+    pub heading: f64,
+}
+
+impl AnalysisSample {
+    // Call again after changing the forces
+    pub(crate) fn fill_angles(&mut self) {
+        self.sagittal = libm::atan2(self.long, self.vert).to_degrees();
+        self.coronal = libm::atan2(self.lat, self.vert).to_degrees();
+    }
+}
+
+impl TrackSpline {
+    pub fn analysis(&self, step: f64) -> Vec<AnalysisSample> {
+        let mut samples = Vec::new();
+        if step <= 0.0 {
+            return samples;
+        }
+
+        let mut distance = 0.0;
+        let mut next_sample = 0.0;
+        let banks = self.bank_angles();
+        let headings = self.headings();
+
+        for (i, pair) in self.points.windows(2).enumerate() {
+            let (last_point, point) = (&pair[0], &pair[1]);
+            let delta_dist = (point.pos - last_point.pos).length();
+            distance += delta_dist;
+
+            if distance < next_sample {
+                continue;
+            }
+            let Some(forces) = pair_forces(last_point, point) else {
+                continue;
+            };
+            let delta_time = point.time - last_point.time;
+            let long = pair_long(last_point, point);
+            let roll_rate = pair_roll_rate(last_point, point);
+            let curvature =
+                (last_point.rot.0 * DVec3::Z).angle_between(point.rot.0 * DVec3::Z) / delta_dist;
+
+            while next_sample <= distance {
+                let t = 1.0 - (distance - next_sample) / delta_dist;
+                let mut sample = AnalysisSample {
+                    distance: next_sample,
+                    time: last_point.time + delta_time * t,
+                    speed: last_point.velocity + (point.velocity - last_point.velocity) * t,
+                    vert: forces.vert,
+                    lat: forces.lat,
+                    long,
+                    roll_rate,
+                    curvature,
+                    height: last_point.pos.y + (point.pos.y - last_point.pos.y) * t,
+                    bank: banks[i] + (banks[i + 1] - banks[i]) * t,
+                    heading: headings[i] + (headings[i + 1] - headings[i]) * t,
+                    ..Default::default()
+                };
+                sample.fill_angles();
+                samples.push(sample);
+                next_sample += step;
+            }
+        }
+
+        samples
+    }
+}
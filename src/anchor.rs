@@ -0,0 +1,48 @@
+use glam::{DQuat, DVec3};
+
+use crate::{track::Track, TrackPoint};
+
+pub fn snap_to_grid(pos: DVec3, grid: f64) -> DVec3 {
+    if grid <= 0.0 {
+        return pos;
+    }
+    (pos / grid).round() * grid
+}
+
+// Degrees, e.g. an increment of 90 snaps to the cardinal directions
+pub fn snap_heading(heading: f64, increment: f64) -> f64 {
+    if increment <= 0.0 {
+        return heading;
+    }
+    (heading / increment).round() * increment
+}
+
+// Degrees about the vertical axis, 0 is the default direction of travel
+pub fn heading(rot: DQuat) -> f64 {
+    let dir = rot * DVec3::Z;
+    libm::atan2(dir.x, dir.z).to_degrees()
+}
+
+impl Track {
+    pub fn heading(&self) -> f64 {
+        heading(self.anchor.rot.0)
+    }
+
+    pub fn set_heading(&mut self, heading: f64) {
+        self.anchor.rot = DQuat::from_rotation_y(heading.to_radians()).into();
+    }
+
+    pub fn snap_anchor(&mut self, grid: f64, heading_increment: f64) {
+        self.anchor.pos = snap_to_grid(self.anchor.pos, grid);
+        self.set_heading(snap_heading(self.heading(), heading_increment));
+    }
+
+    // Moves and turns the whole track so that `from`, a point on its current spline,
+    // ends up at `pos` facing `target_heading`
+    pub fn reanchor(&mut self, from: &TrackPoint, pos: DVec3, target_heading: f64) {
+        let turn = target_heading - heading(from.rot.0);
+        let offset = DQuat::from_rotation_y(turn.to_radians()) * (self.anchor.pos - from.pos);
+        self.anchor.pos = pos + offset;
+        self.set_heading(self.heading() + turn);
+    }
+}
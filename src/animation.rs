@@ -0,0 +1,119 @@
+use glam::{DQuat, DVec3};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    math::WrapperDQuat,
+    track::{transfer_pose, Track, TrackSection},
+    transitions::TransitionCurve,
+    TrackPoint,
+};
+
+const KEYFRAME_RATE: f64 = 30.0;
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Keyframe {
+    pub time: f64,
+    #[cfg_attr(feature = "ts", ts(type = "[number, number, number]"))]
+    pub translation: DVec3,
+    #[cfg_attr(feature = "ts", ts(type = "[number, number, number, number]"))]
+    pub rot: WrapperDQuat,
+}
+
+// Moves a piece of track from its rest pose: p' = pivot + rot * (p - pivot) + translation
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct PieceAnimation {
+    pub section: usize,
+    #[cfg_attr(feature = "ts", ts(type = "[number, number, number]"))]
+    pub pivot: DVec3,
+    pub geometry: Vec<TrackPoint>,
+    pub keyframes: Vec<Keyframe>,
+}
+
+impl Track {
+    pub fn animations(&self) -> Vec<PieceAnimation> {
+        let splines = self.make_splines();
+        let mut entry = self.initial_point();
+        let mut animations = Vec::new();
+
+        for (i, (section, spline)) in self.sections.iter().zip(&splines).enumerate() {
+            match section {
+                TrackSection::Tilt {
+                    length,
+                    angle,
+                    duration,
+                } => {
+                    let axis = entry.rot.0 * DVec3::X;
+                    animations.push(PieceAnimation {
+                        section: i,
+                        pivot: entry.pos,
+                        geometry: rest_geometry(&entry, *length),
+                        keyframes: keyframes(entry.time, *duration, |u| {
+                            (
+                                DVec3::ZERO,
+                                DQuat::from_axis_angle(axis, angle.to_radians() * u),
+                            )
+                        }),
+                    });
+                }
+                TrackSection::Transfer {
+                    length,
+                    offset,
+                    rotation,
+                    duration,
+                    ..
+                } => {
+                    let (pivot, moved) = transfer_pose(&entry, *length, *rotation);
+                    animations.push(PieceAnimation {
+                        section: i,
+                        pivot,
+                        geometry: rest_geometry(&entry, *length),
+                        keyframes: keyframes(entry.time, *duration, |u| {
+                            (*offset * u, DQuat::IDENTITY.slerp(moved, u))
+                        }),
+                    });
+                }
+                _ => {}
+            }
+            if let Some(point) = spline.points.last() {
+                entry = *point;
+            }
+        }
+
+        animations
+    }
+}
+
+fn rest_geometry(entry: &TrackPoint, length: f64) -> Vec<TrackPoint> {
+    let dir = entry.rot.0 * DVec3::Z;
+    let samples = length.ceil().max(1.0) as usize;
+
+    (0..=samples)
+        .map(|i| TrackPoint {
+            pos: entry.pos + dir * (length * i as f64 / samples as f64),
+            ..*entry
+        })
+        .collect()
+}
+
+fn keyframes(start: f64, duration: f64, pose: impl Fn(f64) -> (DVec3, DQuat)) -> Vec<Keyframe> {
+    let frames = (duration * KEYFRAME_RATE).ceil().max(1.0) as usize;
+
+    (0..=frames)
+        .map(|i| {
+            let u = i as f64 / frames as f64;
+            let (translation, rot) = pose(TransitionCurve::Sinusoidal.eval(u));
+            Keyframe {
+                time: start + u * duration,
+                translation,
+                rot: rot.into(),
+            }
+        })
+        .collect()
+}
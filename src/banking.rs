@@ -0,0 +1,31 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::transitions::TransitionCurve;
+
+// Roll a curved section banks to over its length and back out of by its end
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Banking {
+    // Degrees from the entry roll, positive rolls right
+    pub angle: f64,
+    pub curve: TransitionCurve,
+    // Meters to roll in over, and again to roll out over. Halved for curves too short
+    // for both
+    pub length: f64,
+}
+
+impl Banking {
+    // Radians of roll `p` meters into a curve `total` meters long
+    pub fn roll_at(&self, p: f64, total: f64) -> f64 {
+        let ramp = self.length.min(total / 2.0);
+        let t = if ramp <= 0.0 {
+            1.0
+        } else {
+            (p.min(total - p) / ramp).clamp(0.0, 1.0)
+        };
+        self.angle.to_radians() * self.curve.eval(t)
+    }
+}
@@ -0,0 +1,109 @@
+use std::{
+    fs,
+    io::{self, Write},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+use rayon::prelude::*;
+
+use crate::{csv, output::OutputOptions, stats::TrackStats, track::Track};
+
+#[derive(Clone, Debug, Default)]
+pub struct BatchOptions {
+    // Writes <name>.spline.json for every track when set
+    pub export_dir: Option<PathBuf>,
+    pub output: OutputOptions,
+}
+
+#[derive(Debug)]
+pub struct BatchResult {
+    pub path: PathBuf,
+    pub stats: io::Result<TrackStats>,
+}
+
+// Every .json file directly inside dir, sorted by name
+pub fn track_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "json") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+pub fn process_dir(dir: &Path, options: &BatchOptions) -> io::Result<Vec<BatchResult>> {
+    Ok(process(&track_files(dir)?, options))
+}
+
+pub fn process(paths: &[PathBuf], options: &BatchOptions) -> Vec<BatchResult> {
+    if let Some(dir) = &options.export_dir {
+        if let Err(err) = fs::create_dir_all(dir) {
+            return paths
+                .iter()
+                .map(|path| BatchResult {
+                    path: path.clone(),
+                    stats: Err(io::Error::new(err.kind(), err.to_string())),
+                })
+                .collect();
+        }
+    }
+
+    paths
+        .par_iter()
+        .map(|path| BatchResult {
+            path: path.clone(),
+            stats: process_file(path, options),
+        })
+        .collect()
+}
+
+fn process_file(path: &Path, options: &BatchOptions) -> io::Result<TrackStats> {
+    let track: Track = serde_json::from_reader(io::BufReader::new(fs::File::open(path)?))?;
+
+    // A track that fails to simulate shouldn't take the rest of the batch down with it
+    let ((spline, section_start), stats) = panic::catch_unwind(AssertUnwindSafe(|| {
+        track.try_get_spline_with_stats(&options.output)
+    }))
+    .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "track failed to simulate"))?
+    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+    if let Some(dir) = &options.export_dir {
+        let name = path.file_stem().unwrap_or_default().to_string_lossy();
+        let file = fs::File::create(dir.join(format!("{name}.spline.json")))?;
+        serde_json::to_writer(
+            io::BufWriter::new(file),
+            &(spline.masked(&options.output), &section_start),
+        )?;
+    }
+
+    Ok(stats)
+}
+
+pub fn write_csv<W: Write>(results: &[BatchResult], mut writer: W) -> io::Result<()> {
+    writeln!(
+        writer,
+        "file,length,duration,maxSpeed,minHeight,maxHeight,enclosedLength,error"
+    )?;
+    for result in results {
+        let file = csv::field(&result.path.to_string_lossy());
+        match &result.stats {
+            Ok(stats) => writeln!(
+                writer,
+                "{},{},{},{},{},{},{},",
+                file,
+                stats.total.length,
+                stats.total.duration,
+                stats.total.max_speed,
+                stats.total.lowest.y,
+                stats.total.highest.y,
+                stats.enclosed_length
+            )?,
+            Err(err) => writeln!(writer, "{},,,,,,,{}", file, csv::field(&err.to_string()))?,
+        }
+    }
+    Ok(())
+}
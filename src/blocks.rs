@@ -0,0 +1,128 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::track::{Track, TrackSection};
+
+// Speed steps the stopping distance is integrated over
+const STOP_STEPS: usize = 200;
+// Slowest speed a train is assumed to clear a block at, so a train leaving a station
+// doesn't count as taking forever
+const MIN_CLEAR_SPEED: f64 = 1.0;
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct BlockOptions {
+    // Multiplier on the simulated entry speed, for trains running faster than the
+    // simulated one on a warm day
+    pub speed_margin: f64,
+    // Meters, a block is clear once the last car has left it
+    pub train_length: f64,
+}
+
+impl Default for BlockOptions {
+    fn default() -> Self {
+        Self {
+            speed_margin: 1.1,
+            train_length: 15.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct BlockCheck {
+    pub first_section: usize,
+    // The brake closing the block
+    pub last_section: usize,
+    // Into the closing brake, with the speed margin applied
+    pub entry_speed: f64,
+    // None when the brake can't bring the train to a stop at all
+    pub stopping_distance: Option<f64>,
+    pub brake_length: f64,
+    pub stops: bool,
+    // Seconds from the front entering the block to the back leaving it
+    pub clear_time: f64,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct BlockSpacing {
+    pub blocks: Vec<BlockCheck>,
+    // Seconds between dispatches so no train has to wait on the one ahead, the slowest
+    // block to clear
+    pub min_separation: f64,
+}
+
+// Distance to stop from `speed` at `deceleration`, capped by what the brake can give at
+// each speed
+fn stopping_distance(speed: f64, deceleration: f64, available: impl Fn(f64) -> f64) -> Option<f64> {
+    let dv = speed / STOP_STEPS as f64;
+    (0..STOP_STEPS).try_fold(0.0, |distance, i| {
+        let v = (i as f64 + 0.5) * dv;
+        let a = deceleration.min(available(v));
+        (a > 0.0).then(|| distance + v * dv / a)
+    })
+}
+
+impl Track {
+    // Checks every block brake can stop a train entering it at the margin above the
+    // simulated speed within its own length, and how far apart trains have to be dispatched
+    pub fn block_spacing(&self, options: &BlockOptions) -> BlockSpacing {
+        let splines = self.make_splines();
+        let mut entry = self.initial_point();
+        let mut block_entry = entry;
+        let mut first_section = 0;
+        let mut blocks = Vec::new();
+
+        for (i, (section, spline)) in self.sections.iter().zip(&splines).enumerate() {
+            let Some(exit) = spline.points.last().copied() else {
+                break;
+            };
+            let entry_speed = entry.velocity * options.speed_margin;
+            let brake = match section {
+                TrackSection::Brake {
+                    length,
+                    deceleration,
+                    brake_type,
+                    curve,
+                    ..
+                } => {
+                    let curve = curve.clone().unwrap_or_else(|| brake_type.curve());
+                    let distance = stopping_distance(entry_speed, *deceleration, |v| {
+                        curve.max_deceleration(v)
+                    });
+                    Some((distance, *length))
+                }
+                // Holding brakes stop the train by definition
+                TrackSection::HoldingBrake { length, .. } => Some((Some(0.0), *length)),
+                _ => None,
+            };
+            if let Some((stopping_distance, brake_length)) = brake {
+                let clear_speed = exit.velocity.max(MIN_CLEAR_SPEED);
+                blocks.push(BlockCheck {
+                    first_section,
+                    last_section: i,
+                    entry_speed,
+                    stopping_distance,
+                    brake_length,
+                    stops: stopping_distance.is_some_and(|d| d <= brake_length),
+                    clear_time: exit.time - block_entry.time + options.train_length / clear_speed,
+                });
+                first_section = i + 1;
+                block_entry = exit;
+            }
+            entry = exit;
+        }
+
+        BlockSpacing {
+            min_separation: blocks.iter().map(|b| b.clear_time).fold(0.0, f64::max),
+            blocks,
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use libm::{asin, atan2, cos, hypot, sin};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{track::Track, TrackSpline};
+
+// A trough of circular cross section the train rides in without a lateral rail, as on a
+// bobsled or flume. The train holds its place until the lateral load in its own frame goes
+// over `grip`, then slides up the side until it doesn't
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct TroughOptions {
+    // Meters
+    pub radius: f64,
+    // Degrees from the bottom to the lip of the trough
+    pub wall_angle: f64,
+    // G of lateral the train holds before it slides
+    pub grip: f64,
+    // Meters between samples
+    pub step: f64,
+}
+
+impl Default for TroughOptions {
+    fn default() -> Self {
+        Self {
+            radius: 2.0,
+            wall_angle: 75.0,
+            grip: 0.2,
+            step: 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SledSample {
+    pub distance: f64,
+    // Degrees up the wall, positive towards positive lateral
+    pub angle: f64,
+    // Meters from the centre line across the trough, and up from its bottom
+    pub offset: f64,
+    pub rise: f64,
+    // Forces in the train's frame once it has slid
+    pub vert: f64,
+    pub lat: f64,
+    // The train would have to climb past the lip to hold on
+    pub spill: bool,
+}
+
+impl TrackSpline {
+    pub fn bobsled(&self, options: &TroughOptions) -> Vec<SledSample> {
+        let wall = options.wall_angle.to_radians();
+        let mut angle = 0.0_f64;
+
+        self.analysis(options.step)
+            .iter()
+            .map(|sample| {
+                let (vert, lat) = (sample.vert, sample.lat);
+                let felt = |angle: f64| lat * cos(angle) - vert * sin(angle);
+
+                // Slide just far enough that the remaining lateral is what the grip holds
+                let load = hypot(lat, vert);
+                if felt(angle).abs() > options.grip && load > options.grip {
+                    let settle = asin(options.grip / load);
+                    let direction = atan2(lat, vert);
+                    angle = if felt(angle) > 0.0 {
+                        direction - settle
+                    } else {
+                        direction + settle
+                    };
+                }
+                let spill = angle.abs() > wall;
+                angle = angle.clamp(-wall, wall);
+
+                SledSample {
+                    distance: sample.distance,
+                    angle: angle.to_degrees(),
+                    offset: options.radius * sin(angle),
+                    rise: options.radius * (1.0 - cos(angle)),
+                    vert: vert * cos(angle) + lat * sin(angle),
+                    lat: felt(angle),
+                    spill,
+                }
+            })
+            .collect()
+    }
+}
+
+impl Track {
+    pub fn bobsled(&self, options: &TroughOptions) -> Vec<SledSample> {
+        self.get_spline().0.bobsled(options)
+    }
+}
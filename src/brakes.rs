@@ -0,0 +1,104 @@
+use glam::DVec2;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    math::interpolate,
+    track::{Track, TrackSection},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum BrakeType {
+    #[default]
+    Magnetic,
+    Friction,
+}
+
+// Maximum deceleration (m/s^2) by speed (m/s), linearly interpolated and held past the ends
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct BrakeCurve {
+    #[cfg_attr(feature = "ts", ts(type = "Array<[number, number]>"))]
+    pub points: Vec<DVec2>,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct BrakeViolation {
+    pub section: usize,
+    // Speed through the brake where the requested deceleration is no longer available
+    pub speed: f64,
+    pub requested: f64,
+    pub available: f64,
+}
+
+impl BrakeType {
+    pub fn curve(&self) -> BrakeCurve {
+        let points = match self {
+            // Eddy current brakes lose force as the train slows and can't hold it at a stop
+            BrakeType::Magnetic => vec![
+                DVec2::new(0.0, 0.0),
+                DVec2::new(3.0, 2.0),
+                DVec2::new(10.0, 6.0),
+                DVec2::new(40.0, 6.5),
+            ],
+            BrakeType::Friction => vec![DVec2::new(0.0, 5.0), DVec2::new(40.0, 5.0)],
+        };
+        BrakeCurve { points }
+    }
+}
+
+impl BrakeCurve {
+    pub fn max_deceleration(&self, speed: f64) -> f64 {
+        interpolate(&self.points, speed)
+    }
+}
+
+impl Track {
+    pub fn brake_violations(&self) -> Vec<BrakeViolation> {
+        let splines = self.make_splines();
+        let mut entry = self.initial_point();
+        let mut violations = Vec::new();
+
+        for (i, (section, spline)) in self.sections.iter().zip(&splines).enumerate() {
+            if let TrackSection::Brake {
+                deceleration,
+                exit_speed,
+                brake_type,
+                curve,
+                ..
+            } = section
+            {
+                let curve = curve.clone().unwrap_or_else(|| brake_type.curve());
+
+                // Braking happens between the entry speed and the target exit speed
+                let speeds = std::iter::once(entry.velocity)
+                    .chain(spline.points.iter().map(|p| p.velocity))
+                    .filter(|v| v > exit_speed);
+                if let Some(speed) = speeds
+                    .filter(|v| curve.max_deceleration(*v) < *deceleration)
+                    .reduce(f64::max)
+                {
+                    violations.push(BrakeViolation {
+                        section: i,
+                        speed,
+                        requested: *deceleration,
+                        available: curve.max_deceleration(speed),
+                    });
+                }
+            }
+            if let Some(point) = spline.points.last() {
+                entry = *point;
+            }
+        }
+
+        violations
+    }
+}
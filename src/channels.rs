@@ -0,0 +1,63 @@
+use crate::{
+    math::{deg_diff, euler},
+    pair_rotation, TrackPoint, TrackSpline,
+};
+
+// Accumulates wrapped angles in degrees so they stay continuous past ±180
+pub fn unwrap_degrees(angles: impl IntoIterator<Item = f64>) -> Vec<f64> {
+    let mut unwrapped: Vec<f64> = Vec::new();
+    let mut last = 0.0;
+    for angle in angles {
+        let value = match unwrapped.last() {
+            Some(prev) => prev + deg_diff(last, angle),
+            None => angle,
+        };
+        unwrapped.push(value);
+        last = angle;
+    }
+    unwrapped
+}
+
+impl TrackSpline {
+    // Bank angle relative to gravity for each point, in degrees. Starts from the first
+    // point's roll and adds up the twist about the forward axis between each pair, so
+    // it doesn't flip by 180 going through vertical like the Euler roll does
+    pub fn bank_angles(&self) -> Vec<f64> {
+        self.accumulate(
+            |p| euler(p).2,
+            |last_point, point| pair_rotation(last_point, point).z,
+        )
+    }
+
+    // Yaw of the track direction for each point, in degrees. Adds up the turning about
+    // the vertical between each pair, so it holds through vertical and a plain loop
+    // keeps its heading
+    pub fn headings(&self) -> Vec<f64> {
+        self.accumulate(
+            |p| euler(p).0,
+            |last_point, point| {
+                (point.rot.0 * last_point.rot.0.inverse())
+                    .to_scaled_axis()
+                    .y
+            },
+        )
+    }
+
+    // `step` is the change in radians from one point to the next
+    fn accumulate(
+        &self,
+        first: impl Fn(&TrackPoint) -> f64,
+        step: impl Fn(&TrackPoint, &TrackPoint) -> f64,
+    ) -> Vec<f64> {
+        let Some(start) = self.points.first() else {
+            return Vec::new();
+        };
+        let mut angle = first(start);
+        let mut angles = vec![angle];
+        for pair in self.points.windows(2) {
+            angle += step(&pair[0], &pair[1]).to_degrees();
+            angles.push(angle);
+        }
+        angles
+    }
+}
@@ -0,0 +1,122 @@
+use glam::{DVec2, DVec3};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{track::Track, TrackSpline};
+
+// Cross sections are given in the track frame, x to the left and y up from the rail centerline
+#[derive(Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Envelope {
+    #[default]
+    SitDown,
+    Inverted,
+    Wing,
+    Custom {
+        #[cfg_attr(feature = "ts", ts(type = "Array<[number, number]>"))]
+        profile: Vec<DVec2>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ClearanceViolation {
+    pub obstacle: usize,
+    pub distance: f64,
+    pub time: f64,
+}
+
+impl Envelope {
+    pub fn profile(&self) -> Vec<DVec2> {
+        let rect = |half_width: f64, bottom: f64, top: f64| {
+            vec![
+                DVec2::new(-half_width, bottom),
+                DVec2::new(half_width, bottom),
+                DVec2::new(half_width, top),
+                DVec2::new(-half_width, top),
+            ]
+        };
+        match self {
+            Envelope::SitDown => rect(1.2, -0.6, 2.5),
+            Envelope::Inverted => rect(1.3, -3.0, 0.8),
+            Envelope::Wing => rect(2.8, -0.8, 2.0),
+            Envelope::Custom { profile } => profile.clone(),
+        }
+    }
+
+    pub fn contains(&self, point: DVec2) -> bool {
+        polygon_contains(&self.profile(), point)
+    }
+}
+
+fn polygon_contains(polygon: &[DVec2], point: DVec2) -> bool {
+    let mut inside = false;
+    let mut j = polygon.len().wrapping_sub(1);
+    for i in 0..polygon.len() {
+        let (a, b) = (polygon[i], polygon[j]);
+        if (a.y > point.y) != (b.y > point.y)
+            && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x
+        {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+impl TrackSpline {
+    // `heartline_height` is how far the points sit above the rails the profile is drawn from
+    pub fn clearance_violations(
+        &self,
+        envelope: &Envelope,
+        obstacles: &[DVec3],
+        heartline_height: f64,
+    ) -> Vec<ClearanceViolation> {
+        let profile = envelope.profile();
+        let mut violations = Vec::new();
+        let mut distance = 0.0;
+
+        for (i, point) in self.points.iter().enumerate() {
+            let spacing = match (i.checked_sub(1), self.points.get(i + 1)) {
+                (Some(last), _) => (point.pos - self.points[last].pos).length(),
+                (None, Some(next)) => (next.pos - point.pos).length(),
+                (None, None) => 0.0,
+            };
+            if i > 0 {
+                distance += spacing;
+            }
+
+            let inverse = point.rot.0.inverse();
+            let rail = point.pos - point.rot.0 * DVec3::Y * heartline_height;
+            for (obstacle, pos) in obstacles.iter().enumerate() {
+                let local = inverse * (*pos - rail);
+                if local.z.abs() > spacing / 2.0 {
+                    continue;
+                }
+                if polygon_contains(&profile, local.truncate()) {
+                    violations.push(ClearanceViolation {
+                        obstacle,
+                        distance,
+                        time: point.time,
+                    });
+                }
+            }
+        }
+
+        violations
+    }
+}
+
+impl Track {
+    pub fn clearance_violations(&self, obstacles: &[DVec3]) -> Vec<ClearanceViolation> {
+        self.get_spline().0.clearance_violations(
+            &self.config.envelope(),
+            obstacles,
+            self.config.heartline_height(),
+        )
+    }
+}
@@ -0,0 +1,161 @@
+use std::f64::consts::TAU;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{constants::G, pair_forces, track::Track, TrackSpline};
+
+const SAMPLE_RATE: f64 = 100.0;
+
+// Frequency weighted RMS accelerations in m/s², after ISO 2631-1 Wk (vertical) and Wd (horizontal)
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ComfortReport {
+    pub vert: f64,
+    pub lat: f64,
+    pub long: f64,
+    // Root sum of squares of the three axes, lower is smoother
+    pub total: f64,
+}
+
+#[derive(Clone, Copy)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    x: [f64; 2],
+    y: [f64; 2],
+}
+
+impl Biquad {
+    // Bilinear transform of (b2 s² + b1 s + b0) / (a2 s² + a1 s + a0)
+    fn new(num: [f64; 3], den: [f64; 3]) -> Self {
+        let k = 2.0 * SAMPLE_RATE;
+        let k2 = k * k;
+        let [b2, b1, b0] = num;
+        let [a2, a1, a0] = den;
+        let norm = a2 * k2 + a1 * k + a0;
+        Self {
+            b: [
+                (b2 * k2 + b1 * k + b0) / norm,
+                (2.0 * b0 - 2.0 * b2 * k2) / norm,
+                (b2 * k2 - b1 * k + b0) / norm,
+            ],
+            a: [
+                (2.0 * a0 - 2.0 * a2 * k2) / norm,
+                (a2 * k2 - a1 * k + a0) / norm,
+            ],
+            x: [0.0; 2],
+            y: [0.0; 2],
+        }
+    }
+
+    fn high_pass(f: f64, q: f64) -> Self {
+        let w = TAU * f;
+        Self::new(
+            [1.0 / (w * w), 0.0, 0.0],
+            [1.0 / (w * w), 1.0 / (q * w), 1.0],
+        )
+    }
+
+    fn transition(f3: f64, f4: f64, q4: f64) -> Self {
+        let (w3, w4) = (TAU * f3, TAU * f4);
+        Self::new(
+            [0.0, 1.0 / w3, 1.0],
+            [1.0 / (w4 * w4), 1.0 / (q4 * w4), 1.0],
+        )
+    }
+
+    fn upward_step(f5: f64, q5: f64, f6: f64, q6: f64) -> Self {
+        let (w5, w6) = (TAU * f5, TAU * f6);
+        Self::new(
+            [1.0 / (w5 * w5), 1.0 / (q5 * w5), 1.0],
+            [1.0 / (w6 * w6), 1.0 / (q6 * w6), 1.0],
+        )
+    }
+
+    fn process(&mut self, x: f64) -> f64 {
+        let y = self.b[0] * x + self.b[1] * self.x[0] + self.b[2] * self.x[1]
+            - self.a[0] * self.y[0]
+            - self.a[1] * self.y[1];
+        self.x = [x, self.x[0]];
+        self.y = [y, self.y[0]];
+        y
+    }
+}
+
+// The 100 Hz band limit is above the sample rate's Nyquist frequency and is left out
+fn weighted_wk() -> Vec<Biquad> {
+    vec![
+        Biquad::high_pass(0.4, std::f64::consts::FRAC_1_SQRT_2),
+        Biquad::transition(12.5, 12.5, 0.63),
+        Biquad::upward_step(2.37, 0.91, 3.35, 0.91),
+    ]
+}
+
+fn weighted_wd() -> Vec<Biquad> {
+    vec![
+        Biquad::high_pass(0.4, std::f64::consts::FRAC_1_SQRT_2),
+        Biquad::transition(2.0, 2.0, 0.63),
+    ]
+}
+
+fn weighted_rms(filters: &mut [Biquad], samples: &[f64]) -> f64 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let sum = samples
+        .iter()
+        .map(|&x| filters.iter_mut().fold(x, |x, f| f.process(x)).powi(2))
+        .sum::<f64>();
+    (sum / samples.len() as f64).sqrt()
+}
+
+impl TrackSpline {
+    pub fn comfort(&self) -> ComfortReport {
+        let mut vert = Vec::new();
+        let mut lat = Vec::new();
+        let mut speed = Vec::new();
+
+        let mut next_sample = self.points.first().map(|p| p.time).unwrap_or_default();
+        for pair in self.points.windows(2) {
+            let (last_point, point) = (&pair[0], &pair[1]);
+            let delta_time = point.time - last_point.time;
+            if delta_time <= 0.0 {
+                continue;
+            }
+            let Some(forces) = pair_forces(last_point, point) else {
+                continue;
+            };
+            while next_sample <= point.time {
+                let t = (next_sample - last_point.time) / delta_time;
+                vert.push((forces.vert - 1.0) * G);
+                lat.push(forces.lat * G);
+                speed.push(last_point.velocity + (point.velocity - last_point.velocity) * t);
+                next_sample += 1.0 / SAMPLE_RATE;
+            }
+        }
+
+        let long: Vec<f64> = speed
+            .windows(2)
+            .map(|pair| (pair[1] - pair[0]) * SAMPLE_RATE)
+            .collect();
+
+        let vert = weighted_rms(&mut weighted_wk(), &vert);
+        let lat = weighted_rms(&mut weighted_wd(), &lat);
+        let long = weighted_rms(&mut weighted_wd(), &long);
+        ComfortReport {
+            vert,
+            lat,
+            long,
+            total: (vert * vert + lat * lat + long * long).sqrt(),
+        }
+    }
+}
+
+impl Track {
+    pub fn comfort(&self) -> ComfortReport {
+        TrackSpline::concat(&self.make_splines()).comfort()
+    }
+}
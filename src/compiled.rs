@@ -0,0 +1,81 @@
+use std::sync::{Arc, RwLock};
+
+use crate::{
+    error::TrackError, index, stats::TrackStats, track::Track, transitions::Forces, TrackPoint,
+    TrackSpline,
+};
+
+// Everything derived from simulating the track once
+#[derive(Clone, Debug)]
+pub struct CompiledSpline {
+    pub spline: TrackSpline,
+    pub section_start: Vec<f64>,
+    pub distances: Vec<f64>,
+    pub stats: TrackStats,
+}
+
+impl CompiledSpline {
+    fn new(track: &Track) -> Result<Self, TrackError> {
+        let ((spline, section_start), stats) =
+            track.try_get_spline_with_stats(&Default::default())?;
+        Ok(Self {
+            distances: spline.cumulative_distances(),
+            stats,
+            spline,
+            section_start,
+        })
+    }
+
+    // Same as TrackSplineIndex::forces, on the distances computed once up front
+    pub fn forces(&self, distance: f64) -> Option<Forces> {
+        index::forces(&self.spline.points, &self.distances, distance)
+    }
+
+    pub fn evaluate(&self, distance: f64) -> Option<TrackPoint> {
+        index::evaluate(&self.spline.points, &self.distances, distance)
+    }
+}
+
+// A track shared between threads, e.g. behind an Arc in a server's state. The simulation
+// runs on the first query and every later query reads the cached result
+#[derive(Debug)]
+pub struct CompiledTrack {
+    track: Track,
+    compiled: RwLock<Option<Arc<CompiledSpline>>>,
+}
+
+impl CompiledTrack {
+    pub fn new(track: Track) -> Self {
+        Self {
+            track,
+            compiled: RwLock::new(None),
+        }
+    }
+
+    pub fn track(&self) -> &Track {
+        &self.track
+    }
+
+    pub fn compiled(&self) -> Result<Arc<CompiledSpline>, TrackError> {
+        if let Some(compiled) = self.compiled.read().unwrap().as_ref() {
+            return Ok(compiled.clone());
+        }
+        // Simulate without holding the lock so readers aren't blocked. Two threads may race
+        // to fill an empty cache, the first one to finish wins
+        let compiled = Arc::new(CompiledSpline::new(&self.track)?);
+        let mut cache = self.compiled.write().unwrap();
+        Ok(cache.get_or_insert(compiled).clone())
+    }
+
+    pub fn stats(&self) -> Result<TrackStats, TrackError> {
+        Ok(self.compiled()?.stats.clone())
+    }
+
+    pub fn forces(&self, distance: f64) -> Result<Option<Forces>, TrackError> {
+        Ok(self.compiled()?.forces(distance))
+    }
+
+    pub fn evaluate(&self, distance: f64) -> Result<Option<TrackPoint>, TrackError> {
+        Ok(self.compiled()?.evaluate(distance))
+    }
+}
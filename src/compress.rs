@@ -0,0 +1,40 @@
+use std::io::{self, Read, Write};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+#[cfg(feature = "brotli")]
+const BROTLI_BUFFER_SIZE: usize = 4096;
+#[cfg(feature = "brotli")]
+const BROTLI_QUALITY: u32 = 9;
+#[cfg(feature = "brotli")]
+const BROTLI_WINDOW: u32 = 22;
+
+#[cfg(feature = "gzip")]
+pub fn write_gzip<T: Serialize, W: Write>(value: &T, writer: W) -> io::Result<()> {
+    let mut encoder = flate2::write::GzEncoder::new(writer, flate2::Compression::default());
+    serde_json::to_writer(&mut encoder, value)?;
+    encoder.finish()?;
+    Ok(())
+}
+
+#[cfg(feature = "gzip")]
+pub fn read_gzip<T: DeserializeOwned, R: Read>(reader: R) -> io::Result<T> {
+    let decoder = flate2::read::GzDecoder::new(reader);
+    Ok(serde_json::from_reader(io::BufReader::new(decoder))?)
+}
+
+#[cfg(feature = "brotli")]
+pub fn write_brotli<T: Serialize, W: Write>(value: &T, writer: W) -> io::Result<()> {
+    let mut encoder =
+        brotli::CompressorWriter::new(writer, BROTLI_BUFFER_SIZE, BROTLI_QUALITY, BROTLI_WINDOW);
+    serde_json::to_writer(&mut encoder, value)?;
+    encoder.flush()?;
+    encoder.into_inner();
+    Ok(())
+}
+
+#[cfg(feature = "brotli")]
+pub fn read_brotli<T: DeserializeOwned, R: Read>(reader: R) -> io::Result<T> {
+    let decoder = brotli::Decompressor::new(reader, BROTLI_BUFFER_SIZE);
+    Ok(serde_json::from_reader(io::BufReader::new(decoder))?)
+}
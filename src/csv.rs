@@ -0,0 +1,8 @@
+// Quotes a CSV field when it holds a separator, quote or line break
+pub(crate) fn field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
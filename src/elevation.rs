@@ -0,0 +1,155 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{track::Track, TrackSpline};
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Drop {
+    pub crest_distance: f64,
+    pub crest_height: f64,
+    pub valley_distance: f64,
+    pub valley_height: f64,
+    pub height: f64,
+    // Along the track, crest to valley
+    pub length: f64,
+    pub max_speed: f64,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Hill {
+    pub crest_distance: f64,
+    pub crest_height: f64,
+    // Rise from the valley before the crest
+    pub height: f64,
+    pub crest_speed: f64,
+}
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ElevationReport {
+    // Largest first
+    pub drops: Vec<Drop>,
+    pub hills: Vec<Hill>,
+}
+
+#[derive(Clone, Copy)]
+struct Extremum {
+    index: usize,
+    crest: bool,
+}
+
+impl TrackSpline {
+    // Height changes smaller than min_height are treated as noise and merged into their neighbours
+    pub fn elevation_changes(&self, min_height: f64) -> ElevationReport {
+        let mut report = ElevationReport::default();
+        if self.points.len() < 2 {
+            return report;
+        }
+
+        let distances = self.cumulative_distances();
+        let height = |i: usize| self.points[i].pos.y;
+        let mut extrema = Vec::new();
+        let (mut high, mut low) = (0, 0);
+        let mut rising = None;
+        for i in 1..self.points.len() {
+            if height(i) > height(high) {
+                high = i;
+            }
+            if height(i) < height(low) {
+                low = i;
+            }
+            match rising {
+                // A drift smaller than min_height before the first real change still counts
+                // toward it, so it starts from the lowest or highest point so far
+                None if height(high) - height(0) >= min_height => {
+                    extrema.push(Extremum {
+                        index: low,
+                        crest: false,
+                    });
+                    rising = Some(true);
+                    low = i;
+                }
+                None if height(0) - height(low) >= min_height => {
+                    extrema.push(Extremum {
+                        index: high,
+                        crest: true,
+                    });
+                    rising = Some(false);
+                    high = i;
+                }
+                Some(true) if height(high) - height(i) >= min_height => {
+                    extrema.push(Extremum {
+                        index: high,
+                        crest: true,
+                    });
+                    rising = Some(false);
+                    low = i;
+                }
+                Some(false) if height(i) - height(low) >= min_height => {
+                    extrema.push(Extremum {
+                        index: low,
+                        crest: false,
+                    });
+                    rising = Some(true);
+                    high = i;
+                }
+                _ => {}
+            }
+        }
+        match rising {
+            Some(true) => extrema.push(Extremum {
+                index: high,
+                crest: true,
+            }),
+            Some(false) => extrema.push(Extremum {
+                index: low,
+                crest: false,
+            }),
+            None => {}
+        }
+
+        for pair in extrema.windows(2) {
+            let (start, end) = (pair[0], pair[1]);
+            if start.crest {
+                report.drops.push(Drop {
+                    crest_distance: distances[start.index],
+                    crest_height: height(start.index),
+                    valley_distance: distances[end.index],
+                    valley_height: height(end.index),
+                    height: height(start.index) - height(end.index),
+                    length: distances[end.index] - distances[start.index],
+                    max_speed: self.points[start.index..=end.index]
+                        .iter()
+                        .map(|p| p.velocity.abs())
+                        .fold(0.0, f64::max),
+                });
+            } else {
+                report.hills.push(Hill {
+                    crest_distance: distances[end.index],
+                    crest_height: height(end.index),
+                    height: height(end.index) - height(start.index),
+                    crest_speed: self.points[end.index].velocity.abs(),
+                });
+            }
+        }
+
+        report.drops.sort_by(|a, b| b.height.total_cmp(&a.height));
+        report.hills.sort_by(|a, b| b.height.total_cmp(&a.height));
+        report
+    }
+}
+
+impl Track {
+    pub fn elevation_changes(&self, min_height: f64) -> ElevationReport {
+        let (spline, _) = self.get_spline();
+        spline.elevation_changes(min_height)
+    }
+}
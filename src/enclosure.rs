@@ -0,0 +1,77 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{track::Track, TrackSpline};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum EnclosureKind {
+    #[default]
+    Tunnel,
+    // Show buildings and other covered stretches above ground
+    Building,
+}
+
+// Range of distance along the whole track, in meters
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Enclosure {
+    pub start: f64,
+    pub end: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub kind: EnclosureKind,
+}
+
+impl Enclosure {
+    pub fn contains(&self, distance: f64) -> bool {
+        self.start.min(self.end) <= distance && distance <= self.start.max(self.end)
+    }
+}
+
+impl Track {
+    pub fn is_enclosed(&self, distance: f64) -> bool {
+        self.enclosures.iter().any(|e| e.contains(distance))
+    }
+
+    // Overlapping enclosures only count once, and only the part on a track of `length`
+    pub fn enclosed_length(&self, length: f64) -> f64 {
+        let mut ranges: Vec<(f64, f64)> = self
+            .enclosures
+            .iter()
+            .map(|e| {
+                let (start, end) = (e.start.min(e.end), e.start.max(e.end));
+                (start.clamp(0.0, length), end.clamp(0.0, length))
+            })
+            .collect();
+        ranges.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut total = 0.0;
+        let mut covered = f64::NEG_INFINITY;
+        for (start, end) in ranges {
+            let start = start.max(covered);
+            if end > start {
+                total += end - start;
+            }
+            covered = covered.max(end);
+        }
+        total
+    }
+
+    pub(crate) fn enclosure_ranges(&self) -> Vec<(f64, f64)> {
+        self.enclosures.iter().map(|e| (e.start, e.end)).collect()
+    }
+}
+
+impl TrackSpline {
+    // Expects the spline to start at the beginning of the track
+    pub fn fill_enclosed(&mut self, track: &Track) {
+        let distances = self.cumulative_distances();
+        for (point, distance) in self.points.iter_mut().zip(distances) {
+            point.enclosed = Some(track.is_enclosed(distance));
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TrackError {
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    EmptySections,
+    InvalidConfig {
+        field: &'static str,
+        value: f64,
+    },
+    // The train comes to a stop before the end of the track and can't enter `section`
+    Stalled {
+        section: usize,
+    },
+    // The history has no revision by that index, or it can't be rebuilt
+    MissingRevision {
+        revision: usize,
+    },
+    // The trigger's section isn't on the track or it lands off either end
+    MissingTrigger {
+        name: String,
+    },
+    // Trains roll back off a spike, nothing after it can be reached
+    SpikeNotLast {
+        section: usize,
+    },
+    // A spike carries on in the direction the train comes in, which has to be straight up
+    SpikeEntry {
+        section: usize,
+        pitch: f64,
+    },
+}
+
+impl fmt::Display for TrackError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TrackError::Parse {
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "invalid track JSON at line {line}, column {column}: {message}"
+            ),
+            TrackError::EmptySections => write!(f, "track has no sections"),
+            TrackError::InvalidConfig { field, value } => {
+                write!(f, "invalid config value {value} for {field}")
+            }
+            TrackError::Stalled { section } => write!(f, "train stalls before section {section}"),
+            TrackError::MissingRevision { revision } => {
+                write!(f, "track has no revision {revision}")
+            }
+            TrackError::MissingTrigger { name } => {
+                write!(f, "trigger {name} isn't on the track")
+            }
+            TrackError::SpikeNotLast { section } => {
+                write!(f, "spike at section {section} isn't the last section")
+            }
+            TrackError::SpikeEntry { section, pitch } => write!(
+                f,
+                "spike at section {section} starts at {pitch:.1} deg pitch instead of vertical"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TrackError {}
+
+#[cfg(feature = "json")]
+impl From<serde_json::Error> for TrackError {
+    fn from(err: serde_json::Error) -> Self {
+        TrackError::Parse {
+            line: err.line(),
+            column: err.column(),
+            message: err.to_string(),
+        }
+    }
+}
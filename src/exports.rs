@@ -0,0 +1,284 @@
+// The string in, string out layer the web frontend calls through wasm-bindgen
+use serde::Serialize;
+
+use crate::{
+    error, format,
+    hotspots::HotspotOptions,
+    imu, mesh, nl2,
+    normalize::NormalizeOptions,
+    output::OutputOptions,
+    stats,
+    track::Track,
+    transitions::{Channel, Transitions},
+    triggers, validation, TrackSpline,
+};
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+// Errors reach JavaScript as exceptions instead of aborting the module
+#[cfg(target_arch = "wasm32")]
+type ExportError = JsError;
+#[cfg(not(target_arch = "wasm32"))]
+type ExportError = error::TrackError;
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, ExportError> {
+    let json = serde_json::to_string(value).map_err(error::TrackError::from)?;
+    Ok(json)
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn get_spline(track_json: &str) -> Result<String, ExportError> {
+    #[cfg(target_arch = "wasm32")]
+    console_error_panic_hook::set_once();
+
+    let track = Track::from_json(track_json)?;
+    to_json(&track.try_get_spline()?)
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn get_spline_with_options(
+    track_json: &str,
+    options_json: &str,
+) -> Result<String, ExportError> {
+    #[cfg(target_arch = "wasm32")]
+    console_error_panic_hook::set_once();
+
+    let track = Track::from_json(track_json)?;
+    let options = parse::<OutputOptions>(options_json)?;
+    let (spline, section_start) = track.try_get_spline_with_options(&options)?;
+    to_json(&(spline.masked(&options), section_start))
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn get_spline_with_sections(
+    track_json: &str,
+    options_json: &str,
+) -> Result<String, ExportError> {
+    #[cfg(target_arch = "wasm32")]
+    console_error_panic_hook::set_once();
+
+    let track = Track::from_json(track_json)?;
+    let options = parse::<OutputOptions>(options_json)?;
+    let spline = track.try_get_spline_with_sections(&options)?;
+    to_json(&spline.masked(&options))
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn get_spline_with_timings(
+    track_json: &str,
+    options_json: &str,
+) -> Result<String, ExportError> {
+    #[cfg(target_arch = "wasm32")]
+    console_error_panic_hook::set_once();
+
+    let track = Track::from_json(track_json)?;
+    let options = parse::<OutputOptions>(options_json)?;
+    let (spline, section_start, timings) = track.try_get_spline_with_timings(&options)?;
+    to_json(&(spline.masked(&options), section_start, timings))
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn get_animations(track_json: &str) -> Result<String, ExportError> {
+    #[cfg(target_arch = "wasm32")]
+    console_error_panic_hook::set_once();
+
+    let track = Track::from_json(track_json)?;
+    track.check()?;
+    to_json(&track.animations())
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn preview_transitions(
+    transitions_json: &str,
+    channel_json: &str,
+    n_samples: usize,
+) -> Result<String, ExportError> {
+    #[cfg(target_arch = "wasm32")]
+    console_error_panic_hook::set_once();
+
+    let transitions = parse::<Transitions>(transitions_json)?;
+    let channel = parse::<Channel>(channel_json)?;
+    to_json(&transitions.preview(channel, n_samples))
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn get_stats(track_json: &str) -> Result<String, ExportError> {
+    #[cfg(target_arch = "wasm32")]
+    console_error_panic_hook::set_once();
+
+    let track = Track::from_json(track_json)?;
+    to_json(&track.stats()?)
+}
+
+// Worst first
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn get_hotspots(track_json: &str, options_json: &str) -> Result<String, ExportError> {
+    #[cfg(target_arch = "wasm32")]
+    console_error_panic_hook::set_once();
+
+    let track = Track::from_json(track_json)?;
+    let options = parse::<HotspotOptions>(options_json)?;
+    track.check()?;
+    to_json(&track.hotspots(&options))
+}
+
+// `tracks_json` is an array of tracks, compared in that order
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn compare_tracks(tracks_json: &str, options_json: &str) -> Result<String, ExportError> {
+    #[cfg(target_arch = "wasm32")]
+    console_error_panic_hook::set_once();
+
+    let tracks = parse::<Vec<Track>>(tracks_json)?;
+    let options = parse::<NormalizeOptions>(options_json)?;
+    let mut splines = Vec::with_capacity(tracks.len());
+    for track in &tracks {
+        splines.push(track.try_get_spline()?.0);
+    }
+    to_json(&TrackSpline::compare(&splines, &options))
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn get_revision_spline(track_json: &str, revision: usize) -> Result<String, ExportError> {
+    #[cfg(target_arch = "wasm32")]
+    console_error_panic_hook::set_once();
+
+    let track = Track::from_json(track_json)?;
+    to_json(&track.revision(revision)?.try_get_spline()?)
+}
+
+// As hex strings, JSON numbers can't hold all 64 bits
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn get_section_hashes(track_json: &str) -> Result<String, ExportError> {
+    #[cfg(target_arch = "wasm32")]
+    console_error_panic_hook::set_once();
+
+    let track = Track::from_json(track_json)?;
+    track.check()?;
+    let hashes: Vec<String> = track
+        .section_hashes()
+        .iter()
+        .map(|hash| format!("{hash:016x}"))
+        .collect();
+    to_json(&hashes)
+}
+
+// The stall as JSON, or null if the train makes it to the end
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn get_stall(track_json: &str) -> Result<String, ExportError> {
+    #[cfg(target_arch = "wasm32")]
+    console_error_panic_hook::set_once();
+
+    let track = Track::from_json(track_json)?;
+    track.check()?;
+    to_json(&track.try_make_splines().stall())
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn validate(track_json: &str) -> Result<String, ExportError> {
+    #[cfg(target_arch = "wasm32")]
+    console_error_panic_hook::set_once();
+
+    let track = Track::from_json(track_json)?;
+    track.check()?;
+    to_json(&track.health())
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn check_envelope(track_json: &str, limits_json: &str) -> Result<String, ExportError> {
+    #[cfg(target_arch = "wasm32")]
+    console_error_panic_hook::set_once();
+
+    let track = Track::from_json(track_json)?;
+    let limits = parse::<validation::EnvelopeLimits>(limits_json)?;
+    track.check()?;
+    to_json(&track.check_envelope(&limits))
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn format_stats(stats_json: &str, options_json: &str) -> Result<String, ExportError> {
+    #[cfg(target_arch = "wasm32")]
+    console_error_panic_hook::set_once();
+
+    let stats = parse::<stats::TrackStats>(stats_json)?;
+    let options = parse::<format::FormatOptions>(options_json)?;
+    to_json(&stats.format(&options))
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn get_imu(track_json: &str, options_json: &str) -> Result<String, ExportError> {
+    #[cfg(target_arch = "wasm32")]
+    console_error_panic_hook::set_once();
+
+    let track = Track::from_json(track_json)?;
+    let options = parse::<imu::ImuOptions>(options_json)?;
+    let (spline, _) = track.try_get_spline()?;
+    to_json(&spline.imu(&options))
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn get_nl2_csv(track_json: &str, options_json: &str) -> Result<String, ExportError> {
+    #[cfg(target_arch = "wasm32")]
+    console_error_panic_hook::set_once();
+
+    let track = Track::from_json(track_json)?;
+    let options = parse::<nl2::Nl2CsvOptions>(options_json)?;
+    let (spline, _) = track.try_get_spline()?;
+    // Writing to memory can't fail and the CSV is plain ASCII
+    let mut csv = Vec::new();
+    spline.write_nl2_csv(&options, &mut csv).unwrap();
+    Ok(String::from_utf8(csv).unwrap())
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn get_trigger_manifest(track_json: &str) -> Result<String, ExportError> {
+    #[cfg(target_arch = "wasm32")]
+    console_error_panic_hook::set_once();
+
+    let track = Track::from_json(track_json)?;
+    to_json(&track.trigger_manifest()?)
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn get_trigger_csv(track_json: &str) -> Result<String, ExportError> {
+    #[cfg(target_arch = "wasm32")]
+    console_error_panic_hook::set_once();
+
+    let track = Track::from_json(track_json)?;
+    let placements = track.trigger_manifest()?;
+    // Writing to memory can't fail, names are the only text and come from valid JSON
+    let mut csv = Vec::new();
+    triggers::write_csv(&placements, &mut csv).unwrap();
+    Ok(String::from_utf8(csv).unwrap())
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn get_glb(track_json: &str, options_json: &str) -> Result<Vec<u8>, ExportError> {
+    #[cfg(target_arch = "wasm32")]
+    console_error_panic_hook::set_once();
+
+    let track = Track::from_json(track_json)?;
+    track.check()?;
+    let options = parse::<mesh::MeshOptions>(options_json)?;
+    let mut glb = Vec::new();
+    track.mesh(&options).write_glb(&mut glb).unwrap();
+    Ok(glb)
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn get_obj(track_json: &str, options_json: &str) -> Result<String, ExportError> {
+    #[cfg(target_arch = "wasm32")]
+    console_error_panic_hook::set_once();
+
+    let track = Track::from_json(track_json)?;
+    track.check()?;
+    let options = parse::<mesh::MeshOptions>(options_json)?;
+    let mut obj = Vec::new();
+    track.mesh(&options).write_obj(&mut obj).unwrap();
+    Ok(String::from_utf8(obj).unwrap())
+}
+
+fn parse<T: serde::de::DeserializeOwned>(json: &str) -> Result<T, ExportError> {
+    let value = serde_json::from_str(json).map_err(error::TrackError::from)?;
+    Ok(value)
+}
@@ -0,0 +1,135 @@
+use glam::DVec3;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{math::WrapperDQuat, track::Track, TrackPoint, TrackSpline};
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FabricationOptions {
+    // Meters
+    pub max_length: f64,
+    // Segments are cut no shorter than this unless the track ends first
+    pub min_length: f64,
+}
+
+impl Default for FabricationOptions {
+    fn default() -> Self {
+        Self {
+            max_length: 12.0,
+            min_length: 6.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Frame {
+    #[cfg_attr(feature = "ts", ts(type = "[number, number, number]"))]
+    pub pos: DVec3,
+    #[cfg_attr(feature = "ts", ts(type = "[number, number, number, number]"))]
+    pub rot: WrapperDQuat,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FabricationSegment {
+    // Distance along the spline
+    pub start: f64,
+    pub end: f64,
+    // World space flanges at either end, facing along the track
+    pub start_flange: Frame,
+    pub end_flange: Frame,
+    // Points relative to the start flange, so every segment can be built on its own jig
+    pub geometry: Vec<Frame>,
+}
+
+impl From<&TrackPoint> for Frame {
+    fn from(point: &TrackPoint) -> Self {
+        Self {
+            pos: point.pos,
+            rot: point.rot,
+        }
+    }
+}
+
+fn local_frame(origin: &TrackPoint, point: &TrackPoint) -> Frame {
+    let inverse = origin.rot.0.inverse();
+    Frame {
+        pos: inverse * (point.pos - origin.pos),
+        rot: (inverse * point.rot.0).into(),
+    }
+}
+
+// Bend in radians per meter between a point and the one after it
+fn curvatures(points: &[TrackPoint]) -> Vec<f64> {
+    let mut curvatures: Vec<f64> = points
+        .windows(2)
+        .map(|pair| {
+            let spacing = (pair[1].pos - pair[0].pos).length();
+            let bend = (pair[0].rot.0 * DVec3::Z).angle_between(pair[1].rot.0 * DVec3::Z);
+            if spacing > 0.0 {
+                bend / spacing
+            } else {
+                0.0
+            }
+        })
+        .collect();
+    curvatures.push(curvatures.last().copied().unwrap_or_default());
+    curvatures
+}
+
+impl TrackSpline {
+    // Cuts wherever the track is straightest between `min_length` and `max_length` into
+    // each segment, so flange joints stay out of tight curves
+    pub fn fabrication_segments(&self, options: &FabricationOptions) -> Vec<FabricationSegment> {
+        let points = &self.points;
+        let mut segments = Vec::new();
+        if points.len() < 2 || options.max_length <= 0.0 {
+            return segments;
+        }
+        let distances = self.cumulative_distances();
+        let curvatures = curvatures(points);
+        let total = *distances.last().unwrap();
+
+        let mut start = 0;
+        while start < points.len() - 1 {
+            let end = if total - distances[start] <= options.max_length {
+                points.len() - 1
+            } else {
+                let min = distances[start] + options.min_length.min(options.max_length);
+                let max = distances[start] + options.max_length;
+                (start + 1..points.len())
+                    .take_while(|&i| distances[i] <= max)
+                    .filter(|&i| distances[i] >= min)
+                    .min_by(|&a, &b| curvatures[a].total_cmp(&curvatures[b]).then(b.cmp(&a)))
+                    .unwrap_or(start + 1)
+            };
+
+            segments.push(FabricationSegment {
+                start: distances[start],
+                end: distances[end],
+                start_flange: (&points[start]).into(),
+                end_flange: (&points[end]).into(),
+                geometry: points[start..=end]
+                    .iter()
+                    .map(|p| local_frame(&points[start], p))
+                    .collect(),
+            });
+            start = end;
+        }
+        segments
+    }
+}
+
+impl Track {
+    pub fn fabrication_segments(&self, options: &FabricationOptions) -> Vec<FabricationSegment> {
+        TrackSpline::concat(&self.make_splines()).fabrication_segments(options)
+    }
+}
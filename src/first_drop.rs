@@ -0,0 +1,191 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    math::euler,
+    track::{Track, TrackSection},
+    transitions::{Forces, RollMode, Transition, TransitionCurve, TransitionDomain, Transitions},
+    TrackPoint,
+};
+
+const ITERATIONS: usize = 30;
+// Seconds, longest any one hold is searched up to
+const MAX_HOLD: f64 = 10.0;
+// How close the solved drop has to come before it's returned
+const PITCH_TOLERANCE: f64 = 1.0;
+const HEIGHT_TOLERANCE: f64 = 0.5;
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct DropDesign {
+    // Meters from the end of the track down to where the pullout ends
+    pub height: f64,
+    // G at the bottom of the pullout
+    pub peak_vert: f64,
+    // Degrees above level the train leaves at, negative still heading down
+    pub exit_pitch: f64,
+    // Degrees below level the straight part of the drop is at
+    pub angle: f64,
+    // G over the crest, has to be under the cosine of `angle` for the train to pitch down
+    // that far
+    pub crest_vert: f64,
+    // Seconds each change of vertical g takes
+    pub ramp: f64,
+}
+
+impl Default for DropDesign {
+    fn default() -> Self {
+        Self {
+            height: 40.0,
+            peak_vert: 3.5,
+            exit_pitch: 0.0,
+            angle: 60.0,
+            crest_vert: 0.2,
+            ramp: 0.6,
+        }
+    }
+}
+
+fn ramp(value: f64, length: f64) -> Transition {
+    Transition {
+        curve: TransitionCurve::Sinusoidal,
+        value,
+        length,
+        center: 0.0,
+        tension: 0.0,
+    }
+}
+
+fn hold(length: f64) -> Transition {
+    Transition {
+        curve: TransitionCurve::Linear,
+        value: 0.0,
+        length,
+        center: 0.0,
+        tension: 0.0,
+    }
+}
+
+fn force_section(vert: Vec<Transition>, lat: f64) -> TrackSection {
+    // Zero length transitions evaluate to NaN
+    let vert: Vec<Transition> = vert.into_iter().filter(|t| t.length > 1e-9).collect();
+    let length = vert.iter().map(|t| t.length).sum();
+    TrackSection::Force {
+        fixed_speed: None,
+        transitions: Transitions {
+            vert,
+            lat: vec![Transition {
+                value: lat,
+                ..hold(length)
+            }],
+            roll: vec![hold(length)],
+        },
+        domain: TransitionDomain::Time,
+        roll_mode: RollMode::Rate,
+    }
+}
+
+// Smallest hold up to MAX_HOLD where `reached` turns true. The upper bound grows from a
+// short hold so that a loop carried too far round, which reads as not there yet again,
+// isn't picked up first
+pub(crate) fn bisect(mut reached: impl FnMut(f64) -> Option<bool>) -> Option<f64> {
+    if reached(0.0)? {
+        return Some(0.0);
+    }
+    let (mut low, mut high) = (0.0, 0.125);
+    while !reached(high)? {
+        if high >= MAX_HOLD {
+            return None;
+        }
+        (low, high) = (high, 2.0 * high);
+    }
+    for _ in 0..ITERATIONS {
+        let mid = 0.5 * (low + high);
+        if reached(mid)? {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+    Some(high)
+}
+
+impl Track {
+    fn simulate(
+        &self,
+        start: TrackPoint,
+        forces: Forces,
+        vert: Vec<Transition>,
+    ) -> Option<TrackPoint> {
+        let section = force_section(vert, 0.0);
+        self.make_spline(&section, start, forces)
+            .points
+            .last()
+            .copied()
+    }
+
+    // A force section to add to the end of the track that pitches over the crest, drops
+    // `height` at `angle` and pulls out at `peak_vert` to leave at `exit_pitch`. The three
+    // holds are solved one after the other by bisection on a simulation of that part, None
+    // if the drop can't be made with these numbers
+    pub fn first_drop(&self, design: &DropDesign) -> Option<TrackSection> {
+        let (start, forces) = self.end_state();
+        let dive = libm::cos(design.angle.to_radians());
+        let exit = libm::cos(design.exit_pitch.to_radians());
+        let r = design.ramp;
+        let crest_vert = |crest| {
+            vec![
+                ramp(design.crest_vert - forces.vert, r),
+                hold(crest),
+                ramp(dive - design.crest_vert, r),
+            ]
+        };
+        let pull_vert = |pull| {
+            vec![
+                ramp(design.peak_vert - dive, r),
+                hold(pull),
+                ramp(exit - design.peak_vert, r),
+            ]
+        };
+        let level = Forces {
+            vert: dive,
+            ..forces
+        };
+
+        // Over the crest until the train points down at the drop angle
+        let crest = bisect(|crest| {
+            let end = self.simulate(start, forces, crest_vert(crest))?;
+            Some(euler(&end).1 <= -design.angle)
+        })?;
+        let crest_end = self.simulate(start, forces, crest_vert(crest))?;
+
+        // Long enough down the drop that the pullout ends at the right height
+        let bottom = start.pos.y - design.height;
+        let pullout = |straight: f64| {
+            let dive_end = if straight > 0.0 {
+                self.simulate(crest_end, level, vec![hold(straight)])?
+            } else {
+                crest_end
+            };
+            let pull = bisect(|pull| {
+                let end = self.simulate(dive_end, level, pull_vert(pull))?;
+                Some(euler(&end).1 >= design.exit_pitch)
+            })?;
+            Some((pull, self.simulate(dive_end, level, pull_vert(pull))?))
+        };
+        let straight = bisect(|straight| Some(pullout(straight)?.1.pos.y <= bottom))?;
+        let (pull, _) = pullout(straight)?;
+
+        let mut vert = crest_vert(crest);
+        vert.push(hold(straight));
+        vert.extend(pull_vert(pull));
+        let section = force_section(vert, -forces.lat);
+
+        let end = *self.make_spline(&section, start, forces).points.last()?;
+        ((euler(&end).1 - design.exit_pitch).abs() < PITCH_TOLERANCE
+            && (end.pos.y - bottom).abs() < HEIGHT_TOLERANCE)
+            .then_some(section)
+    }
+}
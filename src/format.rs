@@ -0,0 +1,154 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::stats::TrackStats;
+
+const FEET_PER_METER: f64 = 3.280839895;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum LengthUnit {
+    #[default]
+    Meters,
+    Feet,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum SpeedUnit {
+    MetersPerSecond,
+    #[default]
+    KilometersPerHour,
+    MilesPerHour,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Quantity {
+    // Meters
+    Length,
+    // Meters per second
+    Speed,
+    // Seconds
+    Duration,
+    // g
+    Force,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FormatOptions {
+    pub length_unit: LengthUnit,
+    pub speed_unit: SpeedUnit,
+    // Digits after the decimal separator
+    pub precision: usize,
+    pub decimal_separator: char,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub thousands_separator: Option<char>,
+    // Appends the unit symbol, e.g. "12.5 m"
+    pub units: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            length_unit: LengthUnit::Meters,
+            speed_unit: SpeedUnit::KilometersPerHour,
+            precision: 1,
+            decimal_separator: '.',
+            thousands_separator: None,
+            units: true,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FormattedStats {
+    pub length: String,
+    pub duration: String,
+    pub max_speed: String,
+    pub min_height: String,
+    pub max_height: String,
+    pub enclosed_length: String,
+    pub max_vert: String,
+    pub min_vert: String,
+    pub max_lat: String,
+}
+
+impl FormatOptions {
+    pub fn format(&self, value: f64, quantity: Quantity) -> String {
+        let (value, symbol) = match quantity {
+            Quantity::Length => match self.length_unit {
+                LengthUnit::Meters => (value, "m"),
+                LengthUnit::Feet => (value * FEET_PER_METER, "ft"),
+            },
+            Quantity::Speed => match self.speed_unit {
+                SpeedUnit::MetersPerSecond => (value, "m/s"),
+                SpeedUnit::KilometersPerHour => (value * 3.6, "km/h"),
+                SpeedUnit::MilesPerHour => (value * 3.6 / 1.609344, "mph"),
+            },
+            Quantity::Duration => (value, "s"),
+            Quantity::Force => (value, "g"),
+        };
+
+        let number = self.number(value);
+        if self.units {
+            format!("{number} {symbol}")
+        } else {
+            number
+        }
+    }
+
+    pub fn number(&self, value: f64) -> String {
+        if !value.is_finite() {
+            return value.to_string();
+        }
+        let fixed = format!("{:.*}", self.precision, value.abs());
+        let (whole, fraction) = fixed.split_once('.').unwrap_or((&fixed, ""));
+
+        let mut out = String::new();
+        // Rounding can turn a tiny negative number into zero, which shouldn't keep its sign
+        if value < 0.0 && fixed.chars().any(|c| c.is_ascii_digit() && c != '0') {
+            out.push('-');
+        }
+        for (i, digit) in whole.chars().enumerate() {
+            if i > 0 && (whole.len() - i) % 3 == 0 {
+                out.extend(self.thousands_separator);
+            }
+            out.push(digit);
+        }
+        if !fraction.is_empty() {
+            out.push(self.decimal_separator);
+            out.push_str(fraction);
+        }
+        out
+    }
+}
+
+impl TrackStats {
+    pub fn format(&self, options: &FormatOptions) -> FormattedStats {
+        FormattedStats {
+            length: options.format(self.total.length, Quantity::Length),
+            duration: options.format(self.total.duration, Quantity::Duration),
+            max_speed: options.format(self.total.max_speed, Quantity::Speed),
+            min_height: options.format(self.total.lowest.y, Quantity::Length),
+            max_height: options.format(self.total.highest.y, Quantity::Length),
+            enclosed_length: options.format(self.enclosed_length, Quantity::Length),
+            max_vert: options.format(self.total.max_vert, Quantity::Force),
+            min_vert: options.format(self.total.min_vert, Quantity::Force),
+            max_lat: options.format(self.total.max_lat, Quantity::Force),
+        }
+    }
+}
@@ -0,0 +1,295 @@
+use glam::DVec3;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    brakes::BrakeType,
+    constants::G,
+    math::euler,
+    preset::{ConfigPreset, ForceLimits},
+    roughness::Dice,
+    track::{Track, TrackConfig, TrackSection},
+    transitions::{Forces, RollMode, Transition, TransitionCurve, TransitionDomain, Transitions},
+    TrackPoint, TrackSpline,
+};
+
+const STATION_SPEED: f64 = 4.0;
+// Slowest the train may get outside of the station, lift and final brake
+const MIN_SPEED: f64 = 3.0;
+const ATTEMPTS: usize = 12;
+// m/s^2
+const BRAKE_DECELERATION: f64 = 4.0;
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct LayoutConstraints {
+    pub limits: ForceLimits,
+    pub min_height: f64,
+    pub max_height: f64,
+    pub min_length: f64,
+    pub max_length: f64,
+}
+
+impl Default for LayoutConstraints {
+    fn default() -> Self {
+        Self {
+            limits: ConfigPreset::SteelSitDown.values().limits,
+            min_height: 0.0,
+            max_height: 50.0,
+            min_length: 400.0,
+            max_length: 1200.0,
+        }
+    }
+}
+
+struct Builder<'a> {
+    track: Track,
+    constraints: &'a LayoutConstraints,
+    end: TrackPoint,
+    forces: Forces,
+    length: f64,
+}
+
+impl Builder<'_> {
+    // Appends the sections if the train gets through all of them within the constraints
+    fn try_push(&mut self, sections: Vec<TrackSection>, min_speed: f64) -> bool {
+        let (mut end, mut forces, mut length) = (self.end, self.forces, self.length);
+        for section in &sections {
+            let spline = self.track.make_spline(section, end, forces);
+            let (Some(last), true) = (spline.points.last(), spline.points.len() > 1) else {
+                return false;
+            };
+            length += spline.total_distance();
+            if last.velocity < min_speed
+                || length > self.constraints.max_length
+                || !self.within_limits(&spline, section.is_powered())
+            {
+                return false;
+            }
+            end = *last;
+            forces = spline
+                .forces(spline.total_distance() - 0.005)
+                .unwrap_or(forces);
+        }
+
+        self.track.sections.extend(sections);
+        (self.end, self.forces, self.length) = (end, forces, length);
+        true
+    }
+
+    fn within_limits(&self, spline: &TrackSpline, powered: bool) -> bool {
+        let limits = &self.constraints.limits;
+        let heights_ok = spline.points.iter().all(|p| {
+            p.pos.y >= self.constraints.min_height && p.pos.y <= self.constraints.max_height
+        });
+        // Forces on powered sections are the designer's problem, not worth rejecting a lift over
+        heights_ok
+            && (powered
+                || spline.analysis(1.0).iter().all(|s| {
+                    s.vert >= limits.min_vert
+                        && s.vert <= limits.max_vert
+                        && s.lat.abs() <= limits.max_lat
+                }))
+    }
+
+    fn pitch(&self) -> f64 {
+        euler(&self.end).1
+    }
+}
+
+fn curve(radius: f64, direction: f64, angle: f64, fixed_speed: Option<f64>) -> TrackSection {
+    TrackSection::Curved {
+        fixed_speed,
+        radius,
+        direction,
+        angle,
+        banking: None,
+        spiral: 0.0,
+    }
+}
+
+fn level(builder: &Builder) -> Option<TrackSection> {
+    let pitch = builder.pitch();
+    (pitch.abs() > 0.5).then(|| {
+        curve(
+            60.0,
+            if pitch > 0.0 { 180.0 } else { 0.0 },
+            pitch.abs(),
+            None,
+        )
+    })
+}
+
+fn transition(curve: TransitionCurve, value: f64, length: f64) -> Transition {
+    Transition {
+        curve,
+        value,
+        length,
+        center: 0.0,
+        tension: 0.0,
+    }
+}
+
+fn element(dice: &mut Dice, builder: &Builder) -> Vec<TrackSection> {
+    let speed = builder.end.velocity;
+    let limits = &builder.constraints.limits;
+    match (dice.roll() * 4.0) as u32 {
+        // Airtime hill, the crest radius keeps vertical g above the minimum
+        0 => {
+            let angle = dice.range(10.0, 35.0);
+            let crest = dice.range(limits.min_vert.max(0.0), 0.8);
+            let radius = (speed * speed / (G * (1.0 - crest))).max(10.0);
+            vec![
+                curve(radius, 0.0, angle, None),
+                curve(radius, 180.0, 2.0 * angle, None),
+                curve(radius, 0.0, angle, None),
+            ]
+        }
+        // Dip, pulling out at a positive g the rider is allowed
+        1 => {
+            let angle = dice.range(10.0, 30.0);
+            let pull = dice.range(2.0, (limits.max_vert - 0.5).max(2.0));
+            let radius = (speed * speed / (G * (pull - 1.0))).max(10.0);
+            vec![
+                curve(radius * 2.0, 180.0, angle, None),
+                curve(radius, 0.0, 2.0 * angle, None),
+                curve(radius * 2.0, 180.0, angle, None),
+            ]
+        }
+        // Banked turn, rolled in and out with a smooth roll rate
+        2 => {
+            let bank = dice.range(30.0, 80.0) * if dice.roll() < 0.5 { -1.0 } else { 1.0 };
+            let roll_time = dice.range(1.0, 2.0);
+            let hold_time = dice.range(0.5, 3.0);
+            let rate = TransitionCurve::Sinusoidal;
+            // The average of the sinusoidal ramp up and down is half its peak
+            let peak = 2.0 * bank / roll_time;
+            let total = 2.0 * roll_time + hold_time;
+            let vert = 1.0 / libm::cos(bank.to_radians()) - builder.forces.vert;
+            vec![TrackSection::Force {
+                fixed_speed: None,
+                transitions: Transitions {
+                    vert: vec![
+                        transition(TransitionCurve::Sinusoidal, vert, roll_time),
+                        transition(TransitionCurve::Linear, 0.0, hold_time),
+                        transition(TransitionCurve::Sinusoidal, -vert, roll_time),
+                    ],
+                    lat: vec![transition(
+                        TransitionCurve::Linear,
+                        -builder.forces.lat,
+                        total,
+                    )],
+                    roll: vec![
+                        transition(rate, peak, roll_time / 2.0),
+                        transition(rate, -peak, roll_time / 2.0),
+                        transition(TransitionCurve::Linear, 0.0, hold_time),
+                        transition(rate, -peak, roll_time / 2.0),
+                        transition(rate, peak, roll_time / 2.0),
+                    ],
+                },
+                domain: TransitionDomain::Time,
+                roll_mode: RollMode::Rate,
+            }]
+        }
+        _ => vec![TrackSection::Straight {
+            length: dice.range(5.0, 30.0),
+            fixed_speed: None,
+        }],
+    }
+}
+
+// A random layout of a lift, a first drop, hills, dips and turns and a final brake,
+// always the same for the same seed
+pub fn random_layout(seed: u64, constraints: &LayoutConstraints) -> Track {
+    let mut dice = Dice::new(seed);
+    let mut track = Track {
+        config: TrackConfig::from_preset(ConfigPreset::SteelSitDown),
+        ..Default::default()
+    };
+    track.config.set_limits(constraints.limits);
+    track.anchor.pos = DVec3::new(0.0, constraints.min_height + 1.0, 0.0);
+    track.anchor.velocity = STATION_SPEED;
+
+    let mut builder = Builder {
+        end: track.anchor,
+        track,
+        constraints,
+        forces: Forces::new(1.0, 0.0, 0.0),
+        length: 0.0,
+    };
+
+    let lift_angle = dice.range(30.0, 45.0);
+    let lift_radius = 20.0;
+    let top = dice.range(0.6, 0.95) * constraints.max_height;
+    // Height gained in the two transition curves of the lift
+    let curve_rise = 2.0 * lift_radius * (1.0 - libm::cos(lift_angle.to_radians()));
+    let lift_length =
+        ((top - curve_rise - builder.end.pos.y) / libm::sin(lift_angle.to_radians())).max(5.0);
+    let lift = Some(STATION_SPEED);
+    builder.try_push(
+        vec![
+            TrackSection::Straight {
+                length: 10.0,
+                fixed_speed: lift,
+            },
+            curve(lift_radius, 0.0, lift_angle, lift),
+            TrackSection::Straight {
+                length: lift_length,
+                fixed_speed: lift,
+            },
+            curve(lift_radius, 180.0, lift_angle, lift),
+        ],
+        0.0,
+    );
+
+    // Straight down to just above the ground, pulling out at about 3.5 g at the bottom
+    let drop_angle = dice.range(45.0, 65.0);
+    let crest_radius = 15.0;
+    let drop_height = builder.end.pos.y - constraints.min_height - 2.0;
+    let bottom_speed = (2.0 * G * drop_height).max(0.0).sqrt();
+    let pull_radius = (bottom_speed * bottom_speed / (2.5 * G)).max(15.0);
+    let curve_drop = (crest_radius + pull_radius) * (1.0 - libm::cos(drop_angle.to_radians()));
+    let drop = vec![
+        curve(crest_radius, 180.0, drop_angle, None),
+        TrackSection::Straight {
+            length: ((drop_height - curve_drop) / libm::sin(drop_angle.to_radians())).max(1.0),
+            fixed_speed: None,
+        },
+        curve(pull_radius, 0.0, drop_angle, None),
+    ];
+    if !builder.try_push(drop, MIN_SPEED) {
+        builder.try_push(
+            vec![curve(60.0, 180.0, 20.0, None), curve(60.0, 0.0, 20.0, None)],
+            0.0,
+        );
+    }
+
+    while builder.length < constraints.min_length {
+        let accepted = (0..ATTEMPTS).any(|_| {
+            let sections = element(&mut dice, &builder);
+            if !builder.try_push(sections, MIN_SPEED) {
+                return false;
+            }
+            if let Some(level) = level(&builder) {
+                builder.try_push(vec![level], MIN_SPEED);
+            }
+            true
+        });
+        if !accepted {
+            break;
+        }
+    }
+
+    let speed = builder.end.velocity;
+    let mut track = builder.track;
+    track.sections.push(TrackSection::Brake {
+        length: speed * speed / (2.0 * BRAKE_DECELERATION) + 5.0,
+        deceleration: BRAKE_DECELERATION,
+        exit_speed: 0.0,
+        brake_type: BrakeType::default(),
+        curve: None,
+    });
+    track
+}
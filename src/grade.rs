@@ -0,0 +1,167 @@
+use glam::DVec3;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    track::{Track, TrackSection},
+    TrackPoint, TrackSpline,
+};
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct GradeLimits {
+    // Percent
+    pub max_lift_grade: f64,
+    pub max_transport_grade: f64,
+}
+
+impl Default for GradeLimits {
+    fn default() -> Self {
+        Self {
+            max_lift_grade: 100.0,
+            max_transport_grade: 6.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct GradeSample {
+    pub distance: f64,
+    pub section: usize,
+    // Percent, positive uphill
+    pub grade: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum GradeLimit {
+    Lift,
+    Transport,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct GradeViolation {
+    pub section: usize,
+    pub start: f64,
+    pub end: f64,
+    pub max_grade: f64,
+    pub limit: GradeLimit,
+}
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct GradeReport {
+    pub samples: Vec<GradeSample>,
+    pub max_up: f64,
+    pub max_up_distance: f64,
+    pub max_down: f64,
+    pub max_down_distance: f64,
+    pub violations: Vec<GradeViolation>,
+}
+
+pub fn grade(point: &TrackPoint) -> f64 {
+    let dir = point.rot.0 * DVec3::Z;
+    let horizontal = (dir.x * dir.x + dir.z * dir.z).sqrt().max(1e-9);
+    dir.y / horizontal * 100.0
+}
+
+impl TrackSpline {
+    pub fn grades(&self, step: f64) -> Vec<GradeSample> {
+        grade_samples(self, step, 0, 0.0)
+    }
+}
+
+fn grade_samples(spline: &TrackSpline, step: f64, section: usize, offset: f64) -> Vec<GradeSample> {
+    let mut samples = Vec::new();
+    if step <= 0.0 {
+        return samples;
+    }
+
+    let mut distance = 0.0;
+    let mut next_sample = 0.0;
+    for pair in spline.points.windows(2) {
+        distance += (pair[1].pos - pair[0].pos).length();
+        while next_sample <= distance {
+            samples.push(GradeSample {
+                distance: offset + next_sample,
+                section,
+                grade: grade(&pair[1]),
+            });
+            next_sample += step;
+        }
+    }
+
+    samples
+}
+
+impl Track {
+    pub fn grade_report(&self, step: f64, limits: &GradeLimits) -> GradeReport {
+        let splines = self.make_splines();
+        let mut report = GradeReport::default();
+        let mut offset = 0.0;
+
+        for (i, (section, spline)) in self.sections.iter().zip(&splines).enumerate() {
+            let samples = grade_samples(spline, step, i, offset);
+            offset += spline.total_distance();
+
+            if section.is_powered() {
+                // Chain and cable lifts are built to climb, anything else driving the train
+                // is held to what tyres or a launch can push up
+                let (max_grade, kind) = match section {
+                    TrackSection::Lift { .. } => (limits.max_lift_grade, GradeLimit::Lift),
+                    _ => (limits.max_transport_grade, GradeLimit::Transport),
+                };
+                let mut open: Option<GradeViolation> = None;
+                for sample in &samples {
+                    let abs = sample.grade.abs();
+                    let limit = (abs > max_grade).then_some(kind);
+
+                    match (&mut open, limit) {
+                        (Some(v), Some(limit)) if v.limit == limit => {
+                            v.end = sample.distance;
+                            v.max_grade = v.max_grade.max(abs);
+                        }
+                        (_, limit) => {
+                            report.violations.extend(open.take());
+                            open = limit.map(|limit| GradeViolation {
+                                section: i,
+                                start: sample.distance,
+                                end: sample.distance,
+                                max_grade: abs,
+                                limit,
+                            });
+                        }
+                    }
+                }
+                report.violations.extend(open);
+            }
+
+            report.samples.extend(samples);
+        }
+
+        for sample in &report.samples {
+            if sample.grade > report.max_up {
+                report.max_up = sample.grade;
+                report.max_up_distance = sample.distance;
+            }
+            if -sample.grade > report.max_down {
+                report.max_down = -sample.grade;
+                report.max_down_distance = sample.distance;
+            }
+        }
+
+        report
+    }
+}
@@ -0,0 +1,139 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use glam::DVec3;
+
+use crate::{
+    preset::ConfigPreset,
+    track::{Track, TrackSection},
+    validation::SanityLimits,
+    TrackSpline,
+};
+
+const SAMPLE_STEP: f64 = 0.5;
+// g per second
+const MAX_JERK: f64 = 8.0;
+// Fraction of a section's step its first point may stray from one step on from the end of
+// the section before, with a little slack for steps too short to measure against
+const JOINT_TOLERANCE: f64 = 0.25;
+const JOINT_SLACK: f64 = 1e-3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum HealthCategory {
+    Sanity,
+    Forces,
+    Jerk,
+    Continuity,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct CategoryScore {
+    pub category: HealthCategory,
+    // 0 to 100
+    pub score: f64,
+    pub issues: usize,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct HealthReport {
+    pub score: f64,
+    pub categories: Vec<CategoryScore>,
+}
+
+impl Track {
+    pub fn health(&self) -> HealthReport {
+        let splines = self.make_splines();
+        let spline = TrackSpline::concat(&splines);
+        let samples = spline.analysis(SAMPLE_STEP);
+        let limits = self
+            .config
+            .limits()
+            .unwrap_or(ConfigPreset::SteelSitDown.values().limits);
+
+        let sanity = spline.sanity_check(&SanityLimits::default()).len();
+
+        let outside = samples
+            .iter()
+            .filter(|s| {
+                s.vert < limits.min_vert || s.vert > limits.max_vert || s.lat.abs() > limits.max_lat
+            })
+            .count();
+
+        let jerky = samples
+            .windows(2)
+            .filter(|pair| {
+                let dt = pair[1].time - pair[0].time;
+                dt > 0.0
+                    && ((pair[1].vert - pair[0].vert).abs() / dt > MAX_JERK
+                        || (pair[1].lat - pair[0].lat).abs() / dt > MAX_JERK)
+            })
+            .count();
+
+        let broken = splines
+            .windows(2)
+            .zip(&self.sections[1..])
+            .filter(|(pair, section)| {
+                // These move the train themselves, it's meant to start somewhere else
+                let moved = matches!(
+                    section,
+                    TrackSection::HoldingBrake { .. }
+                        | TrackSection::Tilt { .. }
+                        | TrackSection::Transfer { .. }
+                );
+                !moved && !continues(&pair[0], &pair[1])
+            })
+            .count();
+
+        let total = samples.len().max(1) as f64;
+        let categories = vec![
+            CategoryScore {
+                category: HealthCategory::Sanity,
+                score: (100.0 - 10.0 * sanity as f64).max(0.0),
+                issues: sanity,
+            },
+            CategoryScore {
+                category: HealthCategory::Forces,
+                score: 100.0 * (1.0 - outside as f64 / total),
+                issues: outside,
+            },
+            CategoryScore {
+                category: HealthCategory::Jerk,
+                score: 100.0 * (1.0 - jerky as f64 / total),
+                issues: jerky,
+            },
+            CategoryScore {
+                category: HealthCategory::Continuity,
+                score: 100.0
+                    * (1.0 - broken as f64 / splines.len().saturating_sub(1).max(1) as f64),
+                issues: broken,
+            },
+        ];
+
+        HealthReport {
+            score: categories.iter().map(|c| c.score).sum::<f64>() / categories.len() as f64,
+            categories,
+        }
+    }
+}
+
+// Every section's first point is a step past where the last one ended, so `next` is
+// compared against the end of `prev` carried on by that step
+fn continues(prev: &TrackSpline, next: &TrackSpline) -> bool {
+    let (Some(end), [first, second, ..]) = (prev.points.last(), next.points.as_slice()) else {
+        return true;
+    };
+    let step = (second.pos - first.pos).length();
+    let turn = first.rot.0.angle_between(second.rot.0);
+    let expected = end.pos + end.rot.0 * DVec3::Z * step;
+    (first.pos - expected).length() <= JOINT_TOLERANCE * step + JOINT_SLACK
+        && end.rot.0.angle_between(first.rot.0) <= 2.0 * turn + JOINT_SLACK
+}
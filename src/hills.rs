@@ -0,0 +1,140 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    first_drop::bisect,
+    math::euler,
+    track::{Track, TrackSection},
+    transitions::{RollMode, Transition, TransitionCurve, TransitionDomain, Transitions},
+};
+
+// How close the solved exit has to come to mirroring the entry before it's returned
+const PITCH_TOLERANCE: f64 = 1.0;
+const ROLL_TOLERANCE: f64 = 0.5;
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct HillDesign {
+    // Degrees the train banks to, positive rolls right. An s-hill banks this way first
+    // and then the other
+    pub bank: f64,
+    // G of vertical held over the hill
+    pub vert: f64,
+    // G of lateral held at the bank, flipped along with it on an s-hill
+    pub lat: f64,
+    // Seconds each roll from one bank to the next takes
+    pub roll_time: f64,
+}
+
+impl Default for HillDesign {
+    fn default() -> Self {
+        Self {
+            bank: 70.0,
+            vert: -0.3,
+            lat: 0.4,
+            roll_time: 0.8,
+        }
+    }
+}
+
+fn transition(curve: TransitionCurve, value: f64, length: f64) -> Transition {
+    Transition {
+        curve,
+        value,
+        length,
+        center: 0.0,
+        tension: 0.0,
+    }
+}
+
+impl Track {
+    // A hill over the end of the track that rolls through `sides`, each a multiple of the
+    // design's bank, holding the vertical and lateral at each. The time at each bank is
+    // solved by bisection so the train comes out pitched down as far as it went in pitched
+    // up, then the roll out is trimmed to leave level. None if the train isn't climbing or
+    // doesn't come over with these numbers
+    fn banked_hill(&self, design: &HillDesign, sides: &[f64]) -> Option<TrackSection> {
+        let (start, forces) = self.end_state();
+        let pitch = euler(&start).1;
+        if pitch <= 0.0 {
+            return None;
+        }
+        let exit_vert = libm::cos(pitch.to_radians());
+
+        let r = design.roll_time;
+        let sine = TransitionCurve::Sinusoidal;
+        // `trim` is extra roll on the way out to take out what the train picks up rolling
+        // while it pitches over
+        let section = |hold: f64, trim: f64| {
+            let held = transition(TransitionCurve::Linear, 0.0, hold);
+            let mut roll = Vec::new();
+            let mut lat = Vec::new();
+            let (mut bank, mut side) = (0.0, 0.0);
+            for &next in sides.iter().chain(&[0.0]) {
+                // The average of a sinusoidal ramp up and back down is half its peak
+                let to = if next == 0.0 {
+                    trim
+                } else {
+                    next * design.bank
+                };
+                let peak = 2.0 * (to - bank) / r;
+                roll.extend([
+                    transition(sine, peak, r / 2.0),
+                    transition(sine, -peak, r / 2.0),
+                ]);
+                lat.push(transition(sine, (next - side) * design.lat, r));
+                if next != 0.0 {
+                    roll.push(held);
+                    lat.push(held);
+                }
+                (bank, side) = (next * design.bank, next);
+            }
+            lat[0].value -= forces.lat;
+            // Zero length transitions evaluate to NaN
+            let channel = |transitions: Vec<Transition>| {
+                transitions
+                    .into_iter()
+                    .filter(|t| t.length > 1e-9)
+                    .collect()
+            };
+            let over = sides.len() as f64 * hold + (sides.len() - 1) as f64 * r;
+            TrackSection::Force {
+                fixed_speed: None,
+                transitions: Transitions {
+                    vert: channel(vec![
+                        transition(sine, design.vert - forces.vert, r),
+                        transition(TransitionCurve::Linear, 0.0, over),
+                        transition(sine, exit_vert - design.vert, r),
+                    ]),
+                    lat: channel(lat),
+                    roll: channel(roll),
+                },
+                domain: TransitionDomain::Time,
+                roll_mode: RollMode::Rate,
+            }
+        };
+        let exit = |hold: f64, trim: f64| {
+            let spline = self.make_spline(&section(hold, trim), start, forces);
+            let (_, pitch, roll) = euler(spline.points.last()?);
+            Some((pitch, roll))
+        };
+
+        let hold = bisect(|hold| Some(exit(hold, 0.0)?.0 <= -pitch))?;
+        let trim = -exit(hold, 0.0)?.1;
+        let (exit_pitch, exit_roll) = exit(hold, trim)?;
+        ((exit_pitch + pitch).abs() < PITCH_TOLERANCE && exit_roll.abs() < ROLL_TOLERANCE)
+            .then_some(section(hold, trim))
+    }
+
+    // Banks one way over the hill, the way the design's bank is signed
+    pub fn outerbank_hill(&self, design: &HillDesign) -> Option<TrackSection> {
+        self.banked_hill(design, &[1.0])
+    }
+
+    // Banks one way then rolls through to bank the other way, spending as long at each
+    pub fn s_hill(&self, design: &HillDesign) -> Option<TrackSection> {
+        self.banked_hill(design, &[1.0, -1.0])
+    }
+}
@@ -0,0 +1,209 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    enclosure::Enclosure,
+    error::TrackError,
+    pins::Pin,
+    terrain::Terrain,
+    track::{Track, TrackConfig, TrackSection},
+    triggers::Trigger,
+    TrackPoint,
+};
+
+// One edit to the track, applied on top of the revision before
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        tag = "type",
+        rename_all = "camelCase",
+        rename_all_fields = "camelCase"
+    )
+)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Change {
+    // `removed` sections from `start` on replaced by `inserted`
+    Sections {
+        start: usize,
+        removed: usize,
+        inserted: Vec<TrackSection>,
+    },
+    Config {
+        config: TrackConfig,
+    },
+    Anchor {
+        anchor: TrackPoint,
+    },
+    Pins {
+        pins: Vec<Pin>,
+    },
+    Terrain {
+        terrain: Terrain,
+    },
+    Enclosures {
+        enclosures: Vec<Enclosure>,
+    },
+    Triggers {
+        triggers: Vec<Trigger>,
+    },
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Revision {
+    // Index of the revision this one was made from, None for the first. Several revisions
+    // sharing a parent make the history a tree
+    pub parent: Option<usize>,
+    // Seconds since the Unix epoch
+    pub timestamp: f64,
+    pub label: String,
+    pub changes: Vec<Change>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct History {
+    pub revisions: Vec<Revision>,
+    // The revision the track was last committed as or checked out from
+    pub head: Option<usize>,
+}
+
+impl History {
+    pub fn is_empty(&self) -> bool {
+        self.revisions.is_empty()
+    }
+}
+
+// What it takes to turn `from` into `to`, leaving out the history
+fn changes(from: &Track, to: &Track) -> Vec<Change> {
+    let mut changes = Vec::new();
+
+    let (old, new) = (&from.sections, &to.sections);
+    let start = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let end = old[start..]
+        .iter()
+        .rev()
+        .zip(new[start..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    if start + end < old.len().max(new.len()) {
+        changes.push(Change::Sections {
+            start,
+            removed: old.len() - start - end,
+            inserted: new[start..new.len() - end].to_vec(),
+        });
+    }
+
+    if from.config != to.config {
+        changes.push(Change::Config {
+            config: to.config.clone(),
+        });
+    }
+    if from.anchor != to.anchor {
+        changes.push(Change::Anchor { anchor: to.anchor });
+    }
+    if from.pins != to.pins {
+        changes.push(Change::Pins {
+            pins: to.pins.clone(),
+        });
+    }
+    if from.terrain != to.terrain {
+        changes.push(Change::Terrain {
+            terrain: to.terrain.clone(),
+        });
+    }
+    if from.enclosures != to.enclosures {
+        changes.push(Change::Enclosures {
+            enclosures: to.enclosures.clone(),
+        });
+    }
+    if from.triggers != to.triggers {
+        changes.push(Change::Triggers {
+            triggers: to.triggers.clone(),
+        });
+    }
+    changes
+}
+
+impl Track {
+    // Records the track as it is now as a new revision made from the head, and makes it
+    // the head. Returns its index
+    pub fn commit(&mut self, label: impl Into<String>, timestamp: f64) -> usize {
+        let parent = self.history.head;
+        let base = parent
+            .and_then(|head| self.rebuild(head))
+            .unwrap_or_default();
+        let revision = Revision {
+            parent,
+            timestamp,
+            label: label.into(),
+            changes: changes(&base, self),
+        };
+        self.history.revisions.push(revision);
+        let head = self.history.revisions.len() - 1;
+        self.history.head = Some(head);
+        head
+    }
+
+    // The track as it was at `revision`, with no history of its own
+    pub fn revision(&self, revision: usize) -> Result<Track, TrackError> {
+        self.rebuild(revision)
+            .ok_or(TrackError::MissingRevision { revision })
+    }
+
+    // None if the revision isn't there or its parents don't lead back to the first one
+    fn rebuild(&self, revision: usize) -> Option<Track> {
+        let mut chain = vec![revision];
+        let mut current = revision;
+        while let Some(parent) = self.history.revisions.get(current)?.parent {
+            // Parents always come before their children, which also rules out cycles
+            if parent >= current {
+                return None;
+            }
+            chain.push(parent);
+            current = parent;
+        }
+
+        let mut track = Track::default();
+        for &index in chain.iter().rev() {
+            for change in &self.history.revisions[index].changes {
+                match change.clone() {
+                    Change::Sections {
+                        start,
+                        removed,
+                        inserted,
+                    } => {
+                        let end = start.checked_add(removed)?;
+                        if end > track.sections.len() {
+                            return None;
+                        }
+                        track.sections.splice(start..end, inserted);
+                    }
+                    Change::Config { config } => track.config = config,
+                    Change::Anchor { anchor } => track.anchor = anchor,
+                    Change::Pins { pins } => track.pins = pins,
+                    Change::Terrain { terrain } => track.terrain = terrain,
+                    Change::Enclosures { enclosures } => track.enclosures = enclosures,
+                    Change::Triggers { triggers } => track.triggers = triggers,
+                }
+            }
+        }
+        Some(track)
+    }
+
+    // Replaces the track with `revision` and makes it the head, keeping the history.
+    // Anything not committed is lost
+    pub fn checkout(&mut self, revision: usize) -> Result<(), TrackError> {
+        let track = self.revision(revision)?;
+        let history = std::mem::take(&mut self.history);
+        *self = Track { history, ..track };
+        self.history.head = Some(revision);
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{analysis::AnalysisSample, track::Track, TrackSpline};
+
+// Meters between the samples jerk is taken between
+const SAMPLE_STEP: f64 = 0.5;
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct HotspotOptions {
+    // How many of the worst spots to keep
+    pub count: usize,
+    // G per second of vertical and lateral jerk together a spot starts at
+    pub min_jerk: f64,
+    // G of lateral each side of zero for a spot to count as throwing the rider from one
+    // side to the other
+    pub reversal_lat: f64,
+}
+
+impl Default for HotspotOptions {
+    fn default() -> Self {
+        Self {
+            count: 10,
+            min_jerk: 4.0,
+            reversal_lat: 0.2,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Hotspot {
+    // Meters along the track
+    pub start: f64,
+    pub end: f64,
+    // Where the jerk peaks
+    pub peak: f64,
+    pub start_time: f64,
+    pub duration: f64,
+    // G per second at the peak
+    pub jerk: f64,
+    pub lat_reversal: bool,
+    // What spots are ranked by, the peak jerk doubled for a reversal since snapping from
+    // one side to the other is what throws heads about
+    pub severity: f64,
+}
+
+// Worst first
+fn hotspots(samples: &[AnalysisSample], options: &HotspotOptions) -> Vec<Hotspot> {
+    let mut spots: Vec<Hotspot> = Vec::new();
+    // Spot being built and the lowest and highest lateral through it
+    let mut open: Option<(Hotspot, f64, f64)> = None;
+    let close = |(spot, min_lat, max_lat): (Hotspot, f64, f64)| {
+        let lat_reversal = min_lat <= -options.reversal_lat && max_lat >= options.reversal_lat;
+        Hotspot {
+            lat_reversal,
+            severity: if lat_reversal { 2.0 } else { 1.0 } * spot.jerk,
+            ..spot
+        }
+    };
+    for pair in samples.windows(2) {
+        let (a, b) = (&pair[0], &pair[1]);
+        let dt = b.time - a.time;
+        let jerk = if dt > 0.0 {
+            libm::hypot(b.vert - a.vert, b.lat - a.lat) / dt
+        } else {
+            0.0
+        };
+
+        match (&mut open, jerk >= options.min_jerk) {
+            (Some((spot, min_lat, max_lat)), true) => {
+                spot.end = b.distance;
+                spot.duration = b.time - spot.start_time;
+                if jerk > spot.jerk {
+                    spot.jerk = jerk;
+                    spot.peak = b.distance;
+                }
+                *min_lat = min_lat.min(b.lat);
+                *max_lat = max_lat.max(b.lat);
+            }
+            (None, true) => {
+                let spot = Hotspot {
+                    start: a.distance,
+                    end: b.distance,
+                    peak: b.distance,
+                    start_time: a.time,
+                    duration: dt,
+                    jerk,
+                    ..Default::default()
+                };
+                open = Some((spot, a.lat.min(b.lat), a.lat.max(b.lat)));
+            }
+            (_, false) => spots.extend(open.take().map(close)),
+        }
+    }
+    spots.extend(open.map(close));
+    spots.sort_by(|a, b| b.severity.total_cmp(&a.severity));
+    spots.truncate(options.count);
+    spots
+}
+
+impl TrackSpline {
+    pub fn hotspots(&self, options: &HotspotOptions) -> Vec<Hotspot> {
+        hotspots(&self.analysis(SAMPLE_STEP), options)
+    }
+}
+
+impl Track {
+    // With the track's roughness applied to the samples first
+    pub fn hotspots(&self, options: &HotspotOptions) -> Vec<Hotspot> {
+        hotspots(&self.analysis(SAMPLE_STEP), options)
+    }
+}
@@ -0,0 +1,177 @@
+use std::io::{self, BufRead, Write};
+
+use glam::DVec3;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::G, join::lerp_points, pair_forces, roughness::Dice, track::Track, TrackSpline,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ImuOptions {
+    // Hz
+    pub sample_rate: f64,
+    // Standard deviation of the added white noise, m/s² and rad/s
+    pub accel_noise: f64,
+    pub gyro_noise: f64,
+    pub seed: u64,
+}
+
+impl Default for ImuOptions {
+    fn default() -> Self {
+        Self {
+            sample_rate: 100.0,
+            accel_noise: 0.0,
+            gyro_noise: 0.0,
+            seed: 0,
+        }
+    }
+}
+
+// Readings of a phone lying face up on the seat with its top pointing forward, in the
+// phone's own axes: x to the rider's right, y forward, z up. Like a real accelerometer,
+// `accel` is specific force and reads +1 g on z at rest. `gyro` is in rad/s.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ImuSample {
+    pub time: f64,
+    #[cfg_attr(feature = "ts", ts(type = "[number, number, number]"))]
+    pub accel: DVec3,
+    #[cfg_attr(feature = "ts", ts(type = "[number, number, number]"))]
+    pub gyro: DVec3,
+}
+
+// Track frame (left, up, forward) to phone frame (right, forward, up)
+fn to_phone(v: DVec3) -> DVec3 {
+    DVec3::new(-v.x, v.z, v.y)
+}
+
+impl TrackSpline {
+    pub fn imu(&self, options: &ImuOptions) -> Vec<ImuSample> {
+        if options.sample_rate <= 0.0 {
+            return Vec::new();
+        }
+        let mut dice = Dice::new(options.seed);
+        let mut noise = |scale: f64| {
+            if scale > 0.0 {
+                DVec3::new(dice.gaussian(), dice.gaussian(), dice.gaussian()) * scale
+            } else {
+                DVec3::ZERO
+            }
+        };
+
+        // Normal and lateral loads come from the track's curvature like everywhere else,
+        // differencing the positions would amplify the corners of the sampled polyline
+        let mut readings = Vec::new();
+        let mut next_sample = self.points.first().map(|p| p.time).unwrap_or_default();
+        for pair in self.points.windows(2) {
+            let (last_point, point) = (&pair[0], &pair[1]);
+            let delta_time = point.time - last_point.time;
+            if delta_time <= 0.0 {
+                continue;
+            }
+            let Some(forces) = pair_forces(last_point, point) else {
+                continue;
+            };
+            let rate = (last_point.rot.0.inverse() * point.rot.0).to_scaled_axis() / delta_time;
+            while next_sample <= point.time {
+                let t = (next_sample - last_point.time) / delta_time;
+                let sample = lerp_points(last_point, point, t);
+                readings.push((sample, forces, rate));
+                next_sample += 1.0 / options.sample_rate;
+            }
+        }
+
+        (0..readings.len())
+            .map(|i| {
+                let (point, forces, rate) = readings[i];
+                let (before, after) = (i.saturating_sub(1), (i + 1).min(readings.len() - 1));
+                let acceleration = if after > before {
+                    (readings[after].0.velocity - readings[before].0.velocity)
+                        / (readings[after].0.time - readings[before].0.time)
+                } else {
+                    0.0
+                };
+                let forward = point.rot.0 * DVec3::Z;
+                let specific_force = DVec3::new(
+                    -forces.lat * G,
+                    forces.vert * G,
+                    acceleration + forward.y * G,
+                );
+
+                ImuSample {
+                    time: point.time,
+                    accel: to_phone(specific_force) + noise(options.accel_noise),
+                    gyro: to_phone(rate) + noise(options.gyro_noise),
+                }
+            })
+            .collect()
+    }
+}
+
+impl Track {
+    pub fn imu(&self, options: &ImuOptions) -> Vec<ImuSample> {
+        TrackSpline::concat(&self.make_splines()).imu(options)
+    }
+}
+
+// Same column layout as common phone sensor logger apps
+pub fn write_csv<W: Write>(samples: &[ImuSample], mut writer: W) -> io::Result<()> {
+    writeln!(writer, "time,ax,ay,az,gx,gy,gz")?;
+    for s in samples {
+        writeln!(
+            writer,
+            "{},{},{},{},{},{},{}",
+            s.time, s.accel.x, s.accel.y, s.accel.z, s.gyro.x, s.gyro.y, s.gyro.z
+        )?;
+    }
+    Ok(())
+}
+
+// Reads logs in the layout written by write_csv, columns may come in any order
+pub fn read_csv<R: BufRead>(reader: R) -> io::Result<Vec<ImuSample>> {
+    let invalid = |line: usize, message: String| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("line {line}: {message}"),
+        )
+    };
+    let mut lines = reader.lines();
+    let header = lines.next().transpose()?.unwrap_or_default();
+    let names: Vec<String> = header.split(',').map(|n| n.trim().to_lowercase()).collect();
+    let columns = ["time", "ax", "ay", "az", "gx", "gy", "gz"]
+        .map(|name| names.iter().position(|n| n == name).ok_or(name));
+    if let Some(Err(name)) = columns.iter().find(|c| c.is_err()) {
+        return Err(invalid(1, format!("missing column `{name}`")));
+    }
+    let columns = columns.map(Result::unwrap);
+
+    let mut samples = Vec::new();
+    for (i, line) in lines.enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').collect();
+        let mut values = [0.0; 7];
+        for (value, &column) in values.iter_mut().zip(&columns) {
+            let field = fields.get(column).map(|f| f.trim()).unwrap_or_default();
+            *value = field
+                .parse()
+                .map_err(|_| invalid(i + 2, format!("`{field}` is not a number")))?;
+        }
+        let [time, ax, ay, az, gx, gy, gz] = values;
+        samples.push(ImuSample {
+            time,
+            accel: DVec3::new(ax, ay, az),
+            gyro: DVec3::new(gx, gy, gz),
+        });
+    }
+    Ok(samples)
+}
@@ -0,0 +1,100 @@
+use crate::{pair_forces, train::point_at_distance, transitions::Forces, TrackPoint, TrackSpline};
+
+// Cumulative arc length of every point, so distance lookups are a binary search instead of
+// a walk over the whole spline. Build one and keep it around for repeated queries
+#[derive(Clone, Debug)]
+pub struct TrackSplineIndex<'a> {
+    spline: &'a TrackSpline,
+    distances: Vec<f64>,
+}
+
+impl<'a> TrackSplineIndex<'a> {
+    pub fn new(spline: &'a TrackSpline) -> Self {
+        Self {
+            distances: spline.cumulative_distances(),
+            spline,
+        }
+    }
+
+    pub fn spline(&self) -> &'a TrackSpline {
+        self.spline
+    }
+
+    pub fn distances(&self) -> &[f64] {
+        &self.distances
+    }
+
+    pub fn total_distance(&self) -> f64 {
+        self.distances.last().copied().unwrap_or_default()
+    }
+
+    // Same as TrackSpline::eval_closest
+    pub fn eval_closest(&self, distance: f64) -> Option<(&'a TrackPoint, &'a TrackPoint)> {
+        bracket(&self.spline.points, &self.distances, distance)
+    }
+
+    pub fn forces(&self, distance: f64) -> Option<Forces> {
+        forces(&self.spline.points, &self.distances, distance)
+    }
+
+    pub fn evaluate(&self, distance: f64) -> Option<TrackPoint> {
+        evaluate(&self.spline.points, &self.distances, distance)
+    }
+
+    // Seconds to get from `from` to `to` meters along, negative if `to` comes first. The
+    // whole spline for a lap time
+    pub fn time_between(&self, from: f64, to: f64) -> Option<f64> {
+        Some(self.evaluate(to)?.time - self.evaluate(from)?.time)
+    }
+
+    pub fn speed_at(&self, distance: f64) -> Option<f64> {
+        Some(self.evaluate(distance)?.velocity)
+    }
+}
+
+impl TrackSpline {
+    pub fn index(&self) -> TrackSplineIndex<'_> {
+        TrackSplineIndex::new(self)
+    }
+
+    // These build an index on every call like `evaluate`
+    pub fn time_between(&self, from: f64, to: f64) -> Option<f64> {
+        self.index().time_between(from, to)
+    }
+
+    pub fn speed_at(&self, distance: f64) -> Option<f64> {
+        self.index().speed_at(distance)
+    }
+}
+
+// The lookups behind TrackSplineIndex, shared with CompiledSpline which keeps its own
+// distances next to the spline it owns
+pub(crate) fn bracket<'a>(
+    points: &'a [TrackPoint],
+    distances: &[f64],
+    distance: f64,
+) -> Option<(&'a TrackPoint, &'a TrackPoint)> {
+    let i = distances.partition_point(|&d| d < distance).max(1);
+    Some((points.get(i - 1)?, points.get(i)?))
+}
+
+// From the bracketing pair on to the first one with length, so a train held at `distance`
+// reads the forces it leaves with
+pub(crate) fn forces(points: &[TrackPoint], distances: &[f64], distance: f64) -> Option<Forces> {
+    let i = distances.partition_point(|&d| d < distance).max(1);
+    points
+        .get(i - 1..)?
+        .windows(2)
+        .find_map(|pair| pair_forces(&pair[0], &pair[1]))
+}
+
+pub(crate) fn evaluate(
+    points: &[TrackPoint],
+    distances: &[f64],
+    distance: f64,
+) -> Option<TrackPoint> {
+    if distance < 0.0 || distance > *distances.last()? {
+        return None;
+    }
+    point_at_distance(points, distances, distance)
+}
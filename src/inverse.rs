@@ -0,0 +1,144 @@
+// Recovers force vector sections from a spline built some other way, e.g. by hand or read
+// from an NL2 CSV, so it can be edited as transitions
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    pair_forces, pair_roll_rate,
+    reconstruct::linear,
+    track::{Track, TrackSection},
+    transitions::{Forces, RollMode, Transition, TransitionDomain, Transitions},
+    TrackSpline,
+};
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FitTolerance {
+    // G the fitted vertical and lateral may be off the spline's
+    pub force: f64,
+    // Degrees per second the fitted roll rate may be off
+    pub roll: f64,
+}
+
+impl Default for FitTolerance {
+    fn default() -> Self {
+        Self {
+            force: 0.05,
+            roll: 5.0,
+        }
+    }
+}
+
+impl TrackSpline {
+    // Forces between every pair of points along with the time into the spline they sit
+    // halfway through. Roll is the rate about the train's own forward axis, as force sections roll
+    pub fn sampled_forces(&self) -> Vec<(f64, Forces)> {
+        let Some(start) = self.points.first().map(|p| p.time) else {
+            return Vec::new();
+        };
+        self.points
+            .windows(2)
+            .filter(|pair| pair[1].time > pair[0].time)
+            .filter_map(|pair| {
+                let (last_point, point) = (&pair[0], &pair[1]);
+                let forces = pair_forces(last_point, point)?;
+                let roll = pair_roll_rate(last_point, point);
+                let time = 0.5 * (last_point.time + point.time) - start;
+                Some((time, Forces { roll, ..forces }))
+            })
+            .collect()
+    }
+
+    // Piecewise linear transitions that stay within `tolerance` of the spline's forces,
+    // starting from the level 1g a track's first section starts at
+    pub fn fit_transitions(&self, tolerance: &FitTolerance) -> Transitions {
+        let samples = self.sampled_forces();
+        let Some(&(end, _)) = samples.last() else {
+            return Transitions {
+                vert: Vec::new(),
+                lat: Vec::new(),
+                roll: Vec::new(),
+            };
+        };
+        // The last sample holds until the final point
+        let end = (self.points[self.points.len() - 1].time - self.points[0].time).max(end);
+        let start = Forces::new(1.0, 0.0, 0.0);
+
+        let channel = |value: fn(&Forces) -> f64, start: f64, tolerance: f64| {
+            let mut knots = vec![(0.0, start)];
+            knots.extend(samples.iter().map(|(time, forces)| (*time, value(forces))));
+            knots.push((end, value(&samples[samples.len() - 1].1)));
+            fit_channel(&knots, tolerance)
+        };
+
+        Transitions {
+            vert: channel(|f| f.vert, start.vert, tolerance.force),
+            lat: channel(|f| f.lat, start.lat, tolerance.force),
+            roll: channel(|f| f.roll, start.roll, tolerance.roll),
+        }
+    }
+
+    // A single force section starting from the spline's first point. The speed is left to
+    // the track's friction, set the config to match whatever the spline was built with
+    pub fn fit_track(&self, tolerance: &FitTolerance) -> Track {
+        let mut track = Track::default();
+        if let Some(first) = self.points.first() {
+            track.anchor = *first;
+            track.anchor.time = 0.0;
+            track.anchor.section_time = 0.0;
+        }
+        track.sections.push(TrackSection::Force {
+            fixed_speed: None,
+            transitions: self.fit_transitions(tolerance),
+            domain: TransitionDomain::Time,
+            roll_mode: RollMode::Rate,
+        });
+        track
+    }
+}
+
+// Ramer-Douglas-Peucker over (time, value), then a linear transition between each pair of
+// knots that are kept
+fn fit_channel(samples: &[(f64, f64)], tolerance: f64) -> Vec<Transition> {
+    let mut keep = vec![false; samples.len()];
+    keep[0] = true;
+    keep[samples.len() - 1] = true;
+
+    let mut runs = vec![(0, samples.len() - 1)];
+    while let Some((first, last)) = runs.pop() {
+        let (t0, v0) = samples[first];
+        let (t1, v1) = samples[last];
+        let worst = (first + 1..last)
+            .map(|i| {
+                let (t, v) = samples[i];
+                let line = if t1 > t0 {
+                    v0 + (v1 - v0) * (t - t0) / (t1 - t0)
+                } else {
+                    v0
+                };
+                (i, (v - line).abs())
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+
+        if let Some((i, error)) = worst {
+            if error > tolerance {
+                keep[i] = true;
+                runs.push((first, i));
+                runs.push((i, last));
+            }
+        }
+    }
+
+    let knots: Vec<(f64, f64)> = samples
+        .iter()
+        .zip(keep)
+        .filter_map(|(sample, keep)| keep.then_some(*sample))
+        .collect();
+    knots
+        .windows(2)
+        .filter(|pair| pair[1].0 > pair[0].0)
+        .map(|pair| linear(pair[1].1 - pair[0].1, pair[1].0 - pair[0].0))
+        .collect()
+}
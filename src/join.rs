@@ -0,0 +1,127 @@
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{TrackPoint, TrackSpline};
+
+const BLEND_STEP: f64 = 0.01;
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct JoinOptions {
+    pub max_gap: f64,
+    // Degrees
+    pub max_angle: f64,
+    // Fill gaps between joints with interpolated points
+    pub blend: bool,
+}
+
+impl Default for JoinOptions {
+    fn default() -> Self {
+        Self {
+            max_gap: 0.1,
+            max_angle: 1.0,
+            blend: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct JointError {
+    pub index: usize,
+    pub gap: f64,
+    pub angle: f64,
+}
+
+impl fmt::Display for JointError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "spline {} does not continue from the previous one (gap {:.3} m, angle {:.2} deg)",
+            self.index, self.gap, self.angle
+        )
+    }
+}
+
+impl std::error::Error for JointError {}
+
+impl TrackSpline {
+    pub fn concat(splines: &[TrackSpline]) -> TrackSpline {
+        let options = JoinOptions {
+            max_gap: f64::INFINITY,
+            max_angle: f64::INFINITY,
+            blend: false,
+        };
+        Self::concat_checked(splines, &options).unwrap()
+    }
+
+    // Time of each spline is rebased onto the end of the previous one, keeping
+    // its offset from its own origin (`time - section_time` of its first point).
+    // Distance, where the points carry it, carries on from the previous one across the gap
+    pub fn concat_checked(
+        splines: &[TrackSpline],
+        options: &JoinOptions,
+    ) -> Result<TrackSpline, JointError> {
+        let mut points: Vec<TrackPoint> =
+            Vec::with_capacity(splines.iter().map(|s| s.points.len()).sum());
+
+        for (index, spline) in splines.iter().enumerate() {
+            let Some(first) = spline.points.first() else {
+                continue;
+            };
+            let mut time_offset = 0.0;
+            let mut distance_offset = 0.0;
+
+            if let Some(last) = points.last().copied() {
+                let gap = (first.pos - last.pos).length();
+                let angle = last.rot.0.angle_between(first.rot.0).to_degrees();
+                if gap > options.max_gap || angle > options.max_angle {
+                    return Err(JointError { index, gap, angle });
+                }
+
+                time_offset = last.time - (first.time - first.section_time);
+                if let Some(first_distance) = first.distance {
+                    distance_offset = last.distance.unwrap_or_default() + gap - first_distance;
+                }
+                if options.blend && gap > BLEND_STEP {
+                    let steps = (gap / BLEND_STEP) as usize;
+                    for i in 1..steps {
+                        let t = i as f64 / steps as f64;
+                        let next = TrackPoint {
+                            time: first.time + time_offset,
+                            distance: first.distance.map(|d| d + distance_offset),
+                            ..*first
+                        };
+                        points.push(lerp_points(&last, &next, t));
+                    }
+                }
+            }
+
+            points.extend(spline.points.iter().map(|p| TrackPoint {
+                time: p.time + time_offset,
+                distance: p.distance.map(|d| d + distance_offset),
+                ..*p
+            }));
+        }
+
+        Ok(TrackSpline { points })
+    }
+}
+
+pub(crate) fn lerp_points(a: &TrackPoint, b: &TrackPoint, t: f64) -> TrackPoint {
+    TrackPoint {
+        pos: a.pos.lerp(b.pos, t),
+        rot: a.rot.0.slerp(b.rot.0, t).into(),
+        velocity: a.velocity + (b.velocity - a.velocity) * t,
+        time: a.time + (b.time - a.time) * t,
+        section_time: a.section_time + (b.section_time - a.section_time) * t,
+        distance: a.distance.zip(b.distance).map(|(a, b)| a + (b - a) * t),
+        enclosed: if t < 0.5 { a.enclosed } else { b.enclosed },
+    }
+}
@@ -0,0 +1,141 @@
+use glam::DVec2;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::G,
+    math::interpolate,
+    track::{Track, TrackSection},
+};
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum LaunchProfile {
+    // m/s^2
+    Constant {
+        acceleration: f64,
+    },
+    // Thrust in m/s^2 by distance into the launch, like an LSM stator layout
+    Curve {
+        #[cfg_attr(feature = "ts", ts(type = "Array<[number, number]>"))]
+        points: Vec<DVec2>,
+    },
+}
+
+impl Default for LaunchProfile {
+    fn default() -> Self {
+        LaunchProfile::Constant { acceleration: 10.0 }
+    }
+}
+
+impl LaunchProfile {
+    pub fn thrust(&self, distance: f64) -> f64 {
+        match self {
+            LaunchProfile::Constant { acceleration } => *acceleration,
+            LaunchProfile::Curve { points } => interpolate(points, distance),
+        }
+    }
+}
+
+// The hill a launch has to get the train over
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct HillClearance {
+    // Meters from the launch exit up to the crest
+    pub height: f64,
+    // Meters of track from the launch exit to the crest
+    pub distance: f64,
+    // m/s the train should still have over the crest
+    pub margin: f64,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct LaunchSolution {
+    pub entry_speed: f64,
+    pub exit_speed: f64,
+    // Constant thrust in m/s^2 reaching the exit speed at the end of the launch
+    pub acceleration: f64,
+}
+
+// Kinetic energy per unit mass after `distance` meters, losing `loss` per meter to gravity
+// and rolling friction and a share of itself to air resistance, the same terms as
+// track_friction. Negative distances run it backwards
+fn carried_energy(energy: f64, loss: f64, resistance: f64, distance: f64) -> f64 {
+    let drag = 2.0 * resistance;
+    if drag <= 0.0 {
+        return energy - loss * distance;
+    }
+    let steady = loss / drag;
+    (energy + steady) * libm::exp(-drag * distance) - steady
+}
+
+impl Track {
+    // Launch exit speed that gets the train over `clearance`, assuming an even climb to the
+    // crest under the track's friction settings
+    pub fn launch_exit_speed(&self, clearance: &HillClearance) -> f64 {
+        let distance = clearance.distance.max(clearance.height).max(f64::EPSILON);
+        let loss = G * (clearance.height / distance + self.config.parameter());
+        let top = 0.5 * clearance.margin * clearance.margin;
+        let energy = carried_energy(top, loss, self.config.resistance(), -distance);
+        (2.0 * energy.max(0.0)).sqrt()
+    }
+
+    // Sets the launch at `section` to a constant thrust that reaches the exit speed for
+    // `clearance` right at the end of its length. None when `section` isn't a launch
+    pub fn solve_launch(
+        &mut self,
+        section: usize,
+        clearance: &HillClearance,
+    ) -> Option<LaunchSolution> {
+        let TrackSection::Launch { length, .. } = *self.sections.get(section)? else {
+            return None;
+        };
+        let entry_speed = match section {
+            0 => self.initial_point().velocity,
+            _ => self
+                .make_splines()
+                .get(section - 1)?
+                .points
+                .last()
+                .map_or(0.0, |p| p.velocity),
+        };
+        let exit_speed = self.launch_exit_speed(clearance);
+
+        // Thrust is what's left after friction on a level launch, solved the same way as
+        // carried_energy with the thrust as a gain instead of a loss
+        let (entry, exit) = (
+            0.5 * entry_speed * entry_speed,
+            0.5 * exit_speed * exit_speed,
+        );
+        let drag = 2.0 * self.config.resistance();
+        let gain = if drag > 0.0 {
+            let decay = libm::exp(-drag * length);
+            drag * (exit - entry * decay) / (1.0 - decay)
+        } else {
+            (exit - entry) / length
+        };
+        let acceleration = (gain + G * self.config.parameter()).max(0.0);
+
+        if let Some(TrackSection::Launch {
+            target_speed,
+            profile,
+            ..
+        }) = self.sections.get_mut(section)
+        {
+            *target_speed = exit_speed;
+            *profile = LaunchProfile::Constant { acceleration };
+        }
+        Some(LaunchSolution {
+            entry_speed,
+            exit_speed,
+            acceleration,
+        })
+    }
+}
@@ -1,91 +1,231 @@
+pub mod airtime;
+pub mod analysis;
+pub mod anchor;
+pub mod animation;
+pub mod banking;
+#[cfg(feature = "batch")]
+pub mod batch;
+pub mod blocks;
+pub mod bobsled;
+pub mod brakes;
+pub mod channels;
+pub mod clearance;
+pub mod comfort;
+pub mod compiled;
+#[cfg(any(feature = "gzip", feature = "brotli"))]
+pub mod compress;
 pub mod constants;
+mod csv;
+pub mod elevation;
+pub mod enclosure;
+pub mod error;
+#[cfg(feature = "json")]
+mod exports;
+pub mod fabrication;
+pub mod first_drop;
+pub mod format;
+pub mod generate;
+pub mod grade;
+pub mod health;
+pub mod hills;
+pub mod history;
+pub mod hotspots;
+pub mod imu;
+pub mod index;
+pub mod inverse;
+pub mod join;
+pub mod launch;
+pub mod lift;
+pub mod live;
 pub mod math;
+pub mod mechanics;
+pub mod mesh;
+pub mod nl2;
+pub mod normalize;
+pub mod output;
+pub mod package;
+pub mod pins;
+pub mod playback;
+pub mod prelude;
+pub mod preset;
+pub mod profile;
+pub mod reconstruct;
+pub mod roughness;
+pub mod sections;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod shuttle;
+pub mod simplify;
+pub mod simulation;
+pub mod spatial;
+#[cfg(feature = "spill")]
+pub mod spill;
+pub mod stall;
+pub mod stationing;
+pub mod stats;
+pub mod terrain;
 pub mod track;
+pub mod train;
 pub mod transitions;
+pub mod triggers;
+pub mod turnaround;
+pub mod validation;
+pub mod walkway;
+
+#[cfg(feature = "json")]
+pub use exports::*;
+// The glam version the public API is built on, so callers don't have to match it
+pub use glam;
 
 use constants::G;
 use glam::DVec3;
-use math::{deg_diff, euler, WrapperDQuat};
+use math::WrapperDQuat;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use track::Track;
 use transitions::Forces;
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize, Default)]
-#[serde(rename_all = "camelCase")]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct TrackPoint {
+    #[cfg_attr(feature = "ts", ts(type = "[number, number, number]"))]
     pub pos: DVec3,
+    #[cfg_attr(feature = "ts", ts(type = "[number, number, number, number]"))]
     pub rot: WrapperDQuat,
     pub velocity: f64,
     pub time: f64,
+    pub section_time: f64,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub distance: Option<f64>,
+    // Inside a tunnel or enclosure, see Track::enclosures
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub enclosed: Option<bool>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, Default)]
-#[serde(rename_all = "camelCase")]
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct TrackSpline {
     pub points: Vec<TrackPoint>,
 }
 
 impl TrackSpline {
+    // Pair of points bracketing `distance` meters along the spline. Like `evaluate` this
+    // builds an index on every call, hold a TrackSplineIndex for per-frame queries
     pub fn eval_closest(&self, distance: f64) -> Option<(&TrackPoint, &TrackPoint)> {
-        let mut total_dist = 0.0;
-        for i in 1..self.points.len() {
-            let dist = (self.points[i].pos - self.points[i - 1].pos).length();
-            total_dist += dist;
-            if total_dist >= distance {
-                return Some((&self.points[i - 1], &self.points[i]));
-            }
+        self.index().eval_closest(distance)
+    }
+
+    // Point `distance` meters along the spline, interpolated between the bracketing pair
+    // Builds an index on every call, see TrackSplineIndex for repeated queries
+    pub fn evaluate(&self, distance: f64) -> Option<TrackPoint> {
+        self.index().evaluate(distance)
+    }
+
+    // Point at `time` on the same clock as the points' own time stamps
+    pub fn evaluate_at_time(&self, time: f64) -> Option<TrackPoint> {
+        let (first, last) = (self.points.first()?, self.points.last()?);
+        if time < first.time || time > last.time {
+            return None;
+        }
+        if self.points.len() < 2 {
+            return Some(*first);
         }
-        None
+        let (i, t) = playback::time_bracket(&self.points, time);
+        Some(join::lerp_points(&self.points[i - 1], &self.points[i], t))
     }
+
+    pub fn duration(&self) -> f64 {
+        match (self.points.first(), self.points.last()) {
+            (Some(first), Some(last)) => last.time - first.time,
+            _ => 0.0,
+        }
+    }
+
     pub fn forces(&self, pos: f64) -> Option<Forces> {
-        let points = self.eval_closest(pos);
-        if let Some((last_point, point)) = points {
-            let delta_dist = (point.pos - last_point.pos).length();
-
-            let (last_yaw, last_pitch, _last_roll) = euler(last_point);
-            let (yaw, pitch, roll) = euler(point);
-
-            let pitch_from_last = deg_diff(last_pitch, pitch).to_radians();
-            let yaw_from_last = deg_diff(last_yaw, yaw).to_radians();
-
-            let temp = pitch.abs().to_radians().cos();
-
-            let normal_d_angle = pitch_from_last * (-roll).to_radians().cos()
-                - temp * -yaw_from_last * (-roll).to_radians().sin();
-            let lateral_d_angle = -pitch_from_last * (roll).to_radians().sin()
-                - temp * yaw_from_last * (roll).to_radians().cos();
-
-            let force_vec = DVec3::Y
-                + ((point.rot.0 * DVec3::Y)
-                    * ((point.velocity * point.velocity) / (delta_dist / normal_d_angle) / G))
-                + ((point.rot.0 * DVec3::NEG_X)
-                    * ((point.velocity * point.velocity) / (delta_dist / lateral_d_angle) / G));
-
-            Some(Forces {
-                vert: force_vec.dot(point.rot.0 * DVec3::Y),
-                lat: force_vec.dot(point.rot.0 * DVec3::NEG_X),
-                roll: 0.0,
-            })
-        } else {
-            None
+        self.index().forces(pos)
+    }
+
+    pub fn fill_distance(&mut self) {
+        let mut distance = 0.0;
+        let mut last_pos = self.points.first().map(|p| p.pos).unwrap_or_default();
+        for point in &mut self.points {
+            distance += (point.pos - last_pos).length();
+            last_pos = point.pos;
+            point.distance = Some(distance);
         }
     }
 
-    fn total_distance(&self) -> f64 {
+    // Whether the train runs backwards anywhere along the spline
+    pub fn rolls_back(&self) -> bool {
+        self.points.iter().any(|p| p.velocity < 0.0)
+    }
+
+    pub fn total_distance(&self) -> f64 {
         self.points.windows(2).fold(0.0, |acc, points| {
             acc + (points[1].pos - points[0].pos).length()
         })
     }
 }
 
-#[cfg(target_arch = "wasm32")]
-use wasm_bindgen::prelude::*;
+// Rotation from one frame to the next as a scaled axis in the first frame
+pub(crate) fn pair_rotation(last_point: &TrackPoint, point: &TrackPoint) -> DVec3 {
+    let delta = last_point.rot.0.inverse() * point.rot.0;
+    // q and -q are the same rotation, take the short way round
+    let delta = if delta.w < 0.0 { -delta } else { delta };
+    delta.to_scaled_axis()
+}
+
+// Curvature comes from the rotation between the two frames rather than from Euler angle
+// differences, which fall apart around vertical where the yaw flips. Pairs with no length
+// between them, like a train held on a brake, have no curvature and carry no forces
+pub(crate) fn pair_forces(last_point: &TrackPoint, point: &TrackPoint) -> Option<Forces> {
+    let delta_dist = (point.pos - last_point.pos).length();
+    if delta_dist <= 0.0 {
+        return None;
+    }
+    let angular = pair_rotation(last_point, point);
+
+    // Pitching up turns about the right (-x) axis, turning left about up (y)
+    let normal_d_angle = -angular.x;
+    let lateral_d_angle = -angular.y;
 
-#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
-pub fn get_spline(track_json: &str) -> String {
-    #[cfg(target_arch = "wasm32")]
-    console_error_panic_hook::set_once();
+    let up = point.rot.0 * DVec3::Y;
+    let right = point.rot.0 * DVec3::NEG_X;
+    let speed_squared = point.velocity * point.velocity;
+    let force_vec = DVec3::Y
+        + up * (speed_squared * normal_d_angle / delta_dist / G)
+        + right * (speed_squared * lateral_d_angle / delta_dist / G);
+
+    Some(Forces {
+        vert: force_vec.dot(up),
+        lat: force_vec.dot(right),
+        roll: 0.0,
+    })
+}
+
+// Degrees per second about the train's own forward axis, the way force sections roll
+pub(crate) fn pair_roll_rate(last_point: &TrackPoint, point: &TrackPoint) -> f64 {
+    let dt = point.time - last_point.time;
+    if dt > 0.0 {
+        pair_rotation(last_point, point).z.to_degrees() / dt
+    } else {
+        0.0
+    }
+}
 
-    let track = serde_json::from_str::<Track>(track_json).unwrap();
-    serde_json::to_string(&track.get_spline()).unwrap()
+// G along the train's forward axis, positive pressing the rider back into the seat. A
+// train rolling freely feels none, only friction and whatever drives or brakes it
+pub(crate) fn pair_long(last_point: &TrackPoint, point: &TrackPoint) -> f64 {
+    let dt = point.time - last_point.time;
+    let accel = if dt > 0.0 {
+        (point.velocity - last_point.velocity) / dt
+    } else {
+        0.0
+    };
+    (point.rot.0 * DVec3::Z).y + accel / G
 }
@@ -0,0 +1,118 @@
+use glam::DVec3;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    track::{Track, TrackSection},
+    train::point_at_distance,
+};
+
+// What pulls the train up a lift section
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        tag = "type",
+        rename_all = "camelCase",
+        rename_all_fields = "camelCase"
+    )
+)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum LiftDrive {
+    // Carries the train at chain speed from wherever it catches it
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "chain"))]
+    Chain,
+    // A catch car that accelerates the train up to the top speed and slows it to
+    // `release_speed` by the crest, accelerations in m/s^2
+    #[cfg_attr(feature = "serde", serde(rename = "cable"))]
+    Cable {
+        acceleration: f64,
+        deceleration: f64,
+        release_speed: f64,
+    },
+}
+
+impl LiftDrive {
+    // Speed the drive holds the train at `distance` into a lift of `length`, given what
+    // the train would be doing on its own
+    pub fn speed(
+        &self,
+        coast: f64,
+        entry_speed: f64,
+        top_speed: f64,
+        distance: f64,
+        length: f64,
+    ) -> f64 {
+        match *self {
+            // A train arriving faster than the chain rolls over the dogs until it slows
+            // down to chain speed, then gets carried the rest of the way
+            LiftDrive::Chain => coast.max(top_speed),
+            LiftDrive::Cable {
+                acceleration,
+                deceleration,
+                release_speed,
+            } => {
+                let accelerated =
+                    (entry_speed * entry_speed + 2.0 * acceleration * distance).sqrt();
+                let braked = (release_speed * release_speed
+                    + 2.0 * deceleration * (length - distance).max(0.0))
+                .sqrt();
+                accelerated.min(braked).min(top_speed)
+            }
+        }
+    }
+}
+
+// Where the anti-rollback pawl clicks over a tooth on the way up a lift
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct RollbackTick {
+    pub section: usize,
+    // Meters along the whole track
+    pub distance: f64,
+    pub time: f64,
+    pub velocity: f64,
+    #[cfg_attr(feature = "ts", ts(type = "[number, number, number]"))]
+    pub pos: DVec3,
+}
+
+impl Track {
+    // A tick every `spacing` meters of every lift, starting one tooth in
+    pub fn anti_rollback_ticks(&self, spacing: f64) -> Vec<RollbackTick> {
+        let mut ticks = Vec::new();
+        if spacing <= 0.0 {
+            return ticks;
+        }
+        let splines = self.make_splines();
+        let mut start = 0.0;
+
+        for (i, (section, spline)) in self.sections.iter().zip(&splines).enumerate() {
+            let distances = spline.cumulative_distances();
+            let length = distances.last().copied().unwrap_or_default();
+            if let TrackSection::Lift { .. } = section {
+                let mut distance = spacing;
+                while distance <= length {
+                    let Some(point) = point_at_distance(&spline.points, &distances, distance)
+                    else {
+                        break;
+                    };
+                    ticks.push(RollbackTick {
+                        section: i,
+                        distance: start + distance,
+                        time: point.time,
+                        velocity: point.velocity,
+                        pos: point.pos,
+                    });
+                    distance += spacing;
+                }
+            }
+            start += length;
+        }
+
+        ticks
+    }
+}
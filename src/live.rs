@@ -0,0 +1,207 @@
+use std::ops::Range;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    pair_forces, simplify::SimplifyTolerance, stats::SplineStats, TrackPoint, TrackSpline,
+};
+
+const BLOCK_POINTS: usize = 1024;
+
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct LiveStats {
+    // Same as TrackSpline::stats
+    pub stats: SplineStats,
+    // Seconds under 0g vertical and how many separate stretches that's split over
+    pub airtime: f64,
+    pub air_segments: usize,
+}
+
+// What a block contributes to the stats, including the pair joining it to the block before
+#[derive(Clone, Copy, Debug)]
+struct Summary {
+    stats: SplineStats,
+    airtime: f64,
+    air_segments: usize,
+    first_air: Option<bool>,
+    last_air: Option<bool>,
+}
+
+impl Summary {
+    fn new(previous: Option<&TrackPoint>, points: &[TrackPoint]) -> Self {
+        let mut summary = Summary {
+            stats: SplineStats::empty(),
+            airtime: 0.0,
+            air_segments: 0,
+            first_air: None,
+            last_air: None,
+        };
+        let mut last = previous;
+        for point in points {
+            summary.stats.add_point(point);
+
+            let pair = last.and_then(|last| Some((last, pair_forces(last, point)?)));
+            if let Some((last, forces)) = pair {
+                summary.stats.add_pair(last, point);
+                let air = forces.vert < 0.0;
+                if air {
+                    summary.airtime += point.time - last.time;
+                    if summary.last_air != Some(true) {
+                        summary.air_segments += 1;
+                    }
+                }
+                summary.first_air.get_or_insert(air);
+                summary.last_air = Some(air);
+            }
+            last = Some(point);
+        }
+        summary
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Block {
+    points: Vec<TrackPoint>,
+    summary: Summary,
+}
+
+// A spline kept in blocks with the stats of each, so an edit only recomputes the blocks it
+// touches and the totals are a pass over the blocks rather than every point
+#[derive(Clone, Debug, Default)]
+pub struct LiveSpline {
+    blocks: Vec<Block>,
+}
+
+impl LiveSpline {
+    pub fn new(spline: TrackSpline) -> Self {
+        let mut live = LiveSpline::default();
+        live.splice(0..0, spline.points);
+        live
+    }
+
+    pub fn len(&self) -> usize {
+        self.blocks.iter().map(|b| b.points.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = &TrackPoint> {
+        self.blocks.iter().flat_map(|b| &b.points)
+    }
+
+    pub fn to_spline(&self) -> TrackSpline {
+        TrackSpline {
+            points: self.points().copied().collect(),
+        }
+    }
+
+    pub fn stats(&self) -> LiveStats {
+        let (Some(first), Some(last)) = (
+            self.blocks.first().and_then(|b| b.points.first()),
+            self.blocks.last().and_then(|b| b.points.last()),
+        ) else {
+            return LiveStats::default();
+        };
+
+        let mut live = LiveStats::default();
+        let mut stats = SplineStats::empty();
+        let mut last_air = None;
+        for Block { summary, .. } in &self.blocks {
+            stats.merge(&summary.stats);
+            live.airtime += summary.airtime;
+            live.air_segments += summary.air_segments;
+            // A stretch of air running over the join was counted by both blocks
+            if last_air == Some(true) && summary.first_air == Some(true) {
+                live.air_segments -= 1;
+            }
+            last_air = summary.last_air.or(last_air);
+        }
+        live.stats = stats.finish(first, last);
+        live
+    }
+
+    // Replaces the points in `range` like Vec::splice
+    pub fn splice(
+        &mut self,
+        range: Range<usize>,
+        replacement: impl IntoIterator<Item = TrackPoint>,
+    ) {
+        let len = self.len();
+        assert!(
+            range.start <= range.end && range.end <= len,
+            "splice range {range:?} out of bounds for {len} points"
+        );
+
+        // Blocks `first..last` hold the range, and `offset` is where the first one starts
+        let (mut first, mut offset) = (0, 0);
+        while first < self.blocks.len() && offset + self.blocks[first].points.len() <= range.start {
+            offset += self.blocks[first].points.len();
+            first += 1;
+        }
+        // Appending goes onto the end of the last block
+        if first == self.blocks.len() && first > 0 {
+            first -= 1;
+            offset -= self.blocks[first].points.len();
+        }
+        let mut last = first;
+        let mut end = offset;
+        while last < self.blocks.len() && end < range.end {
+            end += self.blocks[last].points.len();
+            last += 1;
+        }
+        let last = last.max((first + 1).min(self.blocks.len()));
+
+        let mut points: Vec<TrackPoint> = self.blocks[first..last]
+            .iter()
+            .flat_map(|b| b.points.iter().copied())
+            .collect();
+        points.splice(range.start - offset..range.end - offset, replacement);
+
+        let blocks: Vec<Block> = points
+            .chunks(BLOCK_POINTS)
+            .map(|chunk| Block {
+                points: chunk.to_vec(),
+                summary: Summary::new(None, &[]),
+            })
+            .collect();
+        let inserted = blocks.len();
+        self.blocks.splice(first..last, blocks);
+
+        // The new blocks, plus the one after them whose first pair now joins something else
+        let refresh = first..(first + inserted + 1).min(self.blocks.len());
+        for i in refresh {
+            let previous = i
+                .checked_sub(1)
+                .and_then(|p| self.blocks[p].points.last())
+                .copied();
+            let block = &mut self.blocks[i];
+            block.summary = Summary::new(previous.as_ref(), &block.points);
+        }
+    }
+
+    // Keeps only the points in `range`
+    pub fn slice(&mut self, range: Range<usize>) {
+        let len = self.len();
+        self.splice(range.end..len, []);
+        self.splice(0..range.start, []);
+    }
+
+    // Simplifies the points in `range`, the points either side of it are kept as they are
+    pub fn simplify(&mut self, range: Range<usize>, tolerance: &SimplifyTolerance) {
+        let spline = TrackSpline {
+            points: self
+                .points()
+                .skip(range.start)
+                .take(range.len())
+                .copied()
+                .collect(),
+        };
+        self.splice(range, spline.simplify(tolerance).points);
+    }
+}
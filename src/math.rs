@@ -1,17 +1,19 @@
-use glam::{DQuat, DVec3};
+use glam::{DQuat, DVec2, DVec3};
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::TrackPoint;
 
+// Transcendentals go through libm rather than std so native and wasm32 builds agree bit for bit
 pub fn euler(p: &TrackPoint) -> (f64, f64, f64) {
     let dir = p.rot.0 * DVec3::Z;
-    let yaw = (-dir[0]).atan2(-dir[2]);
-    let pitch = dir[1].atan2((dir[0] * dir[0] + dir[2] * dir[2]).sqrt());
+    let yaw = libm::atan2(-dir[0], -dir[2]);
+    let pitch = libm::atan2(dir[1], (dir[0] * dir[0] + dir[2] * dir[2]).sqrt());
 
     let up_dir = p.rot.0 * DVec3::Y;
     let right_dir = p.rot.0 * DVec3::NEG_X;
 
-    let roll = (-right_dir[1]).atan2(up_dir[1]);
+    let roll = libm::atan2(-right_dir[1], up_dir[1]);
     (yaw.to_degrees(), pitch.to_degrees(), roll.to_degrees())
 }
 
@@ -26,6 +28,25 @@ pub fn deg_diff(a: f64, b: f64) -> f64 {
     diff
 }
 
+// Piecewise linear through points sorted by x, held flat past either end
+pub fn interpolate(points: &[DVec2], x: f64) -> f64 {
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return 0.0;
+    };
+    if x <= first.x {
+        return first.y;
+    }
+    points
+        .windows(2)
+        .find(|pair| x <= pair[1].x)
+        .map(|pair| {
+            let t = (x - pair[0].x) / (pair[1].x - pair[0].x);
+            pair[0].y + (pair[1].y - pair[0].y) * t
+        })
+        .unwrap_or(last.y)
+}
+
+#[cfg(feature = "serde")]
 use serde::ser::SerializeTuple;
 
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
@@ -42,6 +63,7 @@ impl From<DQuat> for WrapperDQuat {
     }
 }
 
+#[cfg(feature = "serde")]
 impl Serialize for WrapperDQuat {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -55,6 +77,7 @@ impl Serialize for WrapperDQuat {
         tup.end()
     }
 }
+#[cfg(feature = "serde")]
 impl<'de> Deserialize<'de> for WrapperDQuat {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
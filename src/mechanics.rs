@@ -0,0 +1,156 @@
+use glam::DVec3;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::G,
+    track::{Track, TrackSection},
+    TrackPoint, TrackSpline,
+};
+
+// Kilograms, a loaded train of around two dozen riders
+pub const DEFAULT_TRAIN_MASS: f64 = 6000.0;
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct LoadSample {
+    pub time: f64,
+    // Newtons
+    pub force: f64,
+    // Watts
+    pub power: f64,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct LiftLoad {
+    pub section: usize,
+    pub samples: Vec<LoadSample>,
+    pub peak_force: f64,
+    pub peak_power: f64,
+    pub average_power: f64,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct LaunchRequirement {
+    pub section: usize,
+    pub entry_speed: f64,
+    pub exit_speed: f64,
+    // Joules
+    pub energy: f64,
+    pub peak_force: f64,
+    pub peak_power: f64,
+    pub duration: f64,
+}
+
+impl Track {
+    // Force the chain has to apply to hold a train of `train_mass` kg at the lift speed
+    pub fn lift_loads(&self, train_mass: f64) -> Vec<LiftLoad> {
+        self.lift_loads_of(&self.make_splines(), train_mass)
+    }
+
+    pub(crate) fn lift_loads_of(&self, splines: &[TrackSpline], train_mass: f64) -> Vec<LiftLoad> {
+        let mut loads = Vec::new();
+
+        for (i, (section, spline)) in self.sections.iter().zip(splines).enumerate() {
+            // Launches and driven straights on a grade aren't pulled up by a chain
+            if !matches!(section, TrackSection::Lift { .. }) {
+                continue;
+            }
+
+            let (samples, work) = self.load_samples(&spline.points, train_mass);
+            if samples.is_empty() {
+                continue;
+            }
+            let duration = samples.last().unwrap().time - samples[0].time;
+
+            loads.push(LiftLoad {
+                section: i,
+                peak_force: samples.iter().map(|s| s.force).fold(0.0, f64::max),
+                peak_power: samples.iter().map(|s| s.power).fold(0.0, f64::max),
+                average_power: if duration > 0.0 { work / duration } else { 0.0 },
+                samples,
+            });
+        }
+
+        loads
+    }
+
+    // Energy and power needed to accelerate a train of `train_mass` kg through
+    // powered sections that leave faster than they were entered
+    pub fn launch_requirements(&self, train_mass: f64) -> Vec<LaunchRequirement> {
+        self.launch_requirements_of(&self.make_splines(), train_mass)
+    }
+
+    pub(crate) fn launch_requirements_of(
+        &self,
+        splines: &[TrackSpline],
+        train_mass: f64,
+    ) -> Vec<LaunchRequirement> {
+        let mut entry = self.initial_point();
+        let mut requirements = Vec::new();
+
+        for (i, (section, spline)) in self.sections.iter().zip(splines).enumerate() {
+            let Some(exit) = spline.points.last().copied() else {
+                continue;
+            };
+            if section.is_powered() && exit.velocity > entry.velocity {
+                let points: Vec<TrackPoint> = std::iter::once(entry)
+                    .chain(spline.points.iter().copied())
+                    .collect();
+                let (samples, energy) = self.load_samples(&points, train_mass);
+
+                requirements.push(LaunchRequirement {
+                    section: i,
+                    entry_speed: entry.velocity,
+                    exit_speed: exit.velocity,
+                    energy,
+                    peak_force: samples.iter().map(|s| s.force).fold(0.0, f64::max),
+                    peak_power: samples.iter().map(|s| s.power).fold(0.0, f64::max),
+                    duration: exit.time - entry.time,
+                });
+            }
+            entry = exit;
+        }
+
+        requirements
+    }
+
+    // Samples along with the total work done over them
+    fn load_samples(&self, points: &[TrackPoint], train_mass: f64) -> (Vec<LoadSample>, f64) {
+        let parameter = self.config.parameter();
+        let resistance = self.config.resistance();
+        let mut work = 0.0;
+
+        let samples = points
+            .windows(2)
+            .map(|pair| {
+                let point = &pair[1];
+                let slope = (point.rot.0 * DVec3::Z).y;
+                let dt = point.time - pair[0].time;
+                let accel = (point.velocity - pair[0].velocity) / dt.max(f64::EPSILON);
+                let force = train_mass
+                    * (G * slope
+                        + G * parameter
+                        + resistance * point.velocity * point.velocity
+                        + accel);
+                let power = force * point.velocity;
+                work += power * dt;
+                LoadSample {
+                    time: point.time,
+                    force,
+                    power,
+                }
+            })
+            .collect();
+
+        (samples, work)
+    }
+}
@@ -0,0 +1,252 @@
+use std::io::{self, Write};
+
+use glam::DVec3;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "json")]
+use serde_json::json;
+
+use crate::{track::Track, train::point_at_distance, TrackSpline};
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct MeshOptions {
+    // Meters between the rail centres
+    pub gauge: f64,
+    pub rail_radius: f64,
+    pub spine_radius: f64,
+    // Meters from the rails down to the spine centre
+    pub spine_offset: f64,
+    // Vertices around each tube
+    pub sides: u32,
+    // Meters of track between rings
+    pub spacing: f64,
+}
+
+impl Default for MeshOptions {
+    fn default() -> Self {
+        Self {
+            gauge: 1.1,
+            rail_radius: 0.05,
+            spine_radius: 0.15,
+            spine_offset: 0.4,
+            sides: 8,
+            spacing: 0.5,
+        }
+    }
+}
+
+// Triangles with outward facing counter-clockwise winding, in the spline's coordinates
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Mesh {
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    pub indices: Vec<u32>,
+}
+
+impl TrackSpline {
+    // Left and right rails and the spine below them as tubes following the track's
+    // orientation. The rails sit `heartline_height` below the points
+    pub fn mesh(&self, options: &MeshOptions, heartline_height: f64) -> Mesh {
+        let distances = self.cumulative_distances();
+        let total = distances.last().copied().unwrap_or_default();
+        let spacing = options.spacing.max(f64::EPSILON);
+        let frames: Vec<_> = (0..=(total / spacing).ceil() as usize)
+            .filter_map(|i| {
+                point_at_distance(&self.points, &distances, (i as f64 * spacing).min(total))
+            })
+            .collect();
+
+        let half_gauge = 0.5 * options.gauge;
+        let tubes = [
+            (DVec3::new(half_gauge, 0.0, 0.0), options.rail_radius),
+            (DVec3::new(-half_gauge, 0.0, 0.0), options.rail_radius),
+            (
+                DVec3::new(0.0, -options.spine_offset, 0.0),
+                options.spine_radius,
+            ),
+        ];
+        let sides = options.sides.max(3);
+        let mut mesh = Mesh::default();
+        if frames.len() < 2 {
+            return mesh;
+        }
+
+        for (centre, radius) in tubes {
+            let first = mesh.positions.len() as u32;
+            for frame in &frames {
+                let rot = frame.rot.0;
+                let centre = frame.pos + rot * (centre - DVec3::Y * heartline_height);
+                for k in 0..sides {
+                    let angle = std::f64::consts::TAU * k as f64 / sides as f64;
+                    let normal = rot * (DVec3::X * libm::cos(angle) + DVec3::Y * libm::sin(angle));
+                    mesh.positions
+                        .push((centre + normal * radius).as_vec3().into());
+                    mesh.normals.push(normal.as_vec3().into());
+                }
+            }
+            for ring in 0..frames.len() as u32 - 1 {
+                for k in 0..sides {
+                    let a = first + ring * sides + k;
+                    let b = a + sides;
+                    let c = first + ring * sides + (k + 1) % sides;
+                    let d = c + sides;
+                    mesh.indices.extend([a, c, b, c, d, b]);
+                }
+            }
+        }
+
+        mesh
+    }
+}
+
+impl Track {
+    pub fn mesh(&self, options: &MeshOptions) -> Mesh {
+        self.get_spline()
+            .0
+            .mesh(options, self.config.heartline_height())
+    }
+}
+
+impl Mesh {
+    #[cfg(feature = "json")]
+    // A glTF 2.0 binary holding the mesh as a single node
+    pub fn write_glb<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        const GLB_MAGIC: u32 = 0x4654_6c67;
+        const GLB_JSON: u32 = 0x4e4f_534a;
+        const GLB_BIN: u32 = 0x004e_4942;
+        const ARRAY_BUFFER: u32 = 34962;
+        const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+        const FLOAT: u32 = 5126;
+        const UNSIGNED_INT: u32 = 5125;
+
+        let mut bin: Vec<u8> = Vec::new();
+        for v in self.positions.iter().chain(&self.normals).flatten() {
+            bin.extend(v.to_le_bytes());
+        }
+        for i in &self.indices {
+            bin.extend(i.to_le_bytes());
+        }
+        let vertex_bytes = self.positions.len() * 12;
+
+        let (mut min, mut max) = ([f32::INFINITY; 3], [f32::NEG_INFINITY; 3]);
+        for p in &self.positions {
+            for axis in 0..3 {
+                min[axis] = min[axis].min(p[axis]);
+                max[axis] = max[axis].max(p[axis]);
+            }
+        }
+        if self.positions.is_empty() {
+            (min, max) = ([0.0; 3], [0.0; 3]);
+        }
+
+        let document = json!({
+            "asset": {"version": "2.0", "generator": "fvd-rs"},
+            "scene": 0,
+            "scenes": [{"nodes": [0]}],
+            "nodes": [{"mesh": 0}],
+            "meshes": [{"primitives": [{
+                "attributes": {"POSITION": 0, "NORMAL": 1},
+                "indices": 2,
+            }]}],
+            "buffers": [{"byteLength": bin.len()}],
+            "bufferViews": [
+                {
+                    "buffer": 0,
+                    "byteOffset": 0,
+                    "byteLength": vertex_bytes,
+                    "target": ARRAY_BUFFER,
+                },
+                {
+                    "buffer": 0,
+                    "byteOffset": vertex_bytes,
+                    "byteLength": vertex_bytes,
+                    "target": ARRAY_BUFFER,
+                },
+                {
+                    "buffer": 0,
+                    "byteOffset": 2 * vertex_bytes,
+                    "byteLength": self.indices.len() * 4,
+                    "target": ELEMENT_ARRAY_BUFFER,
+                },
+            ],
+            "accessors": [
+                {
+                    "bufferView": 0,
+                    "componentType": FLOAT,
+                    "count": self.positions.len(),
+                    "type": "VEC3",
+                    "min": min,
+                    "max": max,
+                },
+                {
+                    "bufferView": 1,
+                    "componentType": FLOAT,
+                    "count": self.normals.len(),
+                    "type": "VEC3",
+                },
+                {
+                    "bufferView": 2,
+                    "componentType": UNSIGNED_INT,
+                    "count": self.indices.len(),
+                    "type": "SCALAR",
+                },
+            ],
+        });
+
+        let mut json = serde_json::to_vec(&document)?;
+        // Chunks are 4 byte aligned, JSON padded with spaces and binary with zeros
+        json.resize(json.len().next_multiple_of(4), b' ');
+        bin.resize(bin.len().next_multiple_of(4), 0);
+        let length = 12 + 8 + json.len() + 8 + bin.len();
+
+        for word in [GLB_MAGIC, 2, length as u32] {
+            writer.write_all(&word.to_le_bytes())?;
+        }
+        for (kind, chunk) in [(GLB_JSON, &json), (GLB_BIN, &bin)] {
+            writer.write_all(&(chunk.len() as u32).to_le_bytes())?;
+            writer.write_all(&kind.to_le_bytes())?;
+            writer.write_all(chunk)?;
+        }
+        Ok(())
+    }
+
+    // Wavefront OBJ with per vertex normals, indices are 1 based
+    pub fn write_obj<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "# fvd-rs track mesh")?;
+        for [x, y, z] in &self.positions {
+            writeln!(writer, "v {x} {y} {z}")?;
+        }
+        for [x, y, z] in &self.normals {
+            writeln!(writer, "vn {x} {y} {z}")?;
+        }
+        for face in self.indices.chunks_exact(3) {
+            let [a, b, c] = [face[0] + 1, face[1] + 1, face[2] + 1];
+            writeln!(writer, "f {a}//{a} {b}//{b} {c}//{c}")?;
+        }
+        Ok(())
+    }
+
+    // ASCII PLY with normals, which MeshLab reads as is
+    pub fn write_ply<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "ply")?;
+        writeln!(writer, "format ascii 1.0")?;
+        writeln!(writer, "comment fvd-rs track mesh")?;
+        writeln!(writer, "element vertex {}", self.positions.len())?;
+        for property in ["x", "y", "z", "nx", "ny", "nz"] {
+            writeln!(writer, "property float {property}")?;
+        }
+        writeln!(writer, "element face {}", self.indices.len() / 3)?;
+        writeln!(writer, "property list uchar uint vertex_indices")?;
+        writeln!(writer, "end_header")?;
+        for ([x, y, z], [nx, ny, nz]) in self.positions.iter().zip(&self.normals) {
+            writeln!(writer, "{x} {y} {z} {nx} {ny} {nz}")?;
+        }
+        for face in self.indices.chunks_exact(3) {
+            writeln!(writer, "3 {} {} {}", face[0], face[1], face[2])?;
+        }
+        Ok(())
+    }
+}
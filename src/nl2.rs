@@ -0,0 +1,280 @@
+use std::io::{self, BufRead, Write};
+
+use glam::{DMat3, DQuat, DVec3};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    math::WrapperDQuat,
+    track::{step_dt, track_friction, Track, TrackSection},
+    train::point_at_distance,
+    TrackPoint, TrackSpline,
+};
+
+const CSV_COLUMNS: [&str; 13] = [
+    "No.", "PosX", "PosY", "PosZ", "FrontX", "FrontY", "FrontZ", "LeftX", "LeftY", "LeftZ", "UpX",
+    "UpY", "UpZ",
+];
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Nl2CsvOptions {
+    // Meters of track between exported points
+    pub spacing: f64,
+}
+
+impl Default for Nl2CsvOptions {
+    fn default() -> Self {
+        Self { spacing: 1.0 }
+    }
+}
+
+// Friction values of the NoLimits 2 park the track will be imported into
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Nl2Friction {
+    pub parameter: f64,
+    pub resistance: f64,
+}
+
+impl Default for Nl2Friction {
+    fn default() -> Self {
+        Self {
+            parameter: 0.03,
+            resistance: 2e-5,
+        }
+    }
+}
+
+// How the train is run over an imported track, the CSV only holds its shape
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Nl2ImportOptions {
+    // m/s at the first point
+    pub entry_speed: f64,
+    pub friction: Nl2Friction,
+    pub heartline_height: f64,
+}
+
+impl Default for Nl2ImportOptions {
+    fn default() -> Self {
+        Self {
+            entry_speed: 10.0,
+            friction: Nl2Friction::default(),
+            heartline_height: 0.0,
+        }
+    }
+}
+
+// Speeds at the end of a section, the difference is what a booster or trim
+// placed there in NL2 has to make up
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SpeedHint {
+    pub section: usize,
+    pub speed: f64,
+    pub nl2_speed: f64,
+}
+
+impl Track {
+    // Same points with the speeds NL2 would reach, controlled sections keep their speeds
+    pub fn nl2_speeds(&self, friction: &Nl2Friction) -> TrackSpline {
+        let splines = self.make_splines();
+        let mut last = self.initial_point();
+        let mut points = Vec::new();
+
+        for (section, spline) in self.sections.iter().zip(&splines) {
+            let coasting = coasts(section);
+            for point in &spline.points {
+                let next = if coasting {
+                    self.coast(&last, point, friction)
+                } else {
+                    TrackPoint {
+                        time: last.time + step_dt((point.pos - last.pos).length(), point.velocity),
+                        ..*point
+                    }
+                };
+                points.push(next);
+                last = next;
+            }
+        }
+
+        TrackSpline { points }
+    }
+
+    pub fn nl2_speed_hints(&self, friction: &Nl2Friction) -> Vec<SpeedHint> {
+        let splines = self.make_splines();
+        let mut entry = self.initial_point();
+        let mut hints = Vec::new();
+
+        for (i, (section, spline)) in self.sections.iter().zip(&splines).enumerate() {
+            let Some(exit) = spline.points.last().copied() else {
+                continue;
+            };
+            if coasts(section) {
+                let nl2_exit = spline
+                    .points
+                    .iter()
+                    .fold(entry, |last, point| self.coast(&last, point, friction));
+                hints.push(SpeedHint {
+                    section: i,
+                    speed: exit.velocity,
+                    nl2_speed: nl2_exit.velocity,
+                });
+            }
+            entry = exit;
+        }
+
+        hints
+    }
+
+    fn coast(&self, last: &TrackPoint, point: &TrackPoint, friction: &Nl2Friction) -> TrackPoint {
+        coast(friction, self.config.heartline_height(), last, point)
+    }
+}
+
+fn coast(
+    friction: &Nl2Friction,
+    heartline_height: f64,
+    last: &TrackPoint,
+    point: &TrackPoint,
+) -> TrackPoint {
+    let dp = (point.pos - last.pos).length();
+    let dt = step_dt(dp, last.velocity);
+    let velocity = track_friction(
+        friction.parameter,
+        friction.resistance,
+        heartline_height,
+        last,
+        point,
+        dt,
+    );
+    TrackPoint {
+        velocity,
+        time: last.time + step_dt(2.0 * dp, last.velocity + velocity),
+        ..*point
+    }
+}
+
+impl TrackSpline {
+    // Heartline as a NoLimits 2 external track CSV, tab separated with one frame per
+    // `spacing` meters and the last point always included
+    pub fn write_nl2_csv<W: Write>(
+        &self,
+        options: &Nl2CsvOptions,
+        mut writer: W,
+    ) -> io::Result<()> {
+        let header: Vec<String> = CSV_COLUMNS.iter().map(|c| format!("\"{c}\"")).collect();
+        writeln!(writer, "{}", header.join("\t"))?;
+
+        let distances = self.cumulative_distances();
+        let total = distances.last().copied().unwrap_or_default();
+        let spacing = options.spacing.max(f64::EPSILON);
+        let count = (total / spacing).ceil() as usize;
+
+        for i in 0..=count {
+            let distance = (i as f64 * spacing).min(total);
+            let Some(point) = point_at_distance(&self.points, &distances, distance) else {
+                break;
+            };
+            let rot = point.rot.0;
+            let columns = [point.pos, rot * DVec3::Z, rot * DVec3::X, rot * DVec3::Y];
+            write!(writer, "{}", i + 1)?;
+            for v in columns {
+                write!(writer, "\t{}\t{}\t{}", v.x, v.y, v.z)?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+
+    // Reads a NoLimits 2 track CSV as written by write_nl2_csv or NL2's own export. The
+    // orientation comes from the front and up vectors, and the speed from coasting the
+    // whole ride from `entry_speed`, so tracks with lifts or launches will stall
+    pub fn read_nl2_csv<R: BufRead>(reader: R, options: &Nl2ImportOptions) -> io::Result<Self> {
+        let invalid = |line: usize, message: String| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {line}: {message}"),
+            )
+        };
+        let mut lines = reader.lines();
+        let header = lines.next().transpose()?.unwrap_or_default();
+        let names: Vec<&str> = header
+            .split('\t')
+            .map(|n| n.trim().trim_matches('"'))
+            .collect();
+        // The point number isn't needed
+        let columns: [_; 12] = std::array::from_fn(|i| {
+            let name = CSV_COLUMNS[i + 1];
+            names.iter().position(|n| *n == name).ok_or(name)
+        });
+        if let Some(Err(name)) = columns.iter().find(|c| c.is_err()) {
+            return Err(invalid(1, format!("missing column `{name}`")));
+        }
+        let columns = columns.map(Result::unwrap);
+
+        let mut points: Vec<TrackPoint> = Vec::new();
+        for (i, line) in lines.enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let fields: Vec<&str> = line.split('\t').collect();
+            let mut values = [0.0; 12];
+            for (value, &column) in values.iter_mut().zip(&columns) {
+                let field = fields.get(column).map(|f| f.trim()).unwrap_or_default();
+                *value = field
+                    .parse()
+                    .map_err(|_| invalid(i + 2, format!("`{field}` is not a number")))?;
+            }
+            let [px, py, pz, fx, fy, fz, _, _, _, ux, uy, uz] = values;
+
+            // NL2 rounds the vectors, so square them up again before building the rotation
+            let front = DVec3::new(fx, fy, fz).normalize_or_zero();
+            let up = DVec3::new(ux, uy, uz).reject_from_normalized(front);
+            let up = up.normalize_or_zero();
+            if front == DVec3::ZERO || up == DVec3::ZERO {
+                return Err(invalid(i + 2, "degenerate orientation".to_string()));
+            }
+            let rot = DQuat::from_mat3(&DMat3::from_cols(up.cross(front), up, front));
+
+            let point = TrackPoint {
+                pos: DVec3::new(px, py, pz),
+                rot: WrapperDQuat(rot),
+                velocity: options.entry_speed,
+                ..Default::default()
+            };
+            let point = match points.last() {
+                Some(last) => coast(&options.friction, options.heartline_height, last, &point),
+                None => point,
+            };
+            if point.velocity <= 0.0 {
+                return Err(invalid(i + 2, "train stalls".to_string()));
+            }
+            points.push(TrackPoint {
+                section_time: point.time,
+                ..point
+            });
+        }
+        Ok(Self { points })
+    }
+}
+
+fn coasts(section: &TrackSection) -> bool {
+    matches!(
+        section,
+        TrackSection::Straight { .. }
+            | TrackSection::Force { .. }
+            | TrackSection::Curved { .. }
+            | TrackSection::Roll { .. }
+    ) && !section.is_powered()
+}
@@ -0,0 +1,119 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{analysis::AnalysisSample, TrackSpline};
+
+// Meters between the samples the normalized ones are interpolated from
+const SAMPLE_STEP: f64 = 0.1;
+
+// What a fraction of the ride is a fraction of
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum RideFraction {
+    #[default]
+    Distance,
+    Time,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct NormalizeOptions {
+    pub by: RideFraction,
+    // Samples from the start to the end of the ride, both included
+    pub samples: usize,
+}
+
+impl Default for NormalizeOptions {
+    fn default() -> Self {
+        Self {
+            by: RideFraction::Distance,
+            samples: 500,
+        }
+    }
+}
+
+// Rides sampled at the same fractions, `rides[i][j]` being ride i at `fractions[j]`
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Comparison {
+    pub fractions: Vec<f64>,
+    pub rides: Vec<Vec<AnalysisSample>>,
+}
+
+fn lerp(a: &AnalysisSample, b: &AnalysisSample, t: f64) -> AnalysisSample {
+    let mix = |a: f64, b: f64| a + (b - a) * t;
+    let mut sample = AnalysisSample {
+        distance: mix(a.distance, b.distance),
+        time: mix(a.time, b.time),
+        speed: mix(a.speed, b.speed),
+        vert: mix(a.vert, b.vert),
+        lat: mix(a.lat, b.lat),
+        long: mix(a.long, b.long),
+        // Mixed straight across these would take the long way round past ±180
+        sagittal: 0.0,
+        coronal: 0.0,
+        roll_rate: mix(a.roll_rate, b.roll_rate),
+        curvature: mix(a.curvature, b.curvature),
+        height: mix(a.height, b.height),
+        bank: mix(a.bank, b.bank),
+        heading: mix(a.heading, b.heading),
+    };
+    sample.fill_angles();
+    sample
+}
+
+fn fractions(samples: usize) -> Vec<f64> {
+    match samples {
+        0 => Vec::new(),
+        1 => vec![0.0],
+        n => (0..n).map(|i| i as f64 / (n - 1) as f64).collect(),
+    }
+}
+
+impl TrackSpline {
+    // The spline sampled at evenly spaced fractions of its length or duration, so rides of
+    // different lengths line up sample for sample
+    pub fn normalized(&self, options: &NormalizeOptions) -> Vec<AnalysisSample> {
+        let samples = self.analysis(SAMPLE_STEP);
+        let (Some(first), Some(last)) = (samples.first(), samples.last()) else {
+            return Vec::new();
+        };
+        let key = |sample: &AnalysisSample| match options.by {
+            RideFraction::Distance => sample.distance,
+            RideFraction::Time => sample.time,
+        };
+        let (start, span) = (key(first), key(last) - key(first));
+
+        let mut i = 0;
+        fractions(options.samples)
+            .into_iter()
+            .map(|fraction| {
+                let target = start + fraction * span;
+                while i + 2 < samples.len() && key(&samples[i + 1]) < target {
+                    i += 1;
+                }
+                let (a, b) = (&samples[i], &samples[(i + 1).min(samples.len() - 1)]);
+                let gap = key(b) - key(a);
+                let t = if gap > 0.0 {
+                    ((target - key(a)) / gap).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                lerp(a, b, t)
+            })
+            .collect()
+    }
+
+    pub fn compare(splines: &[TrackSpline], options: &NormalizeOptions) -> Comparison {
+        Comparison {
+            fractions: fractions(options.samples),
+            rides: splines.iter().map(|s| s.normalized(options)).collect(),
+        }
+    }
+}
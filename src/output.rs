@@ -0,0 +1,321 @@
+#[cfg(feature = "serde")]
+use serde::{ser::SerializeMap, Deserialize, Serialize, Serializer};
+
+use glam::DVec3;
+
+use crate::{pair_forces, TrackPoint, TrackSpline};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum PointField {
+    Pos,
+    Rot,
+    Velocity,
+    Time,
+    SectionTime,
+    Distance,
+    Enclosed,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Layout {
+    // {"points": [{"pos": .., "rot": .., ..}, ..]}
+    #[default]
+    Points,
+    // {"pos": [..], "rot": [..], ..}
+    Columns,
+}
+
+// Which of the simulated points make it into the output. The spacing based policies also
+// keep the last point so the spline reaches the end of the track
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        tag = "type",
+        rename_all = "camelCase",
+        rename_all_fields = "camelCase"
+    )
+)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Decimation {
+    #[cfg_attr(feature = "serde", serde(rename = "every"))]
+    Every { n: usize },
+    // A point whenever `spacing` meters of track have passed since the last one
+    #[cfg_attr(feature = "serde", serde(rename = "maxSpacing"))]
+    MaxSpacing { spacing: f64 },
+    // A point whenever the track has turned `max_angle` degrees or run `max_spacing` meters
+    // since the last one, dense through tight curves and sparse on straights
+    #[cfg_attr(feature = "serde", serde(rename = "curvature"))]
+    Curvature { max_angle: f64, max_spacing: f64 },
+}
+
+impl Default for Decimation {
+    fn default() -> Self {
+        Self::Every { n: 4 }
+    }
+}
+
+impl Decimation {
+    // Roughly how many simulated points apart the kept points are, for spotting extrema
+    fn window(&self, spline: &TrackSpline) -> usize {
+        let spacing = match *self {
+            Decimation::Every { n } => return n.max(1),
+            Decimation::MaxSpacing { spacing } => spacing,
+            Decimation::Curvature { max_spacing, .. } => max_spacing,
+        };
+        let steps = spline.points.len().saturating_sub(1).max(1);
+        let point_spacing = spline.total_distance() / steps as f64;
+        if point_spacing > 0.0 {
+            ((spacing / point_spacing) as usize).clamp(1, steps)
+        } else {
+            1
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct OutputOptions {
+    // Fill in the cumulative arc length of each point
+    pub distance: bool,
+    // Flag the points inside tunnels and enclosures
+    pub enclosed: bool,
+    pub omit: Vec<PointField>,
+    pub layout: Layout,
+    pub decimation: Decimation,
+    // Also keep the peaks of speed, height and forces that decimation would skip over
+    pub keep_extrema: bool,
+}
+
+impl OutputOptions {
+    pub fn includes(&self, field: PointField) -> bool {
+        !self.omit.contains(&field)
+    }
+}
+
+#[cfg(feature = "serde")]
+pub struct MaskedSpline<'a> {
+    spline: &'a TrackSpline,
+    options: &'a OutputOptions,
+}
+
+#[cfg(feature = "serde")]
+struct MaskedPoint<'a> {
+    point: &'a TrackPoint,
+    options: &'a OutputOptions,
+}
+
+impl TrackSpline {
+    pub fn decimate(&self, decimation: Decimation, keep_extrema: bool) -> TrackSpline {
+        let points = self
+            .decimated_indices(decimation, keep_extrema, &[])
+            .into_iter()
+            .map(|i| self.points[i])
+            .collect();
+        TrackSpline { points }
+    }
+
+    // Indices of the points decimate keeps, plus any flagged in `keep`
+    pub(crate) fn decimated_indices(
+        &self,
+        decimation: Decimation,
+        keep_extrema: bool,
+        keep: &[bool],
+    ) -> Vec<usize> {
+        let points = &self.points;
+        let extrema = if keep_extrema {
+            extrema(points, decimation.window(self))
+        } else {
+            Vec::new()
+        };
+        let flagged = |flags: &[bool], i: usize| flags.get(i).copied().unwrap_or_default();
+
+        let mut kept = Vec::new();
+        let mut distance = 0.0;
+        let mut heading = DVec3::Z;
+        for (i, point) in points.iter().enumerate() {
+            let forward = point.rot.0 * DVec3::Z;
+            if i > 0 {
+                distance += (point.pos - points[i - 1].pos).length();
+            }
+            let last = i + 1 == points.len();
+            let regular = match decimation {
+                Decimation::Every { n } => i % n.max(1) == 0,
+                Decimation::MaxSpacing { spacing } => i == 0 || last || distance >= spacing,
+                Decimation::Curvature {
+                    max_angle,
+                    max_spacing,
+                } => {
+                    i == 0
+                        || last
+                        || distance >= max_spacing
+                        || heading.angle_between(forward).to_degrees() >= max_angle
+                }
+            };
+            // Where the train stands still, e.g. on a holding brake, the frame can jump
+            // between points with no length between them. Dropping them would leave that
+            // jump on a pair with length and a force to go with it
+            let held = (i > 0 && points[i - 1].pos == point.pos)
+                || points.get(i + 1).is_some_and(|next| next.pos == point.pos);
+            if regular || held || flagged(&extrema, i) || flagged(keep, i) {
+                kept.push(i);
+                distance = 0.0;
+                heading = forward;
+            }
+        }
+        kept
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn masked<'a>(&'a self, options: &'a OutputOptions) -> MaskedSpline<'a> {
+        MaskedSpline {
+            spline: self,
+            options,
+        }
+    }
+}
+
+// Points that are the highest or lowest of any channel within `window` points either side.
+// Ties go to the first point of a plateau, so flat stretches don't keep every point
+fn extrema(points: &[TrackPoint], window: usize) -> Vec<bool> {
+    let channels: Vec<[f64; 4]> = (0..points.len())
+        .map(|i| {
+            let forces = match i {
+                0 => None,
+                _ => pair_forces(&points[i - 1], &points[i]),
+            };
+            [
+                points[i].velocity,
+                points[i].pos.y,
+                forces.map_or(f64::NAN, |f| f.vert),
+                forces.map_or(f64::NAN, |f| f.lat),
+            ]
+        })
+        .collect();
+
+    (0..points.len())
+        .map(|i| {
+            let before = &channels[i.saturating_sub(window)..i];
+            let after = &channels[i + 1..(i + 1 + window).min(points.len())];
+            (0..4).any(|c| {
+                let v = channels[i][c];
+                let peak = before.iter().all(|n| v > n[c]) && after.iter().all(|n| v >= n[c]);
+                let dip = before.iter().all(|n| v < n[c]) && after.iter().all(|n| v <= n[c]);
+                !v.is_nan() && (peak || dip)
+            })
+        })
+        .collect()
+}
+
+#[cfg(feature = "serde")]
+struct Column<'a, F> {
+    points: &'a [TrackPoint],
+    value: F,
+}
+
+#[cfg(feature = "serde")]
+impl<T: Serialize, F: Fn(&TrackPoint) -> T> Serialize for Column<'_, F> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.points.iter().map(&self.value))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl MaskedSpline<'_> {
+    fn serialize_columns<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let points = &self.spline.points[..];
+        let has_distance = points.first().is_some_and(|p| p.distance.is_some());
+        let has_enclosed = points.first().is_some_and(|p| p.enclosed.is_some());
+        let mut map = serializer.serialize_map(None)?;
+        if self.options.includes(PointField::Pos) {
+            let value = |p: &TrackPoint| p.pos;
+            map.serialize_entry("pos", &Column { points, value })?;
+        }
+        if self.options.includes(PointField::Rot) {
+            let value = |p: &TrackPoint| p.rot;
+            map.serialize_entry("rot", &Column { points, value })?;
+        }
+        if self.options.includes(PointField::Velocity) {
+            let value = |p: &TrackPoint| p.velocity;
+            map.serialize_entry("velocity", &Column { points, value })?;
+        }
+        if self.options.includes(PointField::Time) {
+            let value = |p: &TrackPoint| p.time;
+            map.serialize_entry("time", &Column { points, value })?;
+        }
+        if self.options.includes(PointField::SectionTime) {
+            let value = |p: &TrackPoint| p.section_time;
+            map.serialize_entry("sectionTime", &Column { points, value })?;
+        }
+        if has_distance && self.options.includes(PointField::Distance) {
+            let value = |p: &TrackPoint| p.distance.unwrap_or_default();
+            map.serialize_entry("distance", &Column { points, value })?;
+        }
+        if has_enclosed && self.options.includes(PointField::Enclosed) {
+            let value = |p: &TrackPoint| p.enclosed.unwrap_or_default();
+            map.serialize_entry("enclosed", &Column { points, value })?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for MaskedSpline<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.options.layout == Layout::Columns {
+            return self.serialize_columns(serializer);
+        }
+
+        let points: Vec<MaskedPoint> = self
+            .spline
+            .points
+            .iter()
+            .map(|point| MaskedPoint {
+                point,
+                options: self.options,
+            })
+            .collect();
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry("points", &points)?;
+        map.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for MaskedPoint<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let p = self.point;
+        let mut map = serializer.serialize_map(None)?;
+        if self.options.includes(PointField::Pos) {
+            map.serialize_entry("pos", &p.pos)?;
+        }
+        if self.options.includes(PointField::Rot) {
+            map.serialize_entry("rot", &p.rot)?;
+        }
+        if self.options.includes(PointField::Velocity) {
+            map.serialize_entry("velocity", &p.velocity)?;
+        }
+        if self.options.includes(PointField::Time) {
+            map.serialize_entry("time", &p.time)?;
+        }
+        if self.options.includes(PointField::SectionTime) {
+            map.serialize_entry("sectionTime", &p.section_time)?;
+        }
+        if let (true, Some(distance)) = (self.options.includes(PointField::Distance), p.distance) {
+            map.serialize_entry("distance", &distance)?;
+        }
+        if let (true, Some(enclosed)) = (self.options.includes(PointField::Enclosed), p.enclosed) {
+            map.serialize_entry("enclosed", &enclosed)?;
+        }
+        map.end()
+    }
+}
@@ -0,0 +1,112 @@
+use std::fmt::Write as _;
+#[cfg(feature = "json")]
+use std::io::{self, Read, Write};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{error::TrackError, stats::TrackStats, track::Track, TrackSpline};
+
+// 2 replaced the stats with TrackStats
+pub const PACKAGE_FORMAT_VERSION: u32 = 2;
+const THUMBNAIL_SIZE: f64 = 256.0;
+const THUMBNAIL_PADDING: f64 = 8.0;
+const THUMBNAIL_MAX_POINTS: usize = 512;
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct PackageMetadata {
+    pub name: String,
+    pub author: String,
+    pub version: String,
+    pub description: String,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Package {
+    pub format_version: u32,
+    pub metadata: PackageMetadata,
+    pub track: Track,
+    pub spline: TrackSpline,
+    pub section_start: Vec<f64>,
+    pub stats: TrackStats,
+    pub thumbnail: String,
+}
+
+impl Package {
+    // Only tracks that simulate cleanly are packaged, so every site shows the same stats
+    pub fn new(track: Track, metadata: PackageMetadata) -> Result<Self, TrackError> {
+        let ((spline, section_start), stats) =
+            track.try_get_spline_with_stats(&Default::default())?;
+        let thumbnail = thumbnail_svg(&spline);
+
+        Ok(Self {
+            format_version: PACKAGE_FORMAT_VERSION,
+            metadata,
+            track,
+            spline,
+            section_start,
+            stats,
+            thumbnail,
+        })
+    }
+
+    #[cfg(feature = "json")]
+    pub fn write<W: Write>(&self, writer: W) -> io::Result<()> {
+        serde_json::to_writer(writer, self)?;
+        Ok(())
+    }
+
+    #[cfg(feature = "json")]
+    pub fn read<R: Read>(reader: R) -> io::Result<Self> {
+        let mut package: Self = serde_json::from_reader(io::BufReader::new(reader))?;
+        if package.format_version > PACKAGE_FORMAT_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "unsupported package format version {}",
+                    package.format_version
+                ),
+            ));
+        }
+        // Older stats don't fit the current ones, work them out again
+        if package.format_version < 2 {
+            package.stats = package.track.stats_of(&package.track.make_splines());
+            package.format_version = PACKAGE_FORMAT_VERSION;
+        }
+        Ok(package)
+    }
+}
+
+// Top-down view of the layout
+pub fn thumbnail_svg(spline: &TrackSpline) -> String {
+    let (mut min_x, mut max_x) = (f64::INFINITY, f64::NEG_INFINITY);
+    let (mut min_z, mut max_z) = (f64::INFINITY, f64::NEG_INFINITY);
+    for p in &spline.points {
+        min_x = min_x.min(p.pos.x);
+        max_x = max_x.max(p.pos.x);
+        min_z = min_z.min(p.pos.z);
+        max_z = max_z.max(p.pos.z);
+    }
+    let extent = (max_x - min_x).max(max_z - min_z).max(1.0);
+    let scale = (THUMBNAIL_SIZE - 2.0 * THUMBNAIL_PADDING) / extent;
+
+    let step = spline.points.len().div_ceil(THUMBNAIL_MAX_POINTS).max(1);
+    let mut path = String::new();
+    for (i, p) in spline.points.iter().step_by(step).enumerate() {
+        let x = THUMBNAIL_PADDING + (p.pos.x - min_x) * scale;
+        let y = THUMBNAIL_PADDING + (p.pos.z - min_z) * scale;
+        let command = if i == 0 { 'M' } else { 'L' };
+        let _ = write!(path, "{command}{x:.1} {y:.1}");
+    }
+
+    format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {size} {size}" width="{size}" height="{size}"><path d="{path}" fill="none" stroke="black" stroke-width="2"/></svg>"#,
+        size = THUMBNAIL_SIZE
+    )
+}
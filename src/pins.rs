@@ -0,0 +1,85 @@
+use glam::DVec3;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{anchor::heading, math::deg_diff, track::Track};
+
+// The end of `section` has to pass through `pos`, and face `heading` when given
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Pin {
+    pub section: usize,
+    #[cfg_attr(feature = "ts", ts(type = "[number, number, number]"))]
+    pub pos: DVec3,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub heading: Option<f64>,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct PinMiss {
+    pub pin: usize,
+    // None when the train never reaches the end of the pinned section
+    #[cfg_attr(feature = "ts", ts(type = "[number, number, number] | null"))]
+    pub offset: Option<DVec3>,
+    pub distance: Option<f64>,
+    // Degrees
+    pub heading_error: Option<f64>,
+}
+
+impl Track {
+    pub fn pin_misses(&self, max_distance: f64, max_heading_error: f64) -> Vec<PinMiss> {
+        let splines = self.make_splines();
+
+        self.pins
+            .iter()
+            .enumerate()
+            .filter_map(|(i, pin)| {
+                let Some(end) = splines.get(pin.section).and_then(|s| s.points.last()) else {
+                    return Some(PinMiss {
+                        pin: i,
+                        offset: None,
+                        distance: None,
+                        heading_error: None,
+                    });
+                };
+
+                let offset = end.pos - pin.pos;
+                let heading_error = pin.heading.map(|h| deg_diff(h, heading(end.rot.0)));
+                let missed = offset.length() > max_distance
+                    || heading_error.is_some_and(|e| e.abs() > max_heading_error);
+
+                missed.then_some(PinMiss {
+                    pin: i,
+                    offset: Some(offset),
+                    distance: Some(offset.length()),
+                    heading_error,
+                })
+            })
+            .collect()
+    }
+
+    // Moves and turns the whole track so the first pin is met exactly, the rest are
+    // left to be checked with pin_misses
+    pub fn fit_first_pin(&mut self) -> bool {
+        let Some(pin) = self.pins.first().copied() else {
+            return false;
+        };
+        let splines = self.make_splines();
+        let Some(end) = splines.get(pin.section).and_then(|s| s.points.last()) else {
+            return false;
+        };
+
+        let target_heading = pin.heading.unwrap_or_else(|| heading(end.rot.0));
+        self.reanchor(end, pin.pos, target_heading);
+        true
+    }
+}
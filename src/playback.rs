@@ -0,0 +1,153 @@
+use glam::{DQuat, DVec3};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    join::lerp_points,
+    math::WrapperDQuat,
+    train::{bogie_pose, car_distances, point_at_distance, train_pose, BogieOffsets},
+    TrackPoint, TrackSpline,
+};
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct CarTransform {
+    #[cfg_attr(feature = "ts", ts(type = "[number, number, number]"))]
+    pub pos: DVec3,
+    #[cfg_attr(feature = "ts", ts(type = "[number, number, number, number]"))]
+    pub rot: WrapperDQuat,
+}
+
+impl From<(DVec3, DQuat)> for CarTransform {
+    fn from((pos, rot): (DVec3, DQuat)) -> Self {
+        Self {
+            pos,
+            rot: rot.into(),
+        }
+    }
+}
+
+// Index of the point ending the segment that contains `time` and how far into it `time`
+// is, for splines of at least two points
+pub(crate) fn time_bracket(points: &[TrackPoint], time: f64) -> (usize, f64) {
+    let i = points
+        .partition_point(|p| p.time < time)
+        .clamp(1, points.len() - 1);
+    let (a, b) = (&points[i - 1], &points[i]);
+    let t = if b.time > a.time {
+        ((time - a.time) / (b.time - a.time)).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (i, t)
+}
+
+// Places a train on a spline for a given wall-clock time, independent of frame rate
+#[derive(Clone, Debug)]
+pub struct Playback {
+    spline: TrackSpline,
+    distances: Vec<f64>,
+    // Distance between consecutive cars, see TrackSpline::train_pose
+    pub car_spacings: Vec<f64>,
+    pub bogies: Option<BogieOffsets>,
+    pub looping: bool,
+}
+
+impl Playback {
+    pub fn new(spline: TrackSpline) -> Self {
+        Self {
+            distances: spline.cumulative_distances(),
+            spline,
+            car_spacings: Vec::new(),
+            bogies: None,
+            looping: false,
+        }
+    }
+
+    pub fn with_cars(mut self, car_spacings: Vec<f64>) -> Self {
+        self.car_spacings = car_spacings;
+        self
+    }
+
+    pub fn with_bogies(mut self, offsets: BogieOffsets) -> Self {
+        self.bogies = Some(offsets);
+        self
+    }
+
+    pub fn spline(&self) -> &TrackSpline {
+        &self.spline
+    }
+
+    pub fn start_time(&self) -> f64 {
+        self.spline
+            .points
+            .first()
+            .map(|p| p.time)
+            .unwrap_or_default()
+    }
+
+    pub fn duration(&self) -> f64 {
+        self.spline.duration()
+    }
+
+    // Point at `time` seconds after the start, along with its distance along the spline
+    pub fn point_at(&self, time: f64) -> Option<(TrackPoint, f64)> {
+        let duration = self.duration();
+        let time = if self.looping && duration > 0.0 {
+            time.rem_euclid(duration)
+        } else {
+            time.clamp(0.0, duration)
+        } + self.start_time();
+
+        let points = &self.spline.points;
+        if points.len() < 2 {
+            return points.first().map(|p| (*p, 0.0));
+        }
+        let (i, t) = time_bracket(points, time);
+        let distance = self.distances[i - 1] + (self.distances[i] - self.distances[i - 1]) * t;
+
+        Some((lerp_points(&points[i - 1], &points[i], t), distance))
+    }
+
+    pub fn point_at_distance(&self, distance: f64) -> Option<TrackPoint> {
+        point_at_distance(&self.spline.points, &self.distances, distance)
+    }
+
+    // Car spacings signed so cars trail behind the front in the direction of travel
+    fn spacings_at(&self, time: f64) -> Option<(f64, Vec<f64>, f64)> {
+        let (front, distance) = self.point_at(time)?;
+        let direction = if front.velocity < 0.0 { -1.0 } else { 1.0 };
+        let spacings = self.car_spacings.iter().map(|s| s * direction).collect();
+        Some((distance, spacings, direction))
+    }
+
+    // One transform per car
+    pub fn sample(&self, time: f64) -> Vec<CarTransform> {
+        let Some((distance, spacings, _)) = self.spacings_at(time) else {
+            return Vec::new();
+        };
+
+        train_pose(&self.spline.points, &self.distances, distance, &spacings)
+            .into_iter()
+            .map(CarTransform::from)
+            .collect()
+    }
+
+    // Lead and trail bogie per car, empty unless bogie offsets are set
+    pub fn sample_bogies(&self, time: f64) -> Vec<[CarTransform; 2]> {
+        let (Some(offsets), Some((distance, spacings, direction))) =
+            (self.bogies, self.spacings_at(time))
+        else {
+            return Vec::new();
+        };
+        let points = &self.spline.points;
+        let cars = car_distances(points, &self.distances, distance, &spacings);
+
+        bogie_pose(points, &self.distances, &cars, offsets, direction)
+            .into_iter()
+            .map(|bogies| bogies.map(CarTransform::from))
+            .collect()
+    }
+}
@@ -0,0 +1,10 @@
+// `use fvd_rs::prelude::*` for the types most programs need
+pub use glam::{DQuat, DVec3};
+
+pub use crate::{
+    error::TrackError,
+    output::OutputOptions,
+    track::{Track, TrackConfig, TrackSection},
+    transitions::{Channel, Forces, Transition, TransitionCurve, Transitions},
+    TrackPoint, TrackSpline,
+};
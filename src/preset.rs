@@ -0,0 +1,81 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::roughness::Roughness;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ForceLimits {
+    pub min_vert: f64,
+    pub max_vert: f64,
+    pub max_lat: f64,
+    // Degrees per second
+    pub max_roll_rate: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct PresetValues {
+    pub parameter: f64,
+    pub resistance: f64,
+    pub heartline_height: f64,
+    pub limits: ForceLimits,
+    pub roughness: Option<Roughness>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum ConfigPreset {
+    SteelSitDown,
+    Wooden,
+    LaunchedSteel,
+}
+
+impl ConfigPreset {
+    pub fn values(&self) -> PresetValues {
+        match self {
+            ConfigPreset::SteelSitDown => PresetValues {
+                parameter: 0.03,
+                resistance: 2e-5,
+                heartline_height: 1.1,
+                limits: ForceLimits {
+                    min_vert: -1.5,
+                    max_vert: 5.0,
+                    max_lat: 1.8,
+                    max_roll_rate: 180.0,
+                },
+                roughness: None,
+            },
+            ConfigPreset::Wooden => PresetValues {
+                parameter: 0.05,
+                resistance: 3e-5,
+                heartline_height: 1.0,
+                limits: ForceLimits {
+                    min_vert: -1.0,
+                    max_vert: 4.0,
+                    max_lat: 1.5,
+                    max_roll_rate: 90.0,
+                },
+                roughness: Some(Roughness::default()),
+            },
+            ConfigPreset::LaunchedSteel => PresetValues {
+                parameter: 0.025,
+                resistance: 1.5e-5,
+                heartline_height: 1.2,
+                limits: ForceLimits {
+                    min_vert: -1.5,
+                    max_vert: 5.5,
+                    max_lat: 2.0,
+                    max_roll_rate: 240.0,
+                },
+                roughness: None,
+            },
+        }
+    }
+}
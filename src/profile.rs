@@ -0,0 +1,59 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{error::TrackError, output::OutputOptions, track::Track, TrackSpline};
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SectionTiming {
+    pub section: usize,
+    // Wall clock time spent simulating the section
+    pub millis: f64,
+    // Simulated points, before decimation
+    pub points: usize,
+}
+
+#[cfg(target_arch = "wasm32")]
+mod clock {
+    use wasm_bindgen::prelude::*;
+
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(js_namespace = performance)]
+        fn now() -> f64;
+    }
+
+    pub fn millis() -> f64 {
+        now()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod clock {
+    use std::{sync::OnceLock, time::Instant};
+
+    static START: OnceLock<Instant> = OnceLock::new();
+
+    pub fn millis() -> f64 {
+        START.get_or_init(Instant::now).elapsed().as_secs_f64() * 1000.0
+    }
+}
+
+pub(crate) use clock::millis;
+
+impl Track {
+    // Same as try_get_spline_with_options, also timing each section as it's simulated
+    pub fn try_get_spline_with_timings(
+        &self,
+        options: &OutputOptions,
+    ) -> Result<(TrackSpline, Vec<f64>, Vec<SectionTiming>), TrackError> {
+        self.check()?;
+        let mut timings = Vec::with_capacity(self.sections.len());
+        let splines = self.make_splines_timed(Some(&mut timings));
+        self.check_splines(&splines)?;
+        let (spline, section_start) = self.assemble(splines, options);
+        Ok((spline, section_start, timings))
+    }
+}
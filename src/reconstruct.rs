@@ -0,0 +1,123 @@
+// Experimental: rebuilds an approximate track from an onboard accelerometer log so
+// recreations have something to start from. The result is only as good as the log.
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::G,
+    imu::ImuSample,
+    track::{Track, TrackSection},
+    transitions::{Forces, RollMode, Transition, TransitionCurve, TransitionDomain, Transitions},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FitOptions {
+    // m/s when the log starts
+    pub initial_speed: f64,
+    // Seconds covered by each fitted transition, longer smooths out more noise
+    pub segment: f64,
+}
+
+impl Default for FitOptions {
+    fn default() -> Self {
+        Self {
+            initial_speed: 10.0,
+            segment: 0.5,
+        }
+    }
+}
+
+// Vertical and lateral g straight from the accelerometer and roll rate in degrees per
+// second from the gyro, see ImuSample for the axes
+fn log_forces(samples: &[ImuSample]) -> Vec<(f64, Forces)> {
+    let mut forces: Vec<(f64, Forces)> = samples
+        .iter()
+        .map(|s| {
+            let forces = Forces::new(s.accel.z / G, s.accel.x / G, s.gyro.y.to_degrees());
+            (s.time, forces)
+        })
+        .collect();
+
+    // Rides start and end level, so whatever bank the integrated gyro ends up at is bias.
+    // Spreading it evenly over the log keeps the fitted roll from drifting off
+    let (Some(&(start, _)), Some(&(end, _))) = (forces.first(), forces.last()) else {
+        return forces;
+    };
+    if end > start {
+        let bank: f64 = forces
+            .windows(2)
+            .map(|w| 0.5 * (w[0].1.roll + w[1].1.roll) * (w[1].0 - w[0].0))
+            .sum();
+        let bias = bank / (end - start);
+        for (_, f) in &mut forces {
+            f.roll -= bias;
+        }
+    }
+    forces
+}
+
+pub(crate) fn linear(value: f64, length: f64) -> Transition {
+    Transition {
+        curve: TransitionCurve::Linear,
+        value,
+        length,
+        center: 0.0,
+        tension: 0.0,
+    }
+}
+
+// Linear transitions through the log averaged over a window around every segment boundary
+pub fn fit_transitions(samples: &[ImuSample], options: &FitOptions) -> Transitions {
+    let mut transitions = Transitions {
+        vert: Vec::new(),
+        lat: Vec::new(),
+        roll: Vec::new(),
+    };
+    let forces = log_forces(samples);
+    let (Some(&(start, _)), Some(&(end, _))) = (forces.first(), forces.last()) else {
+        return transitions;
+    };
+    if options.segment <= 0.0 {
+        return transitions;
+    }
+
+    // A track's first section always starts out at 1g
+    let mut last = Forces::new(1.0, 0.0, 0.0);
+    let segments = ((end - start) / options.segment).floor() as usize;
+    for k in 1..=segments {
+        let boundary = start + k as f64 * options.segment;
+        let window: Vec<Forces> = forces
+            .iter()
+            .filter(|(time, _)| (time - boundary).abs() <= 0.5 * options.segment)
+            .map(|(_, f)| *f)
+            .collect();
+        let target = if window.is_empty() {
+            last
+        } else {
+            window.iter().fold(Forces::ZERO, |a, &b| a + b) * (1.0 / window.len() as f64)
+        };
+
+        let delta = target - last;
+        transitions.vert.push(linear(delta.vert, options.segment));
+        transitions.lat.push(linear(delta.lat, options.segment));
+        transitions.roll.push(linear(delta.roll, options.segment));
+        last = target;
+    }
+    transitions
+}
+
+// A single force section replaying the log from a level start at `initial_speed`
+pub fn fit_track(samples: &[ImuSample], options: &FitOptions) -> Track {
+    let mut track = Track::default();
+    track.anchor.velocity = options.initial_speed;
+    track.sections.push(TrackSection::Force {
+        fixed_speed: None,
+        transitions: fit_transitions(samples, options),
+        domain: TransitionDomain::Time,
+        roll_mode: RollMode::Rate,
+    });
+    track
+}
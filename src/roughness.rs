@@ -0,0 +1,106 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{analysis::AnalysisSample, transitions::Forces};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Roughness {
+    pub seed: u64,
+    // g of shake at `reference_speed`
+    pub amplitude: f64,
+    pub reference_speed: f64,
+    // Distance between track irregularities in meters
+    pub wavelength: f64,
+    // Lateral shake relative to vertical
+    pub lateral_ratio: f64,
+}
+
+impl Default for Roughness {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            amplitude: 0.15,
+            reference_speed: 20.0,
+            wavelength: 1.5,
+            lateral_ratio: 0.5,
+        }
+    }
+}
+
+impl Roughness {
+    pub fn perturb(&self, distance: f64, speed: f64, forces: Forces) -> Forces {
+        let scale = self.amplitude * speed.abs() / self.reference_speed;
+        let x = distance / self.wavelength;
+
+        Forces {
+            vert: forces.vert + scale * self.noise(x, 0),
+            lat: forces.lat + scale * self.lateral_ratio * self.noise(x, 1),
+            roll: forces.roll,
+        }
+    }
+
+    pub fn apply(&self, samples: &mut [AnalysisSample]) {
+        for sample in samples {
+            let forces = self.perturb(
+                sample.distance,
+                sample.speed,
+                Forces::new(sample.vert, sample.lat, 0.0),
+            );
+            sample.vert = forces.vert;
+            sample.lat = forces.lat;
+            sample.fill_angles();
+        }
+    }
+
+    // Smoothly interpolated value noise in [-1, 1]
+    fn noise(&self, x: f64, channel: u64) -> f64 {
+        let cell = x.floor();
+        let t = x - cell;
+        let a = self.lattice(cell as i64, channel);
+        let b = self.lattice(cell as i64 + 1, channel);
+        let t = 0.5 - 0.5 * libm::cos(std::f64::consts::PI * t);
+        a + (b - a) * t
+    }
+
+    fn lattice(&self, cell: i64, channel: u64) -> f64 {
+        let hash = splitmix64(self.seed ^ splitmix64(cell as u64 ^ (channel << 62)));
+        (hash >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+    }
+}
+
+pub(crate) fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e3779b97f4a7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
+
+// Uniform random values in [0, 1), reproducible from the seed
+pub(crate) struct Dice {
+    seed: u64,
+    rolls: u64,
+}
+
+impl Dice {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { seed, rolls: 0 }
+    }
+
+    pub(crate) fn roll(&mut self) -> f64 {
+        self.rolls += 1;
+        (splitmix64(self.seed ^ splitmix64(self.rolls)) >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    pub(crate) fn range(&mut self, min: f64, max: f64) -> f64 {
+        min + (max - min) * self.roll()
+    }
+
+    // Standard normal, Box-Muller
+    pub(crate) fn gaussian(&mut self) -> f64 {
+        let u = 1.0 - self.roll();
+        libm::sqrt(-2.0 * libm::log(u)) * libm::cos(std::f64::consts::TAU * self.roll())
+    }
+}
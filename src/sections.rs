@@ -0,0 +1,127 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::TrackError,
+    output::{Decimation, OutputOptions},
+    track::Track,
+    TrackSpline,
+};
+
+// The spline along with which section every point came from, so editors can map a point
+// back to the section under the cursor. The first and last point of every section survive
+// decimation
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SplineWithSections {
+    pub spline: TrackSpline,
+    pub section_start: Vec<f64>,
+    // Per point, the index of its section
+    pub section: Vec<usize>,
+    // Per point, how far through its section it is by distance, 0 at the start and 1 at the end
+    pub parameter: Vec<f64>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaskedSections<'a> {
+    spline: crate::output::MaskedSpline<'a>,
+    section_start: &'a [f64],
+    section: &'a [usize],
+    parameter: &'a [f64],
+}
+
+#[cfg(feature = "serde")]
+impl SplineWithSections {
+    pub fn masked<'a>(&'a self, options: &'a OutputOptions) -> MaskedSections<'a> {
+        MaskedSections {
+            spline: self.spline.masked(options),
+            section_start: &self.section_start,
+            section: &self.section,
+            parameter: &self.parameter,
+        }
+    }
+}
+
+impl Track {
+    pub fn try_get_spline_with_sections(
+        &self,
+        options: &OutputOptions,
+    ) -> Result<SplineWithSections, TrackError> {
+        self.check()?;
+        let splines = self.make_splines();
+        self.check_splines(&splines)?;
+
+        let mut section = Vec::new();
+        let mut parameter = Vec::new();
+        let mut boundary = Vec::new();
+        for (i, spline) in splines.iter().enumerate() {
+            let distances = spline.cumulative_distances();
+            let length = distances.last().copied().unwrap_or_default();
+            for (j, distance) in distances.iter().enumerate() {
+                section.push(i);
+                parameter.push(if length > 0.0 { distance / length } else { 0.0 });
+                boundary.push(j == 0 || j + 1 == distances.len());
+            }
+        }
+
+        let full = OutputOptions {
+            decimation: Decimation::Every { n: 1 },
+            keep_extrema: false,
+            ..options.clone()
+        };
+        let (spline, section_start) = self.assemble(splines, &full);
+        let kept = spline.decimated_indices(options.decimation, options.keep_extrema, &boundary);
+
+        Ok(SplineWithSections {
+            spline: TrackSpline {
+                points: kept.iter().map(|&i| spline.points[i]).collect(),
+            },
+            section_start,
+            section: kept.iter().map(|&i| section[i]).collect(),
+            parameter: kept.iter().map(|&i| parameter[i]).collect(),
+        })
+    }
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+impl TrackSpline {
+    // FNV-1a over the bits of every position and rotation, the same on every run and
+    // platform. Speeds and times aren't included, only what a mesh is built from
+    pub fn content_hash(&self) -> u64 {
+        let mut hash = FNV_OFFSET;
+        for point in &self.points {
+            let rot = point.rot.0;
+            for value in [
+                point.pos.x,
+                point.pos.y,
+                point.pos.z,
+                rot.x,
+                rot.y,
+                rot.z,
+                rot.w,
+            ] {
+                for byte in value.to_bits().to_le_bytes() {
+                    hash = (hash ^ byte as u64).wrapping_mul(FNV_PRIME);
+                }
+            }
+        }
+        hash
+    }
+}
+
+impl Track {
+    // The content hash of each section's spline, so a renderer can keep the mesh of every
+    // section whose hash didn't change after an edit
+    pub fn section_hashes(&self) -> Vec<u64> {
+        self.make_splines()
+            .iter()
+            .map(TrackSpline::content_hash)
+            .collect()
+    }
+}
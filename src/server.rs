@@ -0,0 +1,177 @@
+use std::{io, sync::Arc};
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use tokio::net::{TcpListener, ToSocketAddrs};
+
+use crate::{
+    compiled::CompiledTrack,
+    error::TrackError,
+    health::HealthReport,
+    simulation::{Integrator, SimulationSettings, MIN_STEP_SCALE},
+    stats::TrackStats,
+    track::{Track, TrackSection},
+    transitions::{Forces, TransitionDomain},
+    TrackSpline,
+};
+
+// Tracks come from anyone, so the work one request can ask for is capped. Steps finer than
+// the high resolution preset are refused, and so is anything estimated to take more steps
+// than this, which is about 200 MB of points
+const MAX_STEPS: f64 = 2_000_000.0;
+// Distance based force sections step further the faster the train goes. Without a fixed
+// speed they're estimated at this speed, a coasting train much slower than that stalls
+const ESTIMATE_SPEED: f64 = 1.0;
+
+// Same request and response bodies as get_spline, get_stats and validate
+pub fn router() -> Router {
+    Router::new()
+        .route("/spline", post(spline))
+        .route("/stats", post(stats))
+        .route("/validate", post(validate))
+}
+
+// Queries against one track every handler shares, simulated on the first request
+pub fn compiled_router(track: Arc<CompiledTrack>) -> Router {
+    Router::new()
+        .route("/stats", get(compiled_stats))
+        .route("/forces", get(compiled_forces))
+        .with_state(track)
+}
+
+pub async fn serve(addr: impl ToSocketAddrs) -> io::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    axum::serve(listener, router()).await
+}
+
+// Simulation is CPU bound, so it runs off the async workers
+async fn simulate<T: Serialize + Send + 'static>(
+    f: impl FnOnce() -> Result<T, TrackError> + Send + 'static,
+) -> Result<Json<T>, (StatusCode, String)> {
+    match tokio::task::spawn_blocking(f).await {
+        Ok(Ok(value)) => Ok(Json(value)),
+        Ok(Err(err)) => Err((StatusCode::UNPROCESSABLE_ENTITY, err.to_string())),
+        Err(_) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "track failed to simulate".to_string(),
+        )),
+    }
+}
+
+// Refuses tracks that would take more work to simulate than one request is allowed
+pub fn check_budget(track: &Track) -> Result<(), (StatusCode, String)> {
+    let settings = track.config.simulation();
+    let finest = SimulationSettings::high_resolution();
+    let steps = [
+        ("simulation.timeStep", settings.time_step < finest.time_step),
+        (
+            "simulation.spatialStep",
+            settings.spatial_step < finest.spatial_step,
+        ),
+        (
+            "simulation.curveSubdivisions",
+            settings.curve_subdivisions > finest.curve_subdivisions,
+        ),
+    ];
+    if let Some((field, _)) = steps.iter().find(|(_, too_fine)| *too_fine) {
+        return Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            format!("{field} is finer than this server simulates"),
+        ));
+    }
+
+    // Adaptive steps can shrink all the way down to MIN_STEP_SCALE, each one a point
+    let force_scale = match settings.integrator {
+        Integrator::Adaptive { .. } => 1.0 / MIN_STEP_SCALE,
+        Integrator::Euler | Integrator::Rk4 => 1.0,
+    };
+    let estimate: f64 = track
+        .sections
+        .iter()
+        .map(|section| match section {
+            TrackSection::Force {
+                transitions,
+                domain,
+                fixed_speed,
+                ..
+            } => {
+                let per_step = match domain {
+                    TransitionDomain::Time => settings.time_step,
+                    TransitionDomain::Distance => {
+                        fixed_speed.unwrap_or(ESTIMATE_SPEED) * settings.time_step
+                    }
+                };
+                transitions.length() / per_step * force_scale
+            }
+            TrackSection::Curved { .. } => settings.curve_subdivisions as f64,
+            TrackSection::Straight { length, .. }
+            | TrackSection::HoldingBrake { length, .. }
+            | TrackSection::Tilt { length, .. }
+            | TrackSection::Transfer { length, .. }
+            | TrackSection::Launch { length, .. }
+            | TrackSection::Lift { length, .. }
+            | TrackSection::Brake { length, .. }
+            | TrackSection::Spike { length, .. }
+            | TrackSection::Roll { length, .. } => length / settings.spatial_step,
+        })
+        .sum();
+    // Rolling back can retrace everything simulated so far
+    let estimate = if settings.rollback {
+        2.0 * estimate
+    } else {
+        estimate
+    };
+    // NaN from a broken length is refused too
+    if estimate.is_nan() || estimate > MAX_STEPS {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!("track would take about {estimate:.0} steps to simulate, the limit is {MAX_STEPS:.0}"),
+        ));
+    }
+    Ok(())
+}
+
+async fn spline(
+    Json(track): Json<Track>,
+) -> Result<Json<(TrackSpline, Vec<f64>)>, (StatusCode, String)> {
+    check_budget(&track)?;
+    simulate(move || track.try_get_spline()).await
+}
+
+async fn stats(Json(track): Json<Track>) -> Result<Json<TrackStats>, (StatusCode, String)> {
+    check_budget(&track)?;
+    simulate(move || track.stats()).await
+}
+
+async fn validate(Json(track): Json<Track>) -> Result<Json<HealthReport>, (StatusCode, String)> {
+    check_budget(&track)?;
+    simulate(move || {
+        track.check()?;
+        Ok(track.health())
+    })
+    .await
+}
+
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+struct DistanceQuery {
+    distance: f64,
+}
+
+async fn compiled_stats(
+    State(track): State<Arc<CompiledTrack>>,
+) -> Result<Json<TrackStats>, (StatusCode, String)> {
+    simulate(move || track.stats()).await
+}
+
+async fn compiled_forces(
+    State(track): State<Arc<CompiledTrack>>,
+    Query(query): Query<DistanceQuery>,
+) -> Result<Json<Option<Forces>>, (StatusCode, String)> {
+    simulate(move || track.forces(query.distance)).await
+}
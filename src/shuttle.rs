@@ -0,0 +1,114 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    track::{step_dt, Track},
+    TrackPoint, TrackSpline,
+};
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SwingLaunch {
+    pub launch_distance: f64,
+    // Speed the train is launched to on each pass, alternating forwards and backwards
+    pub passes: Vec<f64>,
+}
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ShuttleRun {
+    pub spline: TrackSpline,
+    pub pass_start_times: Vec<f64>,
+    // Passes that made it back through the launch. Fewer than asked for when the train
+    // runs off either end of the track or stops again on the way back, and the timeline
+    // ends there
+    pub completed_passes: usize,
+}
+
+impl SwingLaunch {
+    pub fn simulate(&self, track: &Track) -> ShuttleRun {
+        let path: Vec<TrackPoint> = track
+            .make_splines()
+            .iter()
+            .flat_map(|s| s.points.iter())
+            .filter(|p| p.velocity >= 0.0)
+            .copied()
+            .collect();
+        let mut run = ShuttleRun::default();
+        if path.len() < 2 {
+            return run;
+        }
+
+        let mut launch = path.len() - 1;
+        let mut distance = 0.0;
+        for i in 1..path.len() {
+            distance += (path[i].pos - path[i - 1].pos).length();
+            if distance >= self.launch_distance {
+                launch = i;
+                break;
+            }
+        }
+
+        let mut time = path[launch].time;
+        let mut forward = true;
+
+        for &speed in &self.passes {
+            run.pass_start_times.push(time);
+            let mut index = launch;
+            let mut velocity = speed;
+            let mut reversed = false;
+
+            loop {
+                let next = if forward {
+                    index + 1
+                } else {
+                    index.wrapping_sub(1)
+                };
+                if next >= path.len() {
+                    return run;
+                }
+
+                let last_point = TrackPoint {
+                    velocity,
+                    ..path[index]
+                };
+                let point = TrackPoint {
+                    velocity,
+                    ..path[next]
+                };
+                let dp = (point.pos - last_point.pos).length();
+                let next_velocity = track.friction(&last_point, &point, step_dt(dp, velocity));
+
+                if next_velocity <= 0.0 {
+                    if reversed {
+                        return run;
+                    }
+                    reversed = true;
+                    forward = !forward;
+                    velocity = 0.0;
+                    continue;
+                }
+
+                time += 2.0 * dp / (velocity + next_velocity);
+                velocity = next_velocity;
+                index = next;
+                run.spline.points.push(TrackPoint {
+                    velocity: if forward { velocity } else { -velocity },
+                    time,
+                    ..path[index]
+                });
+
+                if reversed && index == launch {
+                    run.completed_passes += 1;
+                    break;
+                }
+            }
+        }
+
+        run
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{TrackPoint, TrackSpline};
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SimplifyTolerance {
+    // Meters a dropped point may sit off the line between the points kept either side
+    pub position: f64,
+    // Degrees a dropped point's orientation may differ from the one blended between them
+    pub angle: f64,
+}
+
+impl Default for SimplifyTolerance {
+    fn default() -> Self {
+        Self {
+            position: 0.005,
+            angle: 0.5,
+        }
+    }
+}
+
+// How far past the tolerance `point` is when left out between `a` and `b`, where `t` is
+// how far along the run it sits. Above 1 means it has to stay
+fn excess(
+    a: &TrackPoint,
+    b: &TrackPoint,
+    point: &TrackPoint,
+    t: f64,
+    tolerance: &SimplifyTolerance,
+) -> f64 {
+    let chord = b.pos - a.pos;
+    let offset = point.pos - a.pos;
+    let along = if chord.length_squared() > 0.0 {
+        (offset.dot(chord) / chord.length_squared()).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let position = (offset - chord * along).length();
+    let angle = a
+        .rot
+        .0
+        .slerp(b.rot.0, t)
+        .angle_between(point.rot.0)
+        .to_degrees();
+
+    (position / tolerance.position).max(angle / tolerance.angle)
+}
+
+impl TrackSpline {
+    // Ramer-Douglas-Peucker over position and orientation. Straights and gentle curves
+    // collapse to a handful of points while tight elements keep what they need
+    pub fn simplify(&self, tolerance: &SimplifyTolerance) -> TrackSpline {
+        let points = &self.points;
+        if points.len() < 3 {
+            return self.clone();
+        }
+        let distances = self.cumulative_distances();
+        let mut keep = vec![false; points.len()];
+        keep[0] = true;
+        keep[points.len() - 1] = true;
+
+        let mut runs = vec![(0, points.len() - 1)];
+        while let Some((first, last)) = runs.pop() {
+            let span = distances[last] - distances[first];
+            let worst = (first + 1..last)
+                .map(|i| {
+                    let t = if span > 0.0 {
+                        (distances[i] - distances[first]) / span
+                    } else {
+                        0.0
+                    };
+                    (
+                        i,
+                        excess(&points[first], &points[last], &points[i], t, tolerance),
+                    )
+                })
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+
+            if let Some((i, excess)) = worst {
+                if excess > 1.0 {
+                    keep[i] = true;
+                    runs.push((first, i));
+                    runs.push((i, last));
+                }
+            }
+        }
+
+        TrackSpline {
+            points: points
+                .iter()
+                .zip(keep)
+                .filter_map(|(point, keep)| keep.then_some(*point))
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use glam::{DQuat, DVec3};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::{DT, G},
+    math::euler,
+    track::{roll_rate_to, Track},
+    transitions::{FastTransitions, Forces, RollMode, TransitionDomain},
+    TrackPoint,
+};
+
+// How force sections are stepped through time
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Integrator {
+    #[default]
+    Euler,
+    Rk4,
+    // RK4 with step doubling. The step shrinks until a full step and two half steps agree
+    // to within `tolerance` (meters plus radians) and grows again on easy stretches
+    Adaptive {
+        tolerance: f64,
+    },
+}
+
+// Resolution of the integrators, coarser steps simulate faster at the cost of accuracy
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SimulationSettings {
+    // Seconds per step of force sections
+    pub time_step: f64,
+    // Meters per step of straight, launch, lift and brake sections
+    pub spatial_step: f64,
+    // Steps per curved section regardless of its length
+    pub curve_subdivisions: u32,
+    pub integrator: Integrator,
+    // A train that stops partway through a section rolls back down the track it came up
+    // instead of the track ending there
+    pub rollback: bool,
+}
+
+impl Default for SimulationSettings {
+    fn default() -> Self {
+        Self {
+            time_step: DT,
+            spatial_step: 0.01,
+            curve_subdivisions: 200,
+            integrator: Integrator::Euler,
+            rollback: false,
+        }
+    }
+}
+
+impl SimulationSettings {
+    // Fast enough to resimulate on every edit
+    pub fn preview() -> Self {
+        Self {
+            time_step: 4.0 * DT,
+            spatial_step: 0.05,
+            curve_subdivisions: 50,
+            integrator: Integrator::Euler,
+            rollback: false,
+        }
+    }
+
+    // For final exports
+    pub fn high_resolution() -> Self {
+        Self {
+            time_step: DT / 4.0,
+            spatial_step: 0.0025,
+            curve_subdivisions: 800,
+            integrator: Integrator::Rk4,
+            rollback: false,
+        }
+    }
+}
+
+// The adaptive step stays within these multiples of the configured time step
+pub(crate) const MIN_STEP_SCALE: f64 = 1.0 / 64.0;
+const MAX_STEP_SCALE: f64 = 16.0;
+
+// World-space rotation rate of the track frame. Same model as the Euler step in
+// Track::integrate_section, taken as a rate instead of a finite rotation
+fn angular_velocity(rot: DQuat, forces: Forces, velocity: f64) -> DVec3 {
+    let (forward, up, right) = (rot * DVec3::Z, rot * DVec3::Y, rot * DVec3::NEG_X);
+    let roll = if forces.roll.abs() > 0.01 {
+        forces.roll.to_radians()
+    } else {
+        0.0
+    };
+    let normal_force = (forces.vert - up.y) * G;
+    let lateral_force = (forces.lat - right.y) * G;
+
+    forward * roll + right * (normal_force / velocity) - up * (lateral_force / velocity)
+}
+
+// Within a step the speed follows the height without friction, the proper friction
+// update is applied once the step is taken
+fn rk4_step(
+    forces_at: &impl Fn(f64) -> Forces,
+    time: f64,
+    (pos, rot): (DVec3, DQuat),
+    velocity: f64,
+    fixed_speed: bool,
+    h: f64,
+) -> (DVec3, DQuat) {
+    let speed = |p: DVec3| {
+        if fixed_speed {
+            velocity
+        } else {
+            (velocity * velocity - 2.0 * G * (p.y - pos.y))
+                .max(f64::EPSILON)
+                .sqrt()
+        }
+    };
+    let derivative = |time: f64, p: DVec3, rot: DQuat| {
+        let velocity = speed(p);
+        let omega = angular_velocity(rot, forces_at(time), velocity);
+        (
+            rot * DVec3::Z * velocity,
+            DQuat::from_xyzw(omega.x, omega.y, omega.z, 0.0) * rot * 0.5,
+        )
+    };
+    let nudge = |rot: DQuat, d: DQuat, h: f64| (rot + d * h).normalize();
+
+    let (p1, q1) = derivative(time, pos, rot);
+    let (p2, q2) = derivative(
+        time + 0.5 * h,
+        pos + p1 * (0.5 * h),
+        nudge(rot, q1, 0.5 * h),
+    );
+    let (p3, q3) = derivative(
+        time + 0.5 * h,
+        pos + p2 * (0.5 * h),
+        nudge(rot, q2, 0.5 * h),
+    );
+    let (p4, q4) = derivative(time + h, pos + p3 * h, nudge(rot, q3, h));
+
+    (
+        pos + (p1 + (p2 + p3) * 2.0 + p4) * (h / 6.0),
+        nudge(rot, q1 + (q2 + q3) * 2.0 + q4, h / 6.0),
+    )
+}
+
+impl Track {
+    // Higher order counterpart of the Euler loop for force sections
+    pub(crate) fn integrate_forces(
+        &self,
+        transitions: &FastTransitions,
+        (domain, roll_mode): (TransitionDomain, RollMode),
+        start: TrackPoint,
+        start_forces: Forces,
+        fixed_speed: Option<f64>,
+    ) -> Vec<TrackPoint> {
+        let settings = self.config.simulation();
+        let dt = settings.time_step;
+        let tolerance = match settings.integrator {
+            Integrator::Adaptive { tolerance } => Some(tolerance),
+            _ => None,
+        };
+        // Transitions end exactly at their length, the last stage may land on it
+        let end = transitions.length;
+        let forces_at = |time: f64| {
+            transitions
+                .evaluate(time.min(end * (1.0 - f64::EPSILON)))
+                .unwrap_or_default()
+                + start_forces
+        };
+
+        let entry_bank = euler(&start).2;
+        let mut points: Vec<TrackPoint> = Vec::with_capacity((end / dt) as usize);
+        let mut velocity = fixed_speed.unwrap_or(start.velocity);
+        let mut state = (start.pos, start.rot.0);
+        let mut time = 0.0;
+        // How far through the transitions, in seconds or meters
+        let mut along = 0.0;
+        let mut h = dt;
+        let fixed = fixed_speed.is_some();
+
+        while along < end && velocity > 0.0 {
+            // Transitions covered per second, the speed is held over a step
+            let rate = match domain {
+                TransitionDomain::Time => 1.0,
+                TransitionDomain::Distance => velocity,
+            };
+            let step = h.min((end - along) / rate);
+            // Banks are chased by a roll rate held over the step
+            let roll = (roll_mode == RollMode::Bank).then(|| {
+                let bank = entry_bank + transitions.roll_at(along + step * rate);
+                roll_rate_to(state.1, bank, step)
+            });
+            let forces_in_step = |t: f64| {
+                let forces = forces_at(along + (t - time) * rate);
+                match roll {
+                    Some(roll) => Forces { roll, ..forces },
+                    None => forces,
+                }
+            };
+            let next = match tolerance {
+                None => rk4_step(&forces_in_step, time, state, velocity, fixed, step),
+                Some(tolerance) => {
+                    let full = rk4_step(&forces_in_step, time, state, velocity, fixed, step);
+                    let half = rk4_step(&forces_in_step, time, state, velocity, fixed, 0.5 * step);
+                    let half = rk4_step(
+                        &forces_in_step,
+                        time + 0.5 * step,
+                        half,
+                        velocity,
+                        fixed,
+                        0.5 * step,
+                    );
+                    let error = (full.0 - half.0).length() + full.1.angle_between(half.1);
+                    if error > tolerance && h > dt * MIN_STEP_SCALE {
+                        h *= 0.5;
+                        continue;
+                    }
+                    if error < tolerance / 32.0 {
+                        h = (h * 2.0).min(dt * MAX_STEP_SCALE);
+                    }
+                    half
+                }
+            };
+            state = next;
+
+            let point = TrackPoint {
+                pos: state.0,
+                rot: state.1.into(),
+                velocity,
+                time: start.time + time + step,
+                section_time: 0.0,
+                distance: None,
+                enclosed: None,
+            };
+            if fixed_speed.is_none() {
+                velocity = self.friction(points.last().unwrap_or(&point), &point, step);
+            }
+            if velocity <= 0.0 {
+                break;
+            }
+            points.push(TrackPoint { velocity, ..point });
+            time += step;
+            along += step * rate;
+        }
+        points
+    }
+}
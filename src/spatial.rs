@@ -0,0 +1,139 @@
+use glam::DVec3;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{join::lerp_points, TrackPoint, TrackSpline};
+
+const CHUNK_SIZE: usize = 64;
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct RayHit {
+    // Index of the first point of the segment that was hit
+    pub index: usize,
+    pub ray_distance: f64,
+    pub point: TrackPoint,
+}
+
+struct Chunk {
+    start: usize,
+    end: usize,
+    min: DVec3,
+    max: DVec3,
+}
+
+// Bounding boxes over fixed size runs of segments
+pub struct SpatialIndex {
+    chunks: Vec<Chunk>,
+}
+
+impl SpatialIndex {
+    pub fn new(spline: &TrackSpline) -> Self {
+        let segments = spline.points.len().saturating_sub(1);
+        let chunks = (0..segments)
+            .step_by(CHUNK_SIZE)
+            .map(|start| {
+                let end = (start + CHUNK_SIZE).min(segments);
+                let (min, max) = spline.points[start..=end]
+                    .iter()
+                    .fold((DVec3::INFINITY, DVec3::NEG_INFINITY), |(min, max), p| {
+                        (min.min(p.pos), max.max(p.pos))
+                    });
+                Chunk {
+                    start,
+                    end,
+                    min,
+                    max,
+                }
+            })
+            .collect();
+
+        Self { chunks }
+    }
+
+    pub fn raycast(
+        &self,
+        spline: &TrackSpline,
+        origin: DVec3,
+        dir: DVec3,
+        radius: f64,
+    ) -> Option<RayHit> {
+        let dir = dir.try_normalize()?;
+        let mut best: Option<RayHit> = None;
+
+        for chunk in &self.chunks {
+            let Some(entry) = ray_box(
+                origin,
+                dir,
+                chunk.min - DVec3::splat(radius),
+                chunk.max + radius,
+            ) else {
+                continue;
+            };
+            if best.is_some_and(|b| b.ray_distance < entry) {
+                continue;
+            }
+
+            for i in chunk.start..chunk.end {
+                let (a, b) = (&spline.points[i], &spline.points[i + 1]);
+                let (ray_distance, t, gap) = ray_segment(origin, dir, a.pos, b.pos);
+                if gap <= radius && best.is_none_or(|b| ray_distance < b.ray_distance) {
+                    best = Some(RayHit {
+                        index: i,
+                        ray_distance,
+                        point: lerp_points(a, b, t),
+                    });
+                }
+            }
+        }
+
+        best
+    }
+}
+
+impl TrackSpline {
+    pub fn spatial_index(&self) -> SpatialIndex {
+        SpatialIndex::new(self)
+    }
+
+    pub fn raycast(&self, origin: DVec3, dir: DVec3, radius: f64) -> Option<RayHit> {
+        self.spatial_index().raycast(self, origin, dir, radius)
+    }
+}
+
+// Distance along the ray where it enters the box
+fn ray_box(origin: DVec3, dir: DVec3, min: DVec3, max: DVec3) -> Option<f64> {
+    let inv = dir.recip();
+    let t1 = (min - origin) * inv;
+    let t2 = (max - origin) * inv;
+    let near = t1.min(t2).max_element().max(0.0);
+    let far = t1.max(t2).min_element();
+    (near <= far).then_some(near)
+}
+
+// Returns (distance along ray, parameter along segment, closest distance between them)
+fn ray_segment(origin: DVec3, dir: DVec3, a: DVec3, b: DVec3) -> (f64, f64, f64) {
+    let seg = b - a;
+    let w = origin - a;
+    let seg_len2 = seg.length_squared();
+    let d = dir.dot(seg);
+    let e = dir.dot(w);
+    let f = seg.dot(w);
+    let denom = seg_len2 - d * d;
+
+    let mut t = if seg_len2 > 0.0 && denom > 1e-12 {
+        ((f - d * e) / denom).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let mut s = (t * d - e).max(0.0);
+    if seg_len2 > 0.0 {
+        t = ((s * d + f) / seg_len2).clamp(0.0, 1.0);
+        s = (t * d - e).max(0.0);
+    }
+
+    let gap = ((origin + dir * s) - (a + seg * t)).length();
+    (s, t, gap)
+}
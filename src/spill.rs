@@ -0,0 +1,222 @@
+use std::{
+    fs,
+    io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    mem,
+    path::{Path, PathBuf},
+};
+
+use glam::{DQuat, DVec3};
+
+use crate::{math::WrapperDQuat, TrackPoint, TrackSpline};
+
+// pos, rot, velocity, time, section time, distance and enclosed as little endian f64s,
+// with NaN standing in for a missing distance or enclosed flag
+const FIELDS: usize = 12;
+const RECORD_SIZE: usize = FIELDS * 8;
+
+fn encode(point: &TrackPoint) -> [u8; RECORD_SIZE] {
+    let rot = point.rot.0;
+    let fields: [f64; FIELDS] = [
+        point.pos.x,
+        point.pos.y,
+        point.pos.z,
+        rot.x,
+        rot.y,
+        rot.z,
+        rot.w,
+        point.velocity,
+        point.time,
+        point.section_time,
+        point.distance.unwrap_or(f64::NAN),
+        point.enclosed.map_or(f64::NAN, |e| e as u8 as f64),
+    ];
+
+    let mut record = [0; RECORD_SIZE];
+    for (chunk, field) in record.chunks_exact_mut(8).zip(fields) {
+        chunk.copy_from_slice(&field.to_le_bytes());
+    }
+    record
+}
+
+fn decode(record: &[u8; RECORD_SIZE]) -> TrackPoint {
+    let mut f = [0.0; FIELDS];
+    for (field, chunk) in f.iter_mut().zip(record.chunks_exact(8)) {
+        *field = f64::from_le_bytes(chunk.try_into().unwrap());
+    }
+    TrackPoint {
+        pos: DVec3::new(f[0], f[1], f[2]),
+        rot: WrapperDQuat(DQuat::from_xyzw(f[3], f[4], f[5], f[6])),
+        velocity: f[7],
+        time: f[8],
+        section_time: f[9],
+        distance: (!f[10].is_nan()).then_some(f[10]),
+        enclosed: (!f[11].is_nan()).then_some(f[11] != 0.0),
+    }
+}
+
+// A spline's points in a file instead of memory, read back a point or a chunk at a time
+#[derive(Clone, Debug)]
+pub struct SpilledSpline {
+    path: PathBuf,
+    len: usize,
+}
+
+impl SpilledSpline {
+    pub fn create(path: impl Into<PathBuf>, spline: &TrackSpline) -> io::Result<Self> {
+        let path = path.into();
+        let mut writer = BufWriter::new(fs::File::create(&path)?);
+        for point in &spline.points {
+            writer.write_all(&encode(point))?;
+        }
+        writer.flush()?;
+        Ok(Self {
+            path,
+            len: spline.points.len(),
+        })
+    }
+
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let size = fs::metadata(&path)?.len() as usize;
+        if !size.is_multiple_of(RECORD_SIZE) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} is not a spilled spline", path.display()),
+            ));
+        }
+        Ok(Self {
+            path,
+            len: size / RECORD_SIZE,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, index: usize) -> io::Result<Option<TrackPoint>> {
+        if index >= self.len {
+            return Ok(None);
+        }
+        let mut file = fs::File::open(&self.path)?;
+        file.seek(SeekFrom::Start((index * RECORD_SIZE) as u64))?;
+        let mut record = [0; RECORD_SIZE];
+        file.read_exact(&mut record)?;
+        Ok(Some(decode(&record)))
+    }
+
+    // Streams the points in order without holding more than a read buffer
+    pub fn points(&self) -> io::Result<impl Iterator<Item = io::Result<TrackPoint>>> {
+        let mut reader = BufReader::new(fs::File::open(&self.path)?);
+        Ok((0..self.len).map(move |_| {
+            let mut record = [0; RECORD_SIZE];
+            reader.read_exact(&mut record)?;
+            Ok(decode(&record))
+        }))
+    }
+
+    pub fn load(&self) -> io::Result<TrackSpline> {
+        Ok(TrackSpline {
+            points: self.points()?.collect::<io::Result<_>>()?,
+        })
+    }
+}
+
+#[derive(Debug)]
+enum Stored {
+    Memory(TrackSpline),
+    Disk(SpilledSpline),
+}
+
+// Splines from a sweep or batch run kept in memory up to `memory_cap` bytes of points, with
+// everything after that spilled to files in `dir`. The files are removed on drop
+#[derive(Debug)]
+pub struct SplineStore {
+    dir: PathBuf,
+    memory_cap: usize,
+    in_memory: usize,
+    splines: Vec<Stored>,
+}
+
+impl SplineStore {
+    pub fn new(dir: impl Into<PathBuf>, memory_cap: usize) -> Self {
+        Self {
+            dir: dir.into(),
+            memory_cap,
+            in_memory: 0,
+            splines: Vec::new(),
+        }
+    }
+
+    // Returns the index to look the spline up by
+    pub fn push(&mut self, spline: TrackSpline) -> io::Result<usize> {
+        let size = spline.points.len() * mem::size_of::<TrackPoint>();
+        let stored = if self.in_memory + size <= self.memory_cap {
+            self.in_memory += size;
+            Stored::Memory(spline)
+        } else {
+            fs::create_dir_all(&self.dir)?;
+            let path = self.dir.join(format!("{}.points", self.splines.len()));
+            Stored::Disk(SpilledSpline::create(path, &spline)?)
+        };
+        self.splines.push(stored);
+        Ok(self.splines.len() - 1)
+    }
+
+    pub fn len(&self) -> usize {
+        self.splines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.splines.is_empty()
+    }
+
+    // Bytes of points held in memory
+    pub fn memory_used(&self) -> usize {
+        self.in_memory
+    }
+
+    pub fn is_spilled(&self, index: usize) -> bool {
+        matches!(self.splines.get(index), Some(Stored::Disk(_)))
+    }
+
+    // A spilled spline is read back in full, use `for_each_point` to stream it instead
+    pub fn get(&self, index: usize) -> io::Result<Option<TrackSpline>> {
+        match self.splines.get(index) {
+            Some(Stored::Memory(spline)) => Ok(Some(spline.clone())),
+            Some(Stored::Disk(spilled)) => spilled.load().map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub fn for_each_point(&self, index: usize, mut f: impl FnMut(&TrackPoint)) -> io::Result<()> {
+        match self.splines.get(index) {
+            Some(Stored::Memory(spline)) => spline.points.iter().for_each(f),
+            Some(Stored::Disk(spilled)) => {
+                for point in spilled.points()? {
+                    f(&point?);
+                }
+            }
+            None => {}
+        }
+        Ok(())
+    }
+}
+
+impl Drop for SplineStore {
+    fn drop(&mut self) {
+        for stored in &self.splines {
+            if let Stored::Disk(spilled) = stored {
+                let _ = fs::remove_file(spilled.path());
+            }
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use glam::DVec3;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{track::Track, TrackPoint, TrackSpline};
+
+// Meters per second of extra entry speed searched up to
+const MAX_EXTRA_SPEED: f64 = 100.0;
+const ITERATIONS: usize = 30;
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Stall {
+    pub section: usize,
+    // Meters along the track the train stops at
+    pub distance: f64,
+    #[cfg_attr(feature = "ts", ts(type = "[number, number, number]"))]
+    pub position: DVec3,
+    // Meters per second more the train would need entering the section to clear it, and the
+    // joules per kilogram of kinetic energy that adds. None if no speed up to
+    // MAX_EXTRA_SPEED does
+    pub extra_speed: Option<f64>,
+    pub deficit_energy: Option<f64>,
+}
+
+#[derive(Clone, Debug)]
+pub enum SplineResult {
+    Complete(Vec<TrackSpline>),
+    // The splines up to and including the section the train stops in
+    Stalled {
+        splines: Vec<TrackSpline>,
+        stall: Stall,
+    },
+}
+
+impl SplineResult {
+    pub fn splines(&self) -> &[TrackSpline] {
+        match self {
+            SplineResult::Complete(splines) | SplineResult::Stalled { splines, .. } => splines,
+        }
+    }
+
+    pub fn stall(&self) -> Option<&Stall> {
+        match self {
+            SplineResult::Complete(_) => None,
+            SplineResult::Stalled { stall, .. } => Some(stall),
+        }
+    }
+}
+
+impl Track {
+    // Like `make_splines`, but says where the train stops if it doesn't make it to the end
+    pub fn try_make_splines(&self) -> SplineResult {
+        let splines = self.make_splines();
+        let Some(section) = self.stalled(&splines) else {
+            return SplineResult::Complete(splines);
+        };
+        let stall = self.stall(&splines, section);
+        SplineResult::Stalled { splines, stall }
+    }
+
+    fn stall(&self, splines: &[TrackSpline], section: usize) -> Stall {
+        let before = &splines[..section.min(splines.len())];
+        let (entry, forces) = self.state_after(before);
+        let stopped = splines.get(section).and_then(|spline| spline.points.last());
+
+        // The smallest extra entry speed the section is cleared with
+        let clears = |extra: f64| {
+            let entry = TrackPoint {
+                velocity: entry.velocity + extra,
+                ..entry
+            };
+            let spline = self.make_spline(&self.sections[section], entry, forces);
+            !self.falls_short(&self.sections[section], &entry, &spline)
+        };
+        let extra_speed = clears(MAX_EXTRA_SPEED).then(|| {
+            let (mut low, mut high) = (0.0, MAX_EXTRA_SPEED);
+            for _ in 0..ITERATIONS {
+                let mid = 0.5 * (low + high);
+                if clears(mid) {
+                    high = mid;
+                } else {
+                    low = mid;
+                }
+            }
+            high
+        });
+        let deficit_energy = extra_speed.map(|extra| {
+            let v = entry.velocity.max(0.0);
+            0.5 * ((v + extra).powi(2) - v * v)
+        });
+
+        Stall {
+            section,
+            distance: before.iter().map(|s| s.total_distance()).sum::<f64>()
+                + splines.get(section).map_or(0.0, |s| s.total_distance()),
+            position: stopped.map_or(entry.pos, |point| point.pos),
+            extra_speed,
+            deficit_energy,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use glam::DVec3;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{join::lerp_points, math::WrapperDQuat, TrackSpline};
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct StationMarker {
+    pub distance: f64,
+    #[cfg_attr(feature = "ts", ts(type = "[number, number, number]"))]
+    pub pos: DVec3,
+    #[cfg_attr(feature = "ts", ts(type = "[number, number, number, number]"))]
+    pub rot: WrapperDQuat,
+    pub label: String,
+}
+
+// Station labels in kilometers and meters, e.g. 1250 m is "1+250"
+pub fn station_label(distance: f64) -> String {
+    let meters = distance.round() as i64;
+    format!("{}+{:03}", meters / 1000, meters % 1000)
+}
+
+impl TrackSpline {
+    pub fn stationing(&self, interval: f64) -> Vec<StationMarker> {
+        let mut markers = Vec::new();
+        if interval <= 0.0 {
+            return markers;
+        }
+
+        let mut distance = 0.0;
+        let mut next_marker = 0.0;
+        for pair in self.points.windows(2) {
+            let delta_dist = (pair[1].pos - pair[0].pos).length();
+            distance += delta_dist;
+            if delta_dist <= 0.0 {
+                continue;
+            }
+
+            while next_marker <= distance {
+                let t = 1.0 - (distance - next_marker) / delta_dist;
+                let point = lerp_points(&pair[0], &pair[1], t);
+                markers.push(StationMarker {
+                    distance: next_marker,
+                    pos: point.pos,
+                    rot: point.rot,
+                    label: station_label(next_marker),
+                });
+                next_marker += interval;
+            }
+        }
+
+        markers
+    }
+}
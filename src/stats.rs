@@ -0,0 +1,193 @@
+use glam::DVec3;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::TrackError,
+    mechanics::{LaunchRequirement, LiftLoad},
+    output::OutputOptions,
+    pair_forces, pair_long,
+    track::Track,
+    TrackPoint, TrackSpline,
+};
+
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SplineStats {
+    pub length: f64,
+    pub duration: f64,
+    pub min_speed: f64,
+    pub max_speed: f64,
+    pub max_vert: f64,
+    pub min_vert: f64,
+    // Largest either way
+    pub max_lat: f64,
+    // Degrees, the furthest the felt force leans from straight into the seat either way,
+    // see `AnalysisSample::sagittal`
+    pub max_sagittal: f64,
+    pub max_coronal: f64,
+    #[cfg_attr(feature = "ts", ts(type = "[number, number, number]"))]
+    pub highest: DVec3,
+    #[cfg_attr(feature = "ts", ts(type = "[number, number, number]"))]
+    pub lowest: DVec3,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SectionStats {
+    pub section: usize,
+    // Meters along the track the section starts at
+    pub start: f64,
+    pub stats: SplineStats,
+}
+
+// Everything the frontends, packages, the server and batch runs report about a track
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct TrackStats {
+    pub total: SplineStats,
+    pub sections: Vec<SectionStats>,
+    // Meters inside tunnels and other enclosures, overlaps counted once
+    pub enclosed_length: f64,
+    // For a train of the config's `train_mass`
+    pub lift_loads: Vec<LiftLoad>,
+    pub launch_requirements: Vec<LaunchRequirement>,
+}
+
+impl SplineStats {
+    // Nothing added yet, fill in with `add_point`, `add_pair` and `merge` then `finish`
+    pub(crate) fn empty() -> Self {
+        Self {
+            min_speed: f64::INFINITY,
+            max_vert: f64::NEG_INFINITY,
+            min_vert: f64::INFINITY,
+            highest: DVec3::splat(f64::NEG_INFINITY),
+            lowest: DVec3::splat(f64::INFINITY),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn add_point(&mut self, point: &TrackPoint) {
+        self.min_speed = self.min_speed.min(point.velocity);
+        self.max_speed = self.max_speed.max(point.velocity);
+        if point.pos.y > self.highest.y {
+            self.highest = point.pos;
+        }
+        if point.pos.y < self.lowest.y {
+            self.lowest = point.pos;
+        }
+    }
+
+    // Pairs with no length between them carry no forces and are left out
+    pub(crate) fn add_pair(&mut self, last_point: &TrackPoint, point: &TrackPoint) {
+        let Some(forces) = pair_forces(last_point, point) else {
+            return;
+        };
+        self.length += (point.pos - last_point.pos).length();
+        self.max_vert = self.max_vert.max(forces.vert);
+        self.min_vert = self.min_vert.min(forces.vert);
+        self.max_lat = self.max_lat.max(forces.lat.abs());
+        let long = pair_long(last_point, point);
+        let sagittal = libm::atan2(long, forces.vert).to_degrees();
+        let coronal = libm::atan2(forces.lat, forces.vert).to_degrees();
+        self.max_sagittal = self.max_sagittal.max(sagittal.abs());
+        self.max_coronal = self.max_coronal.max(coronal.abs());
+    }
+
+    // `other` covers the points after these, ties go to the earlier point
+    pub(crate) fn merge(&mut self, other: &SplineStats) {
+        self.length += other.length;
+        self.min_speed = self.min_speed.min(other.min_speed);
+        self.max_speed = self.max_speed.max(other.max_speed);
+        self.max_vert = self.max_vert.max(other.max_vert);
+        self.min_vert = self.min_vert.min(other.min_vert);
+        self.max_lat = self.max_lat.max(other.max_lat);
+        self.max_sagittal = self.max_sagittal.max(other.max_sagittal);
+        self.max_coronal = self.max_coronal.max(other.max_coronal);
+        if other.highest.y > self.highest.y {
+            self.highest = other.highest;
+        }
+        if other.lowest.y < self.lowest.y {
+            self.lowest = other.lowest;
+        }
+    }
+
+    pub(crate) fn finish(mut self, first: &TrackPoint, last: &TrackPoint) -> Self {
+        self.duration = last.time - first.time;
+        // A single point has no forces to speak of
+        if self.max_vert < self.min_vert {
+            (self.max_vert, self.min_vert) = (1.0, 1.0);
+        }
+        self
+    }
+}
+
+impl TrackSpline {
+    pub fn stats(&self) -> SplineStats {
+        let (Some(first), Some(last)) = (self.points.first(), self.points.last()) else {
+            return SplineStats::default();
+        };
+
+        let mut stats = SplineStats::empty();
+        for point in &self.points {
+            stats.add_point(point);
+        }
+        for pair in self.points.windows(2) {
+            stats.add_pair(&pair[0], &pair[1]);
+        }
+        stats.finish(first, last)
+    }
+}
+
+impl Track {
+    pub fn stats(&self) -> Result<TrackStats, TrackError> {
+        Ok(self.try_get_spline_with_stats(&OutputOptions::default())?.1)
+    }
+
+    // The spline and its stats from a single simulation. The stats are always of the full
+    // resolution spline, whatever the options decimate the returned one to
+    pub fn try_get_spline_with_stats(
+        &self,
+        options: &OutputOptions,
+    ) -> Result<((TrackSpline, Vec<f64>), TrackStats), TrackError> {
+        self.check()?;
+        let splines = self.make_splines();
+        self.check_splines(&splines)?;
+        let stats = self.stats_of(&splines);
+        Ok((self.assemble(splines, options), stats))
+    }
+
+    // `splines` from make_splines
+    pub(crate) fn stats_of(&self, splines: &[TrackSpline]) -> TrackStats {
+        let mut start = 0.0;
+        let sections = splines
+            .iter()
+            .enumerate()
+            .map(|(section, spline)| {
+                let stats = spline.stats();
+                let section = SectionStats {
+                    section,
+                    start,
+                    stats,
+                };
+                start += stats.length;
+                section
+            })
+            .collect();
+        let total = TrackSpline::concat(splines).stats();
+
+        TrackStats {
+            total,
+            sections,
+            enclosed_length: self.enclosed_length(total.length),
+            lift_loads: self.lift_loads_of(splines, self.config.train_mass()),
+            launch_requirements: self.launch_requirements_of(splines, self.config.train_mass()),
+        }
+    }
+}
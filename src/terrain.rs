@@ -0,0 +1,169 @@
+use glam::{DVec2, DVec3};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{track::Track, TrackSpline};
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        tag = "type",
+        rename_all = "camelCase",
+        rename_all_fields = "camelCase"
+    )
+)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Terrain {
+    Flat {
+        height: f64,
+    },
+    // Ground heights on a regular grid in the xz plane, row by row along z. Queries
+    // outside the grid take the height at the nearest edge
+    Heightmap {
+        #[cfg_attr(feature = "ts", ts(type = "[number, number]"))]
+        origin: DVec2,
+        spacing: f64,
+        columns: usize,
+        heights: Vec<f64>,
+    },
+}
+
+impl Default for Terrain {
+    fn default() -> Self {
+        Terrain::Flat { height: 0.0 }
+    }
+}
+
+// Stretch where the track runs closer to the ground than required
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct LowClearance {
+    pub start: f64,
+    pub end: f64,
+    pub min_height: f64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct GroundClearance {
+    pub min_height: f64,
+    // Distance along the spline of the lowest point
+    pub distance: f64,
+    pub violations: Vec<LowClearance>,
+}
+
+impl Terrain {
+    pub fn height(&self, x: f64, z: f64) -> f64 {
+        match self {
+            Terrain::Flat { height } => *height,
+            Terrain::Heightmap {
+                origin,
+                spacing,
+                columns,
+                heights,
+            } => {
+                let rows = heights.len() / (*columns).max(1);
+                if rows == 0 || *columns == 0 || *spacing <= 0.0 {
+                    return 0.0;
+                }
+                let at = |col: usize, row: usize| heights[row * columns + col];
+                let cell = |v: f64, n: usize| {
+                    let v = v.clamp(0.0, (n - 1) as f64);
+                    let i = (v.floor() as usize).min(n.saturating_sub(2));
+                    (i, (i + 1).min(n - 1), v - i as f64)
+                };
+                let (c0, c1, tx) = cell((x - origin.x) / spacing, *columns);
+                let (r0, r1, tz) = cell((z - origin.y) / spacing, rows);
+
+                let near = at(c0, r0) + (at(c1, r0) - at(c0, r0)) * tx;
+                let far = at(c0, r1) + (at(c1, r1) - at(c0, r1)) * tx;
+                near + (far - near) * tz
+            }
+        }
+    }
+
+    pub fn height_above(&self, pos: DVec3) -> f64 {
+        pos.y - self.height(pos.x, pos.z)
+    }
+}
+
+impl TrackSpline {
+    // Height of the rails above the ground at each point, which is also the length of
+    // a vertical support there. `heartline_height` is how far the points sit above the rails
+    pub fn heights_above_ground(&self, terrain: &Terrain, heartline_height: f64) -> Vec<f64> {
+        self.points
+            .iter()
+            .map(|p| terrain.height_above(p.pos - (p.rot.0 * DVec3::Y) * heartline_height))
+            .collect()
+    }
+
+    // Points inside any of the `excluded` distance ranges are expected to be near or
+    // below ground and are left out
+    pub fn ground_clearance(
+        &self,
+        terrain: &Terrain,
+        heartline_height: f64,
+        required: f64,
+        excluded: &[(f64, f64)],
+    ) -> GroundClearance {
+        let heights = self.heights_above_ground(terrain, heartline_height);
+        let mut report = GroundClearance {
+            min_height: f64::INFINITY,
+            ..Default::default()
+        };
+        let mut open: Option<LowClearance> = None;
+
+        for (distance, height) in self.cumulative_distances().into_iter().zip(heights) {
+            if excluded
+                .iter()
+                .any(|&(start, end)| (start..=end).contains(&distance))
+            {
+                report.violations.extend(open.take());
+                continue;
+            }
+            if height < report.min_height {
+                report.min_height = height;
+                report.distance = distance;
+            }
+
+            match (&mut open, height < required) {
+                (Some(v), true) => {
+                    v.end = distance;
+                    v.min_height = v.min_height.min(height);
+                }
+                (None, true) => {
+                    open = Some(LowClearance {
+                        start: distance,
+                        end: distance,
+                        min_height: height,
+                    })
+                }
+                (_, false) => report.violations.extend(open.take()),
+            }
+        }
+        report.violations.extend(open);
+        report
+    }
+}
+
+impl Track {
+    pub fn heights_above_ground(&self) -> Vec<f64> {
+        TrackSpline::concat(&self.make_splines())
+            .heights_above_ground(&self.terrain, self.config.heartline_height())
+    }
+
+    pub fn ground_clearance(&self, required: f64) -> GroundClearance {
+        TrackSpline::concat(&self.make_splines()).ground_clearance(
+            &self.terrain,
+            self.config.heartline_height(),
+            required,
+            &self.enclosure_ranges(),
+        )
+    }
+}
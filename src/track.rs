@@ -1,53 +1,431 @@
+use std::f64::consts::FRAC_PI_2;
+
 use glam::{DQuat, DVec3};
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    constants::{DT, G},
-    transitions::{FastTransitions, Forces, Transitions},
+    analysis::AnalysisSample,
+    anchor::heading,
+    banking::Banking,
+    brakes::{BrakeCurve, BrakeType},
+    clearance::Envelope,
+    constants::G,
+    enclosure::Enclosure,
+    error::TrackError,
+    history::History,
+    launch::LaunchProfile,
+    lift::LiftDrive,
+    math::{deg_diff, euler},
+    mechanics::DEFAULT_TRAIN_MASS,
+    output::OutputOptions,
+    pins::Pin,
+    preset::{ConfigPreset, ForceLimits},
+    profile::{self, SectionTiming},
+    roughness::Roughness,
+    simulation::{Integrator, SimulationSettings},
+    terrain::Terrain,
+    transitions::{FastTransitions, Forces, RollMode, TransitionDomain, Transitions},
+    triggers::Trigger,
     TrackPoint, TrackSpline,
 };
 
-#[derive(Clone, Debug, Serialize, Deserialize, Default)]
-#[serde(rename_all = "camelCase")]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct TrackConfig {
-    parameter: f64,
-    resistance: f64,
-    heartline_height: f64,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    preset: Option<ConfigPreset>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    parameter: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    resistance: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    heartline_height: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    limits: Option<ForceLimits>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    roughness: Option<Roughness>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    envelope: Option<Envelope>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    simulation: Option<SimulationSettings>,
+    // Kilograms, for the chain and launch loads in the stats
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    train_mass: Option<f64>,
+}
+
+// Values set directly on the config override the ones from its preset
+impl TrackConfig {
+    pub fn new(parameter: f64, resistance: f64, heartline_height: f64) -> Self {
+        Self {
+            parameter: Some(parameter),
+            resistance: Some(resistance),
+            heartline_height: Some(heartline_height),
+            ..Default::default()
+        }
+    }
+
+    pub fn from_preset(preset: ConfigPreset) -> Self {
+        Self {
+            preset: Some(preset),
+            ..Default::default()
+        }
+    }
+
+    pub fn preset(&self) -> Option<ConfigPreset> {
+        self.preset
+    }
+
+    pub fn parameter(&self) -> f64 {
+        self.parameter
+            .or(self.preset.map(|p| p.values().parameter))
+            .unwrap_or_default()
+    }
+
+    pub fn resistance(&self) -> f64 {
+        self.resistance
+            .or(self.preset.map(|p| p.values().resistance))
+            .unwrap_or_default()
+    }
+
+    pub fn heartline_height(&self) -> f64 {
+        self.heartline_height
+            .or(self.preset.map(|p| p.values().heartline_height))
+            .unwrap_or_default()
+    }
+
+    pub fn limits(&self) -> Option<ForceLimits> {
+        self.limits.or(self.preset.map(|p| p.values().limits))
+    }
+
+    pub fn roughness(&self) -> Option<Roughness> {
+        self.roughness
+            .or(self.preset.and_then(|p| p.values().roughness))
+    }
+
+    pub fn set_roughness(&mut self, roughness: Option<Roughness>) {
+        self.roughness = roughness;
+    }
+
+    pub fn envelope(&self) -> Envelope {
+        self.envelope.clone().unwrap_or_default()
+    }
+
+    pub fn set_envelope(&mut self, envelope: Envelope) {
+        self.envelope = Some(envelope);
+    }
+
+    pub fn simulation(&self) -> SimulationSettings {
+        self.simulation.unwrap_or_default()
+    }
+
+    pub fn set_simulation(&mut self, simulation: SimulationSettings) {
+        self.simulation = Some(simulation);
+    }
+
+    pub fn train_mass(&self) -> f64 {
+        self.train_mass.unwrap_or(DEFAULT_TRAIN_MASS)
+    }
+
+    pub fn set_train_mass(&mut self, train_mass: f64) {
+        self.train_mass = Some(train_mass);
+    }
+
+    pub fn set_parameter(&mut self, parameter: f64) {
+        self.parameter = Some(parameter);
+    }
+
+    pub fn set_resistance(&mut self, resistance: f64) {
+        self.resistance = Some(resistance);
+    }
+
+    pub fn set_heartline_height(&mut self, heartline_height: f64) {
+        self.heartline_height = Some(heartline_height);
+    }
+
+    pub fn set_limits(&mut self, limits: ForceLimits) {
+        self.limits = Some(limits);
+    }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, Default)]
-#[serde(rename_all = "camelCase")]
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct Track {
     pub sections: Vec<TrackSection>,
     pub config: TrackConfig,
     pub anchor: TrackPoint,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub pins: Vec<Pin>,
+    pub terrain: Terrain,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub enclosures: Vec<Enclosure>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub triggers: Vec<Trigger>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "History::is_empty"))]
+    pub history: History,
 }
 
 impl Track {
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &str) -> Result<Self, TrackError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn check(&self) -> Result<(), TrackError> {
+        if self.sections.is_empty() {
+            return Err(TrackError::EmptySections);
+        }
+        let friction = [
+            ("parameter", self.config.parameter()),
+            ("resistance", self.config.resistance()),
+        ];
+        for (field, value) in friction {
+            if !value.is_finite() || value < 0.0 {
+                return Err(TrackError::InvalidConfig { field, value });
+            }
+        }
+        let heartline_height = self.config.heartline_height();
+        if !heartline_height.is_finite() {
+            return Err(TrackError::InvalidConfig {
+                field: "heartlineHeight",
+                value: heartline_height,
+            });
+        }
+        let simulation = self.config.simulation();
+        let steps = [
+            ("simulation.timeStep", simulation.time_step),
+            ("simulation.spatialStep", simulation.spatial_step),
+            (
+                "simulation.curveSubdivisions",
+                simulation.curve_subdivisions as f64,
+            ),
+        ];
+        for (field, value) in steps {
+            if !value.is_finite() || value <= 0.0 {
+                return Err(TrackError::InvalidConfig { field, value });
+            }
+        }
+        if let Integrator::Adaptive { tolerance } = simulation.integrator {
+            if !tolerance.is_finite() || tolerance <= 0.0 {
+                return Err(TrackError::InvalidConfig {
+                    field: "simulation.integrator.tolerance",
+                    value: tolerance,
+                });
+            }
+        }
+        if let Some(roughness) = self.config.roughness() {
+            // The shake scales with speed over `reference_speed` and distance over `wavelength`
+            let scales = [
+                ("roughness.referenceSpeed", roughness.reference_speed),
+                ("roughness.wavelength", roughness.wavelength),
+            ];
+            for (field, value) in scales {
+                if !value.is_finite() || value <= 0.0 {
+                    return Err(TrackError::InvalidConfig { field, value });
+                }
+            }
+            if !roughness.amplitude.is_finite() || roughness.amplitude < 0.0 {
+                return Err(TrackError::InvalidConfig {
+                    field: "roughness.amplitude",
+                    value: roughness.amplitude,
+                });
+            }
+        }
+        for section in &self.sections {
+            if let TrackSection::Transfer { exit_speed, .. } = section {
+                // The train is driven off the transfer, it has to move to get anywhere
+                if !exit_speed.is_finite() || *exit_speed <= 0.0 {
+                    return Err(TrackError::InvalidConfig {
+                        field: "transfer.exitSpeed",
+                        value: *exit_speed,
+                    });
+                }
+            }
+        }
+        if let Some(section) = self
+            .sections
+            .iter()
+            .position(|s| matches!(s, TrackSection::Spike { .. }))
+            .filter(|&i| i + 1 < self.sections.len())
+        {
+            return Err(TrackError::SpikeNotLast { section });
+        }
+        if let Terrain::Heightmap {
+            columns, heights, ..
+        } = &self.terrain
+        {
+            if *columns == 0 {
+                return Err(TrackError::InvalidConfig {
+                    field: "terrain.columns",
+                    value: 0.0,
+                });
+            }
+            // Every row has to be complete
+            if heights.len() % columns != 0 {
+                return Err(TrackError::InvalidConfig {
+                    field: "terrain.heights",
+                    value: heights.len() as f64,
+                });
+            }
+        }
+        Ok(())
+    }
+
     pub fn get_spline(&self) -> (TrackSpline, Vec<f64>) {
+        self.get_spline_with_options(&OutputOptions::default())
+    }
+
+    pub fn try_get_spline(&self) -> Result<(TrackSpline, Vec<f64>), TrackError> {
+        self.try_get_spline_with_options(&OutputOptions::default())
+    }
+
+    pub fn try_get_spline_with_options(
+        &self,
+        options: &OutputOptions,
+    ) -> Result<(TrackSpline, Vec<f64>), TrackError> {
+        self.check()?;
         let splines = self.make_splines();
+        self.check_splines(&splines)?;
+        Ok(self.assemble(splines, options))
+    }
+
+    // What `check` can't tell without simulating, run on the output of `make_splines`
+    pub(crate) fn check_splines(&self, splines: &[TrackSpline]) -> Result<(), TrackError> {
+        if let Some(section) = self.stalled(splines) {
+            return Err(TrackError::Stalled { section });
+        }
+        // Only if the train made it there, it may have rolled back before
+        let reached = splines.len() == self.sections.len();
+        if let (Some(TrackSection::Spike { .. }), true) = (self.sections.last(), reached) {
+            let section = self.sections.len() - 1;
+            let entry = match section.checked_sub(1) {
+                Some(i) => *splines[i].points.last().unwrap(),
+                None => self.initial_point(),
+            };
+            let pitch = euler(&entry).1;
+            if pitch < 89.0 {
+                return Err(TrackError::SpikeEntry { section, pitch });
+            }
+        }
+        Ok(())
+    }
+
+    // First section the train doesn't make it to the end of
+    pub(crate) fn stalled(&self, splines: &[TrackSpline]) -> Option<usize> {
+        let mut entry = self.initial_point();
+        for (i, section) in self.sections.iter().enumerate() {
+            let Some(spline) = splines.get(i) else {
+                return Some(i);
+            };
+            let spike = matches!(section, TrackSection::Spike { .. });
+            // Rolling back ends the track like a spike does
+            if !spike && self.config.simulation().rollback && spline.rolls_back() {
+                return None;
+            }
+            if self.falls_short(section, &entry, spline) {
+                return Some(i);
+            }
+            if spike {
+                return None;
+            }
+            entry = *spline.points.last().unwrap();
+        }
+        None
+    }
+
+    // Whether `spline`, made for `section` from `entry`, stops before the section ends
+    pub(crate) fn falls_short(
+        &self,
+        section: &TrackSection,
+        entry: &TrackPoint,
+        spline: &TrackSpline,
+    ) -> bool {
+        let (Some(last), true) = (spline.points.last(), spline.points.len() > 1) else {
+            return true;
+        };
+        let travelled = (spline.points[0].pos - entry.pos).length() + spline.total_distance();
+        match section {
+            TrackSection::Straight { length, .. }
+            | TrackSection::HoldingBrake { length, .. }
+            | TrackSection::Tilt { length, .. }
+            | TrackSection::Transfer { length, .. }
+            | TrackSection::Launch { length, .. }
+            | TrackSection::Lift { length, .. }
+            | TrackSection::Roll { length, .. } => travelled < length - 0.05,
+            TrackSection::Curved {
+                radius,
+                angle,
+                spiral,
+                ..
+            } => travelled < curve_lengths(*radius, angle.to_radians(), *spiral).0 - 0.05,
+            TrackSection::Force {
+                transitions,
+                domain: TransitionDomain::Time,
+                ..
+            } => {
+                last.time - entry.time
+                    < transitions.length() - 2.0 * self.config.simulation().time_step
+            }
+            TrackSection::Force {
+                transitions,
+                domain: TransitionDomain::Distance,
+                ..
+            } => travelled < transitions.length() - 0.05,
+            // Brakes may stop the train on purpose
+            TrackSection::Brake { .. } | TrackSection::Spike { .. } => false,
+        }
+    }
+
+    pub fn get_spline_with_options(&self, options: &OutputOptions) -> (TrackSpline, Vec<f64>) {
+        self.assemble(self.make_splines(), options)
+    }
+
+    pub(crate) fn assemble(
+        &self,
+        splines: Vec<TrackSpline>,
+        options: &OutputOptions,
+    ) -> (TrackSpline, Vec<f64>) {
         let mut section_start = Vec::new();
         let mut len_accum = 0.0;
         for spline in &splines {
             section_start.push(len_accum);
             len_accum += spline.total_distance();
         }
-        let points = splines
-            .iter()
-            .flat_map(|s| s.points.iter())
-            .cloned()
-            .step_by(4)
-            .collect();
-        let spline = TrackSpline { points };
+        let mut spline = TrackSpline::concat(&splines);
+        if options.distance {
+            spline.fill_distance();
+        }
+        if options.enclosed {
+            spline.fill_enclosed(self);
+        }
+        (
+            spline.decimate(options.decimation, options.keep_extrema),
+            section_start,
+        )
+    }
 
-        (spline, section_start)
+    pub fn analysis(&self, step: f64) -> Vec<AnalysisSample> {
+        let mut samples = self.get_spline().0.analysis(step);
+        if let Some(roughness) = self.config.roughness() {
+            roughness.apply(&mut samples);
+        }
+        samples
     }
 
     pub fn make_splines(&self) -> Vec<TrackSpline> {
+        self.make_splines_timed(None)
+    }
+
+    pub(crate) fn make_splines_timed(
+        &self,
+        mut timings: Option<&mut Vec<SectionTiming>>,
+    ) -> Vec<TrackSpline> {
         let mut splines: Vec<TrackSpline> = Vec::with_capacity(self.sections.len());
-        let mut initial_point = self.anchor;
-        initial_point.rot = DQuat::IDENTITY.into();
-        initial_point.time = 0.0;
+        let initial_point = self.initial_point();
 
         let mut forces = Forces {
             vert: 1.0,
@@ -55,21 +433,55 @@ impl Track {
             roll: 0.0,
         };
 
-        for section in &self.sections {
-            if splines.is_empty() {
-                splines.push(self.make_spline(section, initial_point, forces));
-            } else {
-                let point = splines.last().unwrap().points.last().unwrap();
-                splines.push(self.make_spline(section, *point, forces));
+        for (i, section) in self.sections.iter().enumerate() {
+            let started = timings.is_some().then(profile::millis);
+            let entry = match splines.last() {
+                Some(spline) => *spline.points.last().unwrap(),
+                None => initial_point,
+            };
+            let mut spline = self.make_spline(section, entry, forces);
+            let rolled_back = self.config.simulation().rollback
+                && !matches!(section, TrackSection::Spike { .. })
+                && self.falls_short(section, &entry, &spline);
+            if rolled_back {
+                self.roll_back(&mut spline, &splines);
+            }
+            splines.push(spline);
+            if let (Some(timings), Some(started)) = (timings.as_deref_mut(), started) {
+                timings.push(SectionTiming {
+                    section: i,
+                    millis: profile::millis() - started,
+                    points: splines.last().unwrap().points.len(),
+                });
+            }
+            if rolled_back || matches!(section, TrackSection::Spike { .. }) {
+                break;
             }
             let spline = splines.last().unwrap();
-            forces = spline.forces(spline.total_distance() - 0.005).unwrap();
+            // The train stalled, nothing after this section can be reached
+            let Some(end_forces) = spline.forces(spline.total_distance() - 0.005) else {
+                break;
+            };
+            forces = end_forces;
         }
 
         splines
     }
 
-    fn make_spline(
+    pub(crate) fn make_spline(
+        &self,
+        section: &TrackSection,
+        start: TrackPoint,
+        start_forces: Forces,
+    ) -> TrackSpline {
+        let mut spline = self.integrate_section(section, start, start_forces);
+        for point in &mut spline.points {
+            point.section_time = point.time - start.time;
+        }
+        spline
+    }
+
+    fn integrate_section(
         &self,
         section: &TrackSection,
         start: TrackPoint,
@@ -81,7 +493,7 @@ impl Track {
                 length,
                 fixed_speed,
             } => {
-                let dp = 0.01;
+                let dp = self.config.simulation().spatial_step;
                 let mut pos = start.pos;
                 let mut velocity = start.velocity;
                 let mut time = start.time;
@@ -99,11 +511,14 @@ impl Track {
                             rot: start.rot,
                             velocity,
                             time,
+                            section_time: 0.0,
+                            distance: None,
+                            enclosed: None,
                         };
                         velocity = track_friction(
-                            self.config.parameter,
-                            self.config.resistance,
-                            self.config.heartline_height,
+                            self.config.parameter(),
+                            self.config.resistance(),
+                            self.config.heartline_height(),
                             last_point.unwrap_or(&point),
                             &point,
                             dt,
@@ -118,6 +533,9 @@ impl Track {
                         rot: start.rot,
                         velocity,
                         time,
+                        section_time: 0.0,
+                        distance: None,
+                        enclosed: None,
                     });
 
                     p += dp;
@@ -128,23 +546,38 @@ impl Track {
                 radius,
                 direction,
                 angle,
+                banking,
+                spiral,
             } => {
                 let mut pos = start.pos;
                 let mut velocity = start.velocity;
                 let mut rot = start.rot.0;
 
-                let angle = angle.to_radians();
                 let rad_per_m = 1.0 / radius;
+                let (length, lead) = curve_lengths(*radius, angle.to_radians(), *spiral);
+                // Curvature ramps up linearly over the lead in and back down over the lead out
+                let curvature = |p: f64| {
+                    if lead > 0.0 {
+                        rad_per_m * (p.min(length - p) / lead).clamp(0.0, 1.0)
+                    } else {
+                        rad_per_m
+                    }
+                };
 
-                let dp = (angle * radius) / 200.0;
+                let dp = length / self.config.simulation().curve_subdivisions as f64;
 
                 let axis = DQuat::from_axis_angle(DVec3::Z, direction.to_radians()) * DVec3::NEG_X;
 
                 let mut p = 0.0;
 
                 let mut time = start.time;
+                // `rot` follows the curve and the bank is rolled on top of it
+                let banked = |rot: DQuat, p: f64| match banking {
+                    Some(banking) => rot * DQuat::from_rotation_z(banking.roll_at(p, length)),
+                    None => rot,
+                };
 
-                while p < angle * radius {
+                while p < length {
                     let last_point = spline.points.last();
 
                     pos += rot * (dp * DVec3::Z);
@@ -154,14 +587,17 @@ impl Track {
                         let dt = dp / velocity;
                         let point = TrackPoint {
                             pos,
-                            rot: start.rot,
+                            rot: banked(rot, p + dp).into(),
                             velocity,
-                            time: p / velocity + start.time,
+                            time,
+                            section_time: 0.0,
+                            distance: None,
+                            enclosed: None,
                         };
                         velocity = track_friction(
-                            self.config.parameter,
-                            self.config.resistance,
-                            self.config.heartline_height,
+                            self.config.parameter(),
+                            self.config.resistance(),
+                            self.config.heartline_height(),
                             last_point.unwrap_or(&point),
                             &point,
                             dt,
@@ -170,13 +606,16 @@ impl Track {
                             return spline;
                         }
                     }
-                    rot *= DQuat::from_axis_angle(axis, rad_per_m * dp);
+                    rot *= DQuat::from_axis_angle(axis, curvature(p + dp / 2.0) * dp);
                     time += dp / velocity;
                     spline.points.push(TrackPoint {
                         pos,
-                        rot: rot.into(),
+                        rot: banked(rot, p + dp).into(),
                         velocity,
                         time,
+                        section_time: 0.0,
+                        distance: None,
+                        enclosed: None,
                     });
 
                     p += dp;
@@ -185,25 +624,62 @@ impl Track {
             TrackSection::Force {
                 fixed_speed,
                 transitions,
+                domain,
+                roll_mode,
             } => {
+                let settings = self.config.simulation();
+                let entry_bank = euler(&start).2;
+                let banked;
+                let transitions = FastTransitions::new(match roll_mode {
+                    RollMode::Rate => transitions,
+                    RollMode::Bank => {
+                        banked = transitions.bank_to_roll(entry_bank);
+                        &banked
+                    }
+                });
+                if settings.integrator != Integrator::Euler {
+                    spline.points = self.integrate_forces(
+                        &transitions,
+                        (*domain, *roll_mode),
+                        start,
+                        start_forces,
+                        *fixed_speed,
+                    );
+                    return spline;
+                }
+
+                let dt = settings.time_step;
                 let mut velocity = fixed_speed.unwrap_or(start.velocity);
                 let mut pos = start.pos;
                 let mut rot = start.rot.0;
                 let mut time = 0.0;
-                let transitions = FastTransitions::new(transitions);
-                spline.points = Vec::with_capacity((transitions.length / DT) as usize);
+                // How far through the transitions, in seconds or meters
+                let mut along = 0.0;
+                // Over distance the number of steps depends on the speed
+                if *domain == TransitionDomain::Time {
+                    spline.points = Vec::with_capacity((transitions.length / dt) as usize);
+                }
 
-                while time < transitions.length {
-                    let delta_distance = velocity * DT;
+                while along < transitions.length {
+                    let delta_distance = velocity * dt;
+                    let next_along = along
+                        + match domain {
+                            TransitionDomain::Time => dt,
+                            TransitionDomain::Distance => delta_distance,
+                        };
 
-                    if let Some(forces) = transitions.evaluate(time) {
-                        let forces = forces + start_forces;
+                    if let Some(forces) = transitions.evaluate(along) {
+                        let mut forces = forces + start_forces;
+                        if *roll_mode == RollMode::Bank {
+                            let bank = entry_bank + transitions.roll_at(next_along);
+                            forces.roll = roll_rate_to(rot, bank, dt);
+                        }
                         let mut next_rot = rot;
 
                         if forces.roll.abs() > 0.01 {
                             next_rot = DQuat::from_axis_angle(
                                 next_rot * DVec3::Z,
-                                forces.roll.to_radians() * DT,
+                                forces.roll.to_radians() * dt,
                             ) * next_rot;
                         }
 
@@ -216,10 +692,10 @@ impl Track {
 
                         next_rot = (DQuat::from_axis_angle(
                             next_rot * DVec3::NEG_X,
-                            (normal_force / velocity) * DT,
+                            (normal_force / velocity) * dt,
                         ) * DQuat::from_axis_angle(
                             next_rot * DVec3::Y,
-                            -(lateral_force / velocity) * DT,
+                            -(lateral_force / velocity) * dt,
                         )) * next_rot;
 
                         pos += (next_rot * DVec3::Z) * delta_distance;
@@ -233,16 +709,19 @@ impl Track {
                         pos,
                         rot: rot.into(),
                         velocity,
-                        time: time + start.time,
+                        time: start.time + time + dt,
+                        section_time: 0.0,
+                        distance: None,
+                        enclosed: None,
                     };
                     if fixed_speed.is_none() {
                         velocity = track_friction(
-                            self.config.parameter,
-                            self.config.resistance,
-                            self.config.heartline_height,
+                            self.config.parameter(),
+                            self.config.resistance(),
+                            self.config.heartline_height(),
                             spline.points.last().unwrap_or(&track_point),
                             &track_point,
-                            DT,
+                            dt,
                         );
                     }
 
@@ -254,17 +733,419 @@ impl Track {
                         pos,
                         rot: rot.into(),
                         velocity,
-                        time: time + start.time,
+                        time: start.time + time + dt,
+                        section_time: 0.0,
+                        distance: None,
+                        enclosed: None,
+                    });
+                    time += dt;
+                    along = next_along;
+                }
+            }
+            TrackSection::HoldingBrake { dwell, length } => {
+                // The guide points straight down, keeping the heading the train came in on.
+                // The train sits on it where the last section ended for the whole dwell, the
+                // pairs with no length in between carry no forces
+                let rot = DQuat::from_rotation_y(heading(start.rot.0).to_radians())
+                    * DQuat::from_rotation_x(FRAC_PI_2);
+                let held = TrackPoint {
+                    rot: rot.into(),
+                    velocity: 0.0,
+                    ..start
+                };
+                let released = TrackPoint {
+                    time: start.time + dwell,
+                    ..held
+                };
+                spline.points.extend([held, released]);
+                self.release(&mut spline, released, *length);
+            }
+            TrackSection::Tilt {
+                length,
+                angle,
+                duration,
+            } => {
+                let rot = DQuat::from_axis_angle(start.rot.0 * DVec3::X, angle.to_radians())
+                    * start.rot.0;
+                let held = TrackPoint {
+                    rot: rot.into(),
+                    velocity: 0.0,
+                    time: start.time + duration,
+                    ..start
+                };
+                self.release(&mut spline, held, *length);
+            }
+            TrackSection::Transfer {
+                length,
+                offset,
+                rotation,
+                duration,
+                exit_speed,
+            } => {
+                let (pivot, moved) = transfer_pose(&start, *length, *rotation);
+                let dp = self.config.simulation().spatial_step;
+                let dir = moved * (start.rot.0 * DVec3::Z);
+                let mut pos = pivot + moved * (start.pos - pivot) + *offset;
+                let mut time = start.time + duration;
+                let mut p = 0.0;
+
+                while p < *length {
+                    pos += dir * dp;
+                    time += dp / exit_speed;
+                    spline.points.push(TrackPoint {
+                        pos,
+                        rot: (moved * start.rot.0).into(),
+                        velocity: *exit_speed,
+                        time,
+                        section_time: 0.0,
+                        distance: None,
+                        enclosed: None,
                     });
-                    time += DT;
+
+                    p += dp;
+                }
+            }
+            TrackSection::Spike { length, twist } => {
+                let dp = self.config.simulation().spatial_step;
+                let dir = start.rot.0 * DVec3::Z;
+                let roll_per_m = twist.to_radians() / length;
+                let mut last_point = start;
+                let mut p = 0.0;
+
+                while p < *length {
+                    let rot = DQuat::from_axis_angle(dir, roll_per_m * (p + dp)) * start.rot.0;
+                    let point = TrackPoint {
+                        pos: last_point.pos + dir * dp,
+                        rot: rot.into(),
+                        ..last_point
+                    };
+                    let velocity = track_friction(
+                        self.config.parameter(),
+                        self.config.resistance(),
+                        self.config.heartline_height(),
+                        &last_point,
+                        &point,
+                        step_dt(dp, last_point.velocity),
+                    );
+                    if velocity <= 0.0 {
+                        break;
+                    }
+                    last_point = TrackPoint {
+                        velocity,
+                        time: last_point.time + 2.0 * dp / (last_point.velocity + velocity),
+                        ..point
+                    };
+                    spline.points.push(last_point);
+
+                    p += dp;
+                }
+                self.roll_back(&mut spline, &[]);
+            }
+            TrackSection::Roll {
+                fixed_speed,
+                length,
+                angle,
+            } => {
+                let dp = self.config.simulation().spatial_step;
+                let dir = start.rot.0 * DVec3::Z;
+                let roll_per_m = angle.to_radians() / length;
+                let mut last_point = start;
+                let mut p = 0.0;
+
+                while p < *length {
+                    let rot = DQuat::from_axis_angle(dir, roll_per_m * (p + dp)) * start.rot.0;
+                    let point = TrackPoint {
+                        pos: last_point.pos + dir * dp,
+                        rot: rot.into(),
+                        ..last_point
+                    };
+                    let velocity = match fixed_speed {
+                        Some(fixed_speed) => *fixed_speed,
+                        None => {
+                            self.friction(&last_point, &point, step_dt(dp, last_point.velocity))
+                        }
+                    };
+                    if velocity <= 0.0 {
+                        return spline;
+                    }
+                    last_point = TrackPoint {
+                        velocity,
+                        time: last_point.time + 2.0 * dp / (last_point.velocity + velocity),
+                        ..point
+                    };
+                    spline.points.push(last_point);
+
+                    p += dp;
+                }
+            }
+            TrackSection::Launch {
+                length,
+                target_speed,
+                profile,
+            } => {
+                let dp = self.config.simulation().spatial_step;
+                let dir = start.rot.0 * DVec3::Z;
+                let mut last_point = start;
+                let mut p = 0.0;
+
+                while p < *length {
+                    let point = TrackPoint {
+                        pos: last_point.pos + dir * dp,
+                        ..last_point
+                    };
+                    let coast =
+                        self.friction(&last_point, &point, step_dt(dp, last_point.velocity));
+                    let velocity = if coast < *target_speed {
+                        (coast * coast + 2.0 * profile.thrust(p) * dp)
+                            .max(0.0)
+                            .sqrt()
+                            .min(*target_speed)
+                    } else {
+                        coast
+                    };
+                    if velocity <= 0.0 {
+                        return spline;
+                    }
+                    last_point = TrackPoint {
+                        velocity,
+                        time: last_point.time + 2.0 * dp / (last_point.velocity + velocity),
+                        ..point
+                    };
+                    spline.points.push(last_point);
+
+                    p += dp;
+                }
+            }
+            TrackSection::Lift {
+                length,
+                pitch,
+                chain_speed,
+                drive,
+            } => {
+                let dp = self.config.simulation().spatial_step;
+                let rot = DQuat::from_rotation_y(heading(start.rot.0).to_radians())
+                    * DQuat::from_rotation_x(-pitch.to_radians());
+                let dir = rot * DVec3::Z;
+                let mut last_point = TrackPoint {
+                    rot: rot.into(),
+                    ..start
+                };
+                let mut p = 0.0;
+
+                while p < *length {
+                    let point = TrackPoint {
+                        pos: last_point.pos + dir * dp,
+                        ..last_point
+                    };
+                    let coast =
+                        self.friction(&last_point, &point, step_dt(dp, last_point.velocity));
+                    let velocity =
+                        drive.speed(coast, start.velocity, *chain_speed, p + dp, *length);
+                    if velocity <= 0.0 {
+                        return spline;
+                    }
+                    last_point = TrackPoint {
+                        velocity,
+                        time: last_point.time + 2.0 * dp / (last_point.velocity + velocity),
+                        ..point
+                    };
+                    spline.points.push(last_point);
+
+                    p += dp;
+                }
+            }
+            TrackSection::Brake {
+                length,
+                deceleration,
+                exit_speed,
+                ..
+            } => {
+                let dp = self.config.simulation().spatial_step;
+                let dir = start.rot.0 * DVec3::Z;
+                let mut last_point = start;
+                let mut p = 0.0;
+
+                while p < *length {
+                    let point = TrackPoint {
+                        pos: last_point.pos + dir * dp,
+                        ..last_point
+                    };
+                    let coast =
+                        self.friction(&last_point, &point, step_dt(dp, last_point.velocity));
+                    let velocity = if coast > *exit_speed {
+                        (coast * coast - 2.0 * deceleration * dp)
+                            .max(exit_speed * exit_speed)
+                            .sqrt()
+                    } else {
+                        coast
+                    };
+                    if velocity <= 0.0 {
+                        return spline;
+                    }
+                    last_point = TrackPoint {
+                        velocity,
+                        time: last_point.time + 2.0 * dp / (last_point.velocity + velocity),
+                        ..point
+                    };
+                    spline.points.push(last_point);
+
+                    p += dp;
                 }
             }
         }
         spline
     }
+
+    pub(crate) fn friction(&self, last_point: &TrackPoint, point: &TrackPoint, dt: f64) -> f64 {
+        track_friction(
+            self.config.parameter(),
+            self.config.resistance(),
+            self.config.heartline_height(),
+            last_point,
+            point,
+            dt,
+        )
+    }
+
+    // Where a section added now would start, and the forces it would start from
+    pub(crate) fn end_state(&self) -> (TrackPoint, Forces) {
+        self.state_after(&self.make_splines())
+    }
+
+    // Where the section after `splines` starts
+    pub(crate) fn state_after(&self, splines: &[TrackSpline]) -> (TrackPoint, Forces) {
+        splines
+            .last()
+            .and_then(|spline| {
+                let forces = spline.forces(spline.total_distance() - 0.005)?;
+                Some((*spline.points.last()?, forces))
+            })
+            .unwrap_or((self.initial_point(), Forces::new(1.0, 0.0, 0.0)))
+    }
+
+    pub(crate) fn initial_point(&self) -> TrackPoint {
+        // Only the heading of the anchor is used, sections always start level
+        let mut initial_point = self.anchor;
+        initial_point.rot = DQuat::from_rotation_y(self.heading().to_radians()).into();
+        initial_point.time = 0.0;
+        initial_point
+    }
+
+    // Runs the train backwards from the last point of the spline over the points
+    // already generated, then on back over `earlier` splines, with negative velocities
+    // marking backwards travel.
+    fn roll_back(&self, spline: &mut TrackSpline, earlier: &[TrackSpline]) {
+        let Some(apex) = spline.points.last() else {
+            return;
+        };
+        let mut last_point = TrackPoint {
+            velocity: 0.0,
+            ..*apex
+        };
+        let path: Vec<TrackPoint> = spline
+            .points
+            .iter()
+            .rev()
+            .skip(1)
+            .chain(earlier.iter().rev().flat_map(|s| s.points.iter().rev()))
+            .copied()
+            .collect();
+
+        for next in path {
+            let point = TrackPoint {
+                velocity: last_point.velocity,
+                time: last_point.time,
+                ..next
+            };
+            let dp = (point.pos - last_point.pos).length();
+            let velocity = track_friction(
+                self.config.parameter(),
+                self.config.resistance(),
+                self.config.heartline_height(),
+                &last_point,
+                &point,
+                step_dt(dp, last_point.velocity),
+            );
+            if velocity <= 0.0 {
+                return;
+            }
+            last_point = TrackPoint {
+                velocity,
+                time: last_point.time + 2.0 * dp / (last_point.velocity + velocity),
+                ..point
+            };
+            spline.points.push(TrackPoint {
+                velocity: -velocity,
+                ..last_point
+            });
+        }
+    }
+
+    fn release(&self, spline: &mut TrackSpline, held: TrackPoint, length: f64) {
+        let dp = self.config.simulation().spatial_step;
+        let dir = held.rot.0 * DVec3::Z;
+        let mut last_point = held;
+        let mut p = 0.0;
+
+        while p < length {
+            let point = TrackPoint {
+                pos: last_point.pos + dir * dp,
+                ..last_point
+            };
+            let velocity = track_friction(
+                self.config.parameter(),
+                self.config.resistance(),
+                self.config.heartline_height(),
+                &last_point,
+                &point,
+                step_dt(dp, last_point.velocity),
+            );
+            if velocity <= 0.0 {
+                return;
+            }
+            last_point = TrackPoint {
+                velocity,
+                time: last_point.time + 2.0 * dp / (last_point.velocity + velocity),
+                ..point
+            };
+            spline.points.push(last_point);
+
+            p += dp;
+        }
+    }
+}
+
+pub(crate) fn transfer_pose(entry: &TrackPoint, length: f64, rotation: f64) -> (DVec3, DQuat) {
+    let pivot = entry.pos + entry.rot.0 * DVec3::Z * (length / 2.0);
+    (pivot, DQuat::from_rotation_y(rotation.to_radians()))
+}
+
+pub(crate) fn step_dt(dp: f64, velocity: f64) -> f64 {
+    if velocity > 0.0 {
+        dp / velocity
+    } else {
+        0.0
+    }
 }
 
-fn track_friction(
+// Degrees per second of roll that brings `rot` round to `bank` over `dt`
+pub(crate) fn roll_rate_to(rot: DQuat, bank: f64, dt: f64) -> f64 {
+    let point = TrackPoint {
+        rot: rot.into(),
+        ..Default::default()
+    };
+    deg_diff(euler(&point).2, bank) / dt
+}
+
+// Meters along a curve turning `angle` radians and how much of each end is spiral. The
+// spirals are cut down to leave no arc between them rather than letting the curve turn less
+// than asked
+fn curve_lengths(radius: f64, angle: f64, spiral: f64) -> (f64, f64) {
+    let lead = spiral.clamp(0.0, angle * radius);
+    (angle * radius + lead, lead)
+}
+
+pub(crate) fn track_friction(
     parameter: f64,
     resistance: f64,
     heartline_height: f64,
@@ -297,24 +1178,151 @@ fn track_friction(
         .sqrt()
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(tag = "type", rename_all_fields = "camelCase")]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(tag = "type", rename_all_fields = "camelCase")
+)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum TrackSection {
-    #[serde(rename = "straight")]
+    #[cfg_attr(feature = "serde", serde(rename = "straight"))]
     Straight {
         length: f64,
+        #[cfg_attr(feature = "serde", serde(default))]
         fixed_speed: Option<f64>,
     },
-    #[serde(rename = "force")]
+    #[cfg_attr(feature = "serde", serde(rename = "force"))]
     Force {
+        #[cfg_attr(feature = "serde", serde(default))]
         fixed_speed: Option<f64>,
         transitions: Transitions,
+        #[cfg_attr(feature = "serde", serde(default))]
+        domain: TransitionDomain,
+        #[cfg_attr(feature = "serde", serde(default))]
+        roll_mode: RollMode,
     },
-    #[serde(rename = "curved")]
+    #[cfg_attr(feature = "serde", serde(rename = "curved"))]
     Curved {
+        #[cfg_attr(feature = "serde", serde(default))]
         fixed_speed: Option<f64>,
         radius: f64,
         direction: f64,
         angle: f64,
+        // Keeps the entry roll throughout when None
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Option::is_none")
+        )]
+        banking: Option<Banking>,
+        // Meters of clothoid leading in to `radius` and again leading back out, turning
+        // half as far as an arc of the same length would. 0 for a plain arc
+        #[cfg_attr(feature = "serde", serde(default))]
+        spiral: f64,
+    },
+    // Holds the train for `dwell` seconds then drops it `length` meters down a vertical
+    // guide
+    #[cfg_attr(feature = "serde", serde(rename = "holdingBrake"))]
+    HoldingBrake {
+        #[cfg_attr(feature = "serde", serde(default))]
+        dwell: f64,
+        length: f64,
+    },
+    #[cfg_attr(feature = "serde", serde(rename = "tilt"))]
+    Tilt {
+        length: f64,
+        angle: f64,
+        duration: f64,
+    },
+    #[cfg_attr(feature = "serde", serde(rename = "transfer"))]
+    Transfer {
+        length: f64,
+        #[cfg_attr(feature = "serde", serde(default))]
+        #[cfg_attr(feature = "ts", ts(type = "[number, number, number]"))]
+        offset: DVec3,
+        #[cfg_attr(feature = "serde", serde(default))]
+        rotation: f64,
+        duration: f64,
+        exit_speed: f64,
+    },
+    // Thrust is added on top of friction until the train reaches `target_speed`
+    #[cfg_attr(feature = "serde", serde(rename = "launch"))]
+    Launch {
+        length: f64,
+        target_speed: f64,
+        #[cfg_attr(feature = "serde", serde(default))]
+        profile: LaunchProfile,
+    },
+    // Straight lift at `pitch` degrees above horizontal, keeping the entry heading.
+    // `chain_speed` is the top speed of whichever drive pulls the train up
+    #[cfg_attr(feature = "serde", serde(rename = "lift"))]
+    Lift {
+        length: f64,
+        pitch: f64,
+        chain_speed: f64,
+        #[cfg_attr(feature = "serde", serde(default))]
+        drive: LiftDrive,
+    },
+    #[cfg_attr(feature = "serde", serde(rename = "brake"))]
+    Brake {
+        length: f64,
+        // m/s^2
+        deceleration: f64,
+        #[cfg_attr(feature = "serde", serde(default))]
+        exit_speed: f64,
+        #[cfg_attr(feature = "serde", serde(default))]
+        brake_type: BrakeType,
+        // Overrides the default curve for `brake_type`
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Option::is_none")
+        )]
+        curve: Option<BrakeCurve>,
+    },
+    // Unfinished track the train runs up and rolls back down, twisting `twist` degrees along
+    // the way. It carries on straight from the section before, which has to leave the train
+    // pointing straight up, and has to be the last section
+    #[cfg_attr(feature = "serde", serde(rename = "spike"))]
+    Spike {
+        length: f64,
+        #[cfg_attr(feature = "serde", serde(default))]
+        twist: f64,
+    },
+    // Straight that rolls `angle` degrees from the entry roll at an even rate per meter
+    // rather than per second, so it ends at the same bank whatever the speed. Rolls about
+    // the heartline like everything else
+    #[cfg_attr(feature = "serde", serde(rename = "roll"))]
+    Roll {
+        #[cfg_attr(feature = "serde", serde(default))]
+        fixed_speed: Option<f64>,
+        length: f64,
+        angle: f64,
     },
 }
+
+impl TrackSection {
+    // Brakes close off a block, a train can be held there while the next one clears
+    pub fn ends_block(&self) -> bool {
+        matches!(
+            self,
+            TrackSection::Brake { .. } | TrackSection::HoldingBrake { .. }
+        )
+    }
+
+    // Sections where the train is driven at a set speed rather than coasting
+    pub fn is_powered(&self) -> bool {
+        match self {
+            TrackSection::Straight { fixed_speed, .. }
+            | TrackSection::Force { fixed_speed, .. }
+            | TrackSection::Curved { fixed_speed, .. }
+            | TrackSection::Roll { fixed_speed, .. } => fixed_speed.is_some(),
+            TrackSection::Transfer { .. }
+            | TrackSection::Launch { .. }
+            | TrackSection::Lift { .. } => true,
+            TrackSection::HoldingBrake { .. }
+            | TrackSection::Brake { .. }
+            | TrackSection::Tilt { .. }
+            | TrackSection::Spike { .. } => false,
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use glam::{DMat3, DQuat, DVec3};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{join::lerp_points, TrackPoint, TrackSpline};
+
+// Where the wheel bogies sit on each car, measured from the car's pivot on the track
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct BogieOffsets {
+    pub lead: f64,
+    pub trail: f64,
+}
+
+impl TrackSpline {
+    pub(crate) fn cumulative_distances(&self) -> Vec<f64> {
+        let mut distances = Vec::with_capacity(self.points.len());
+        let mut distance = 0.0;
+        let mut last_pos = self.points.first().map(|p| p.pos).unwrap_or_default();
+        for point in &self.points {
+            distance += (point.pos - last_pos).length();
+            last_pos = point.pos;
+            distances.push(distance);
+        }
+        distances
+    }
+
+    // Front car at `distance`, each following car `car_spacings[i]` meters behind the
+    // one before it, so there is one more car than spacings
+    pub fn train_pose(&self, distance: f64, car_spacings: &[f64]) -> Vec<(DVec3, DQuat)> {
+        train_pose(
+            &self.points,
+            &self.cumulative_distances(),
+            distance,
+            car_spacings,
+        )
+    }
+
+    // Lead and trail bogie for every car placed by train_pose. Bogies ride on the track,
+    // so they take the track's orientation where they sit rather than the car body's
+    pub fn bogie_pose(
+        &self,
+        distance: f64,
+        car_spacings: &[f64],
+        offsets: BogieOffsets,
+    ) -> Vec<[(DVec3, DQuat); 2]> {
+        let distances = self.cumulative_distances();
+        let cars = car_distances(&self.points, &distances, distance, car_spacings);
+        bogie_pose(&self.points, &distances, &cars, offsets, 1.0)
+    }
+}
+
+pub(crate) fn point_at_distance(
+    points: &[TrackPoint],
+    distances: &[f64],
+    distance: f64,
+) -> Option<TrackPoint> {
+    if points.len() < 2 {
+        return points.first().copied();
+    }
+    let i = distances
+        .partition_point(|&d| d < distance)
+        .clamp(1, points.len() - 1);
+    let (da, db) = (distances[i - 1], distances[i]);
+    let t = if db > da {
+        ((distance - da) / (db - da)).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    Some(lerp_points(&points[i - 1], &points[i], t))
+}
+
+// Cars are rigid, so the next car sits a straight-line `spacing` from the last one rather
+// than an arc length, which would pull cars into each other through tight curves.
+// A negative spacing places the car ahead instead, for trains running backwards.
+fn next_car_distance(points: &[TrackPoint], distances: &[f64], from: f64, spacing: f64) -> f64 {
+    let Some(origin) = point_at_distance(points, distances, from) else {
+        return from;
+    };
+    let end = if spacing < 0.0 {
+        distances.last().copied().unwrap_or_default()
+    } else {
+        0.0
+    };
+    let chord = |d: f64| {
+        point_at_distance(points, distances, d)
+            .map(|p| (p.pos - origin.pos).length())
+            .unwrap_or_default()
+    };
+
+    let clamp = |d: f64| d.clamp(from.min(end), from.max(end));
+    let length = spacing.abs();
+
+    // The chord is never longer than the arc, so the car is at least `spacing` of track away
+    let mut near = clamp(from - spacing);
+    if chord(near) >= length || near == end {
+        return near;
+    }
+    let mut far = near;
+    while chord(far) < length {
+        near = far;
+        far = clamp(far - spacing * 0.25);
+        if far == end && chord(far) < length {
+            return end;
+        }
+    }
+
+    for _ in 0..32 {
+        let mid = 0.5 * (near + far);
+        if chord(mid) < length {
+            near = mid;
+        } else {
+            far = mid;
+        }
+    }
+    0.5 * (near + far)
+}
+
+pub(crate) fn car_distances(
+    points: &[TrackPoint],
+    distances: &[f64],
+    distance: f64,
+    car_spacings: &[f64],
+) -> Vec<f64> {
+    if points.is_empty() {
+        return Vec::new();
+    }
+    let mut cars = vec![distance];
+    for &spacing in car_spacings {
+        let last = *cars.last().unwrap();
+        cars.push(next_car_distance(points, distances, last, spacing));
+    }
+    cars
+}
+
+// `direction` is the sign the car spacings were given in, so the lead bogie is always
+// the one facing the direction of travel
+pub(crate) fn bogie_pose(
+    points: &[TrackPoint],
+    distances: &[f64],
+    car_distances: &[f64],
+    offsets: BogieOffsets,
+    direction: f64,
+) -> Vec<[(DVec3, DQuat); 2]> {
+    let bogie = |from: f64, spacing: f64| {
+        let d = next_car_distance(points, distances, from, spacing * direction);
+        point_at_distance(points, distances, d).map(|p| (p.pos, p.rot.0))
+    };
+    car_distances
+        .iter()
+        .map_while(|&d| Some([bogie(d, -offsets.lead)?, bogie(d, offsets.trail)?]))
+        .collect()
+}
+
+pub(crate) fn train_pose(
+    points: &[TrackPoint],
+    distances: &[f64],
+    distance: f64,
+    car_spacings: &[f64],
+) -> Vec<(DVec3, DQuat)> {
+    let cars = car_distances(points, distances, distance, car_spacings);
+    let Some(front) = point_at_distance(points, distances, distance) else {
+        return Vec::new();
+    };
+    let mut pose = vec![(front.pos, front.rot.0)];
+
+    for (&spacing, &car_distance) in car_spacings.iter().zip(&cars[1..]) {
+        let Some(car) = point_at_distance(points, distances, car_distance) else {
+            break;
+        };
+        let ahead = pose.last().unwrap().0;
+        // Align the car with the coupling to the one before it, keeping the track's up
+        let coupling = (ahead - car.pos) * spacing.signum();
+        let rot = match coupling.try_normalize() {
+            Some(forward) => {
+                let up = car.rot.0 * DVec3::Y;
+                let up = (up - forward * up.dot(forward)).normalize_or(up);
+                DQuat::from_mat3(&DMat3::from_cols(up.cross(forward), up, forward))
+            }
+            None => car.rot.0,
+        };
+        pose.push((car.pos, rot));
+    }
+
+    pose
+}
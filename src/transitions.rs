@@ -1,9 +1,12 @@
-use std::ops::{Add, Sub};
+use std::ops::{Add, Mul, Neg, Sub};
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
-#[serde(rename_all = "kebab-case")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum TransitionCurve {
     Linear,
     Quadratic,
@@ -25,60 +28,130 @@ impl TransitionCurve {
                 if t < 0.5 {
                     4.0 * t * t * t
                 } else {
-                    1.0 - (-2.0 * t + 2.0).powf(3.0) / 2.0
+                    1.0 - libm::pow(-2.0 * t + 2.0, 3.0) / 2.0
                 }
             }
             TransitionCurve::Quadratic => {
                 if t < 0.5 {
                     2.0 * t * t
                 } else {
-                    1.0 - (-2.0 * t + 2.0).powf(2.0) / 2.0
+                    1.0 - libm::pow(-2.0 * t + 2.0, 2.0) / 2.0
                 }
             }
             TransitionCurve::Plateau => {
-                1.0 - (-15.0 * ((1.0 - (2.0 * t - 1.0).abs()).powi(3))).exp()
+                1.0 - libm::exp(-15.0 * libm::pow(1.0 - (2.0 * t - 1.0).abs(), 3.0))
             }
-            TransitionCurve::Sinusoidal => 0.5 * (1. - (std::f64::consts::PI * t).cos()),
+            TransitionCurve::Sinusoidal => 0.5 * (1. - libm::cos(std::f64::consts::PI * t)),
             TransitionCurve::QuarticBump => t * t * (16.0 + t * (-32.0 + t * 16.0)),
         }
     }
 }
 
-fn timewarp(t: f64, center: f64, tension: f64) -> f64 {
+pub fn timewarp(t: f64, center: f64, tension: f64) -> f64 {
     timewarp_tension(timewarp_center(t, center), tension)
 }
 
-fn timewarp_center(t: f64, center: f64) -> f64 {
+pub fn timewarp_center(t: f64, center: f64) -> f64 {
     if center.abs() < 0.01 {
         t
     } else if center > 0.0 {
-        t.powf(2.0_f64.powf(center / 2.0))
+        libm::pow(t, libm::pow(2.0, center / 2.0))
     } else {
-        1.0 - (1.0 - t).powf(2.0_f64.powf(-center / 2.0))
+        1.0 - libm::pow(1.0 - t, libm::pow(2.0, -center / 2.0))
     }
 }
 
-fn timewarp_tension(t: f64, tension: f64) -> f64 {
+pub fn timewarp_tension(t: f64, tension: f64) -> f64 {
     if tension.abs() < 0.01 {
         t
     } else if tension > 0.0 {
-        0.5 * ((2.0 * tension * (t - 0.5)).sinh() / tension.sinh() + 1.0)
+        0.5 * (libm::sinh(2.0 * tension * (t - 0.5)) / libm::sinh(tension) + 1.0)
     } else {
-        0.5 * ((2.0 * tension.sinh() * (t - 0.5)).asinh() / tension + 1.0)
+        0.5 * (libm::asinh(2.0 * libm::sinh(tension) * (t - 0.5)) / tension + 1.0)
     }
 }
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+// Maps warped time back to the linear time that produces it
+pub fn timewarp_inverse(t: f64, center: f64, tension: f64) -> f64 {
+    timewarp_center_inverse(timewarp_tension_inverse(t, tension), center)
+}
+
+pub fn timewarp_center_inverse(t: f64, center: f64) -> f64 {
+    if center.abs() < 0.01 {
+        t
+    } else if center > 0.0 {
+        libm::pow(t, 1.0 / libm::pow(2.0, center / 2.0))
+    } else {
+        1.0 - libm::pow(1.0 - t, 1.0 / libm::pow(2.0, -center / 2.0))
+    }
+}
+
+pub fn timewarp_tension_inverse(t: f64, tension: f64) -> f64 {
+    if tension.abs() < 0.01 {
+        t
+    } else if tension > 0.0 {
+        0.5 + libm::asinh((2.0 * t - 1.0) * libm::sinh(tension)) / (2.0 * tension)
+    } else {
+        0.5 + libm::sinh((2.0 * t - 1.0) * tension) / (2.0 * libm::sinh(tension))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct Transition {
     pub curve: TransitionCurve,
     pub value: f64,
     pub length: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub center: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub tension: f64,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum Channel {
+    Vert,
+    Lat,
+    Roll,
+}
+
+// What the lengths of a force section's transitions are measured in. Over distance the
+// section keeps its length of track when the speed into it changes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum TransitionDomain {
+    // Seconds
+    #[default]
+    Time,
+    // Meters
+    Distance,
+}
+
+// What a force section's roll channel gives
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum RollMode {
+    // Degrees per second
+    #[default]
+    Rate,
+    // Degrees of bank relative to gravity each transition takes the train to, or peaks at
+    // for curves that come back. The rate is solved every step so the bank lands on it
+    // whatever the speed. Loses track of the bank pointing straight up or down
+    Bank,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct Transitions {
     pub vert: Vec<Transition>,
     pub lat: Vec<Transition>,
@@ -95,6 +168,51 @@ impl Transitions {
             .min(self.roll.iter().map(|t| t.length).sum::<f64>())
     }
 
+    // The roll channel read as banks, see RollMode::Bank, turned into changes from the
+    // `entry` bank
+    pub(crate) fn bank_to_roll(&self, entry: f64) -> Transitions {
+        let mut reached = entry;
+        let roll = self
+            .roll
+            .iter()
+            .map(|transition| {
+                let value = transition.value - reached;
+                reached += value * transition.curve.eval(1.0);
+                Transition {
+                    value,
+                    ..*transition
+                }
+            })
+            .collect();
+        Transitions {
+            vert: self.vert.clone(),
+            lat: self.lat.clone(),
+            roll,
+        }
+    }
+
+    pub fn channel(&self, channel: Channel) -> &[Transition] {
+        match channel {
+            Channel::Vert => &self.vert,
+            Channel::Lat => &self.lat,
+            Channel::Roll => &self.roll,
+        }
+    }
+
+    // Evenly spaced (time, value) samples over the whole length of one channel
+    pub fn preview(&self, channel: Channel, n_samples: usize) -> Vec<(f64, f64)> {
+        let transitions = self.channel(channel);
+        let length: f64 = transitions.iter().map(|t| t.length).sum();
+        let last = n_samples.saturating_sub(1).max(1) as f64;
+
+        (0..n_samples)
+            .filter_map(|i| {
+                let time = length * i as f64 / last;
+                Some((time, Self::evaluate_single(transitions, time)?))
+            })
+            .collect()
+    }
+
     fn evaluate_single(transitions: &[Transition], time: f64) -> Option<f64> {
         if time < 0.0 {
             return None;
@@ -220,15 +338,22 @@ impl FastTransitions {
         })
     }
 
+    // The roll channel at `time`, held at where it ends past the end
+    pub(crate) fn roll_at(&self, time: f64) -> f64 {
+        let time = time.clamp(0.0, self.length * (1.0 - f64::EPSILON));
+        self.evaluate_single(&self.roll, time).unwrap_or_default()
+    }
+
     fn evaluate_single(&self, transitions: &[AbsoluteTransition], time: f64) -> Option<f64> {
         if time < 0.0 || time >= self.length {
             return None;
         }
 
-        let transition = transitions
-            .iter()
-            .find(|v| v.start <= time && v.start + v.length > time)
-            .unwrap(); // TODO binary search
+        // Last transition starting at or before `time`, zero length ones before it are skipped
+        let index = transitions
+            .partition_point(|v| v.start <= time)
+            .checked_sub(1)?;
+        let transition = &transitions[index];
 
         let time_relative = time - transition.start;
         let value = transition.start_value
@@ -243,29 +368,64 @@ impl FastTransitions {
     }
 }
 
-// fn find_range_index(arr: &[AbsoluteTransition], target: f64) -> usize {
-//     let mut low = 0;
-//     let mut high = arr.len();
-
-//     while low < high {
-//         let mid = (low + high) / 2;
-//         if arr[mid].start <= target {
-//             low = mid + 1;
-//         } else {
-//             high = mid;
-//         }
-//     }
-
-//     low.clamp(0, arr.len() - 1)
-// }
-
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct Forces {
     pub vert: f64,
     pub lat: f64,
     pub roll: f64,
 }
 
+impl Forces {
+    pub const ZERO: Self = Self::splat(0.0);
+
+    pub const fn new(vert: f64, lat: f64, roll: f64) -> Self {
+        Self { vert, lat, roll }
+    }
+
+    pub const fn splat(v: f64) -> Self {
+        Self::new(v, v, v)
+    }
+
+    pub fn lerp(self, other: Self, t: f64) -> Self {
+        self + (other - self) * t
+    }
+
+    pub fn min(self, other: Self) -> Self {
+        Self::new(
+            self.vert.min(other.vert),
+            self.lat.min(other.lat),
+            self.roll.min(other.roll),
+        )
+    }
+
+    pub fn max(self, other: Self) -> Self {
+        Self::new(
+            self.vert.max(other.vert),
+            self.lat.max(other.lat),
+            self.roll.max(other.roll),
+        )
+    }
+
+    pub fn clamp(self, min: Self, max: Self) -> Self {
+        self.max(min).min(max)
+    }
+
+    pub fn abs(self) -> Self {
+        Self::new(self.vert.abs(), self.lat.abs(), self.roll.abs())
+    }
+
+    // Magnitude of the combined vertical and lateral g, roll is a rate and is left out
+    pub fn g_magnitude(&self) -> f64 {
+        libm::hypot(self.vert, self.lat)
+    }
+
+    pub fn magnitude(&self) -> f64 {
+        (self.vert * self.vert + self.lat * self.lat + self.roll * self.roll).sqrt()
+    }
+}
+
 impl Add<Forces> for Forces {
     type Output = Self;
     fn add(self, other: Self) -> Self {
@@ -287,3 +447,21 @@ impl Sub<Forces> for Forces {
         }
     }
 }
+
+impl Mul<f64> for Forces {
+    type Output = Self;
+    fn mul(self, rhs: f64) -> Self {
+        Self {
+            vert: self.vert * rhs,
+            lat: self.lat * rhs,
+            roll: self.roll * rhs,
+        }
+    }
+}
+
+impl Neg for Forces {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self * -1.0
+    }
+}
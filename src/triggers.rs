@@ -0,0 +1,156 @@
+use std::io::{self, Write};
+
+use glam::DVec3;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{csv, error::TrackError, math::WrapperDQuat, track::Track, TrackSpline};
+
+// Where along the track a trigger goes, before its offset
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        tag = "type",
+        rename_all = "camelCase",
+        rename_all_fields = "camelCase"
+    )
+)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum TriggerAt {
+    // Meters along the track
+    Distance { distance: f64 },
+    SectionStart { section: usize },
+    SectionEnd { section: usize },
+    Highest,
+    Lowest,
+}
+
+// A named point along the track for ride control or show equipment, placed relative to
+// a distance or something on the track so it follows the layout as it's edited
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Trigger {
+    pub name: String,
+    pub at: TriggerAt,
+    // Meters further along than `at`, negative for before it
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub offset: f64,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct TriggerPlacement {
+    pub name: String,
+    pub distance: f64,
+    #[cfg_attr(feature = "ts", ts(type = "[number, number, number]"))]
+    pub pos: DVec3,
+    #[cfg_attr(feature = "ts", ts(type = "[number, number, number, number]"))]
+    pub rot: WrapperDQuat,
+    // Seconds from the start of the ride the train passes it
+    pub time: f64,
+    pub speed: f64,
+}
+
+impl Track {
+    // Every trigger placed on the simulated track, in the order they're defined. Fails if
+    // one refers to a section that isn't there or lands off either end
+    pub fn trigger_manifest(&self) -> Result<Vec<TriggerPlacement>, TrackError> {
+        self.check()?;
+        let splines = self.make_splines();
+        self.check_splines(&splines)?;
+        let spline = TrackSpline::concat(&splines);
+        let index = spline.index();
+        let distances = index.distances();
+
+        // Indices of the first and last point of each section in the joined spline
+        let mut first = 0;
+        let bounds: Vec<Option<(usize, usize)>> = splines
+            .iter()
+            .map(|s| {
+                let range = (!s.points.is_empty()).then(|| (first, first + s.points.len() - 1));
+                first += s.points.len();
+                range
+            })
+            .collect();
+        let extreme = |higher: fn(f64, f64) -> bool| {
+            let mut best = spline.points.first()?;
+            let mut at = 0;
+            for (i, point) in spline.points.iter().enumerate() {
+                if higher(point.pos.y, best.pos.y) {
+                    (best, at) = (point, i);
+                }
+            }
+            Some(distances[at])
+        };
+
+        self.triggers
+            .iter()
+            .map(|trigger| {
+                let base = match trigger.at {
+                    TriggerAt::Distance { distance } => Some(distance),
+                    TriggerAt::SectionStart { section } => bounds
+                        .get(section)
+                        .copied()
+                        .flatten()
+                        .map(|b| distances[b.0]),
+                    TriggerAt::SectionEnd { section } => bounds
+                        .get(section)
+                        .copied()
+                        .flatten()
+                        .map(|b| distances[b.1]),
+                    TriggerAt::Highest => extreme(|a, b| a > b),
+                    TriggerAt::Lowest => extreme(|a, b| a < b),
+                };
+                let distance = base.map(|base| base + trigger.offset);
+                let point = distance
+                    .and_then(|distance| index.evaluate(distance))
+                    .ok_or_else(|| TrackError::MissingTrigger {
+                        name: trigger.name.clone(),
+                    })?;
+                Ok(TriggerPlacement {
+                    name: trigger.name.clone(),
+                    distance: distance.unwrap_or_default(),
+                    pos: point.pos,
+                    rot: point.rot,
+                    time: point.time,
+                    speed: point.velocity,
+                })
+            })
+            .collect()
+    }
+}
+
+// One row per trigger, the frame as its forward and up vectors
+pub fn write_csv<W: Write>(placements: &[TriggerPlacement], mut writer: W) -> io::Result<()> {
+    writeln!(
+        writer,
+        "name,distance,time,speed,x,y,z,forwardX,forwardY,forwardZ,upX,upY,upZ"
+    )?;
+    for p in placements {
+        let (forward, up) = (p.rot.0 * DVec3::Z, p.rot.0 * DVec3::Y);
+        writeln!(
+            writer,
+            "{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            csv::field(&p.name),
+            p.distance,
+            p.time,
+            p.speed,
+            p.pos.x,
+            p.pos.y,
+            p.pos.z,
+            forward.x,
+            forward.y,
+            forward.z,
+            up.x,
+            up.y,
+            up.z
+        )?;
+    }
+    Ok(())
+}
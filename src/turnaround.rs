@@ -0,0 +1,138 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    first_drop::bisect,
+    track::{Track, TrackSection},
+    transitions::{RollMode, Transition, TransitionCurve, TransitionDomain, Transitions},
+    TrackSpline,
+};
+
+const HEADING_TOLERANCE: f64 = 1.0;
+const LAT_TOLERANCE: f64 = 0.05;
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct TurnaroundDesign {
+    // Degrees of heading change, 135 to 180 for a turnaround. Positive banks and turns right
+    pub heading: f64,
+    // G of vertical held through the turn
+    pub vert: f64,
+    // G of lateral the riders may feel. Spending it lets the turn bank less than a
+    // perfectly banked one would
+    pub max_lat: f64,
+    // Seconds to roll in and again to roll out
+    pub roll_time: f64,
+}
+
+impl Default for TurnaroundDesign {
+    fn default() -> Self {
+        Self {
+            heading: 180.0,
+            vert: 2.5,
+            max_lat: 0.2,
+            roll_time: 1.2,
+        }
+    }
+}
+
+fn transition(curve: TransitionCurve, value: f64, length: f64) -> Transition {
+    Transition {
+        curve,
+        value,
+        length,
+        center: 0.0,
+        tension: 0.0,
+    }
+}
+
+// Bank where `vert` and `lat` together hold the train level, from
+// vert cos(bank) - lat sin(bank) = 1
+fn level_bank(vert: f64, lat: f64) -> Option<f64> {
+    let total = libm::hypot(vert, lat);
+    (total >= 1.0).then(|| libm::acos(1.0 / total) - libm::atan2(lat, vert))
+}
+
+impl Track {
+    // A banked force section that turns the train round by `heading` with the lateral kept
+    // under `max_lat`. Rolls in to the bank where the vertical and lateral hold the train
+    // level, and the hold at that bank is solved by bisection for the heading. None if the
+    // turn can't be made with these numbers
+    pub fn turnaround(&self, design: &TurnaroundDesign) -> Option<TrackSection> {
+        let (start, forces) = self.end_state();
+        let side = if design.heading < 0.0 { -1.0 } else { 1.0 };
+        let lat = design.max_lat.max(0.0);
+        let bank = level_bank(design.vert, lat)?.to_degrees();
+        if !(0.0..90.0).contains(&bank) {
+            return None;
+        }
+
+        let r = design.roll_time;
+        let sine = TransitionCurve::Sinusoidal;
+        // The average of a sinusoidal ramp up and back down is half its peak
+        let peak = side * 2.0 * bank / r;
+        // Halfway through rolling in the bank and lateral are half what they will be, and the
+        // vertical is brought up to what holds the train level at that point
+        let half = (bank / 2.0).to_radians();
+        let half_vert = (1.0 + lat / 2.0 * libm::sin(half)) / libm::cos(half);
+        let section = |hold: f64| {
+            let held = transition(TransitionCurve::Linear, 0.0, hold);
+            // Zero length transitions evaluate to NaN
+            let channel = |transitions: Vec<Transition>| {
+                transitions
+                    .into_iter()
+                    .filter(|t| t.length > 1e-9)
+                    .collect()
+            };
+            TrackSection::Force {
+                fixed_speed: None,
+                transitions: Transitions {
+                    vert: channel(vec![
+                        transition(sine, half_vert - forces.vert, r / 2.0),
+                        transition(sine, design.vert - half_vert, r / 2.0),
+                        held,
+                        transition(sine, half_vert - design.vert, r / 2.0),
+                        transition(sine, forces.vert - half_vert, r / 2.0),
+                    ]),
+                    lat: channel(vec![
+                        transition(sine, side * lat - forces.lat, r),
+                        held,
+                        transition(sine, -side * lat, r),
+                    ]),
+                    roll: channel(vec![
+                        transition(sine, peak, r / 2.0),
+                        transition(sine, -peak, r / 2.0),
+                        held,
+                        transition(sine, -peak, r / 2.0),
+                        transition(sine, peak, r / 2.0),
+                    ]),
+                },
+                domain: TransitionDomain::Time,
+                roll_mode: RollMode::Rate,
+            }
+        };
+        let turned = |spline: &TrackSpline| {
+            let headings = spline.headings();
+            // Yaw falls turning right
+            Some(side * (headings.first()? - headings.last()?))
+        };
+        let hold = bisect(|hold| {
+            let spline = self.make_spline(&section(hold), start, forces);
+            Some(turned(&spline)? >= design.heading.abs())
+        })?;
+        let section = section(hold);
+
+        let spline = self.make_spline(&section, start, forces);
+        let turned = turned(&spline)?;
+        let max_lat = spline
+            .analysis(0.1)
+            .iter()
+            .map(|s| s.lat.abs())
+            .fold(0.0, f64::max);
+        ((turned - design.heading.abs()).abs() < HEADING_TOLERANCE
+            && max_lat <= design.max_lat + LAT_TOLERANCE)
+            .then_some(section)
+    }
+}
@@ -0,0 +1,339 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    analysis::AnalysisSample,
+    preset::{ConfigPreset, ForceLimits},
+    track::Track,
+    transitions::Channel,
+    TrackSpline,
+};
+
+// Meters between the samples the force envelope is checked at
+const ENVELOPE_STEP: f64 = 0.1;
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SanityLimits {
+    // m/s
+    pub max_speed: f64,
+    // m/s^2 along the track
+    pub max_acceleration: f64,
+}
+
+impl Default for SanityLimits {
+    fn default() -> Self {
+        Self {
+            max_speed: 60.0,
+            max_acceleration: 30.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum SanityKind {
+    Speed,
+    Acceleration,
+    NonFinite,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SanityIssue {
+    pub kind: SanityKind,
+    pub start: f64,
+    pub end: f64,
+    pub start_time: f64,
+    pub end_time: f64,
+    pub peak: f64,
+}
+
+// Forces that are fine for a moment but not for long, e.g. vertical over 4g for more than
+// 3 seconds. Flagged while the channel is outside `min`..`max`
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SustainedLimit {
+    pub channel: Channel,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub min: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub max: Option<f64>,
+    // Seconds
+    pub duration: f64,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct EnvelopeLimits {
+    pub limits: ForceLimits,
+    pub sustained: Vec<SustainedLimit>,
+}
+
+impl Default for EnvelopeLimits {
+    fn default() -> Self {
+        Self {
+            limits: ConfigPreset::SteelSitDown.values().limits,
+            sustained: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum EnvelopeKind {
+    MinVert,
+    MaxVert,
+    Lat,
+    RollRate,
+    // Index into EnvelopeLimits::sustained
+    Sustained(usize),
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct EnvelopeViolation {
+    pub kind: EnvelopeKind,
+    pub start: f64,
+    pub end: f64,
+    pub start_time: f64,
+    pub end_time: f64,
+    pub peak: f64,
+}
+
+pub(crate) struct FlaggedRange<K> {
+    pub kind: K,
+    pub start: f64,
+    pub end: f64,
+    pub start_time: f64,
+    pub end_time: f64,
+    pub peak: f64,
+}
+
+// Collects consecutive flagged samples into ranges, keeping the largest magnitude value
+pub(crate) struct RangeBuilder<K> {
+    open: Option<FlaggedRange<K>>,
+}
+
+impl<K: Copy + PartialEq> RangeBuilder<K> {
+    pub fn new() -> Self {
+        Self { open: None }
+    }
+
+    pub fn push(
+        &mut self,
+        flag: Option<(K, f64)>,
+        distance: f64,
+        time: f64,
+    ) -> Option<FlaggedRange<K>> {
+        match (&mut self.open, flag) {
+            (Some(open), Some((kind, value))) if open.kind == kind => {
+                open.end = distance;
+                open.end_time = time;
+                if value.abs() > open.peak.abs() {
+                    open.peak = value;
+                }
+                None
+            }
+            (_, flag) => {
+                let closed = self.open.take();
+                self.open = flag.map(|(kind, peak)| FlaggedRange {
+                    kind,
+                    start: distance,
+                    end: distance,
+                    start_time: time,
+                    end_time: time,
+                    peak,
+                });
+                closed
+            }
+        }
+    }
+
+    pub fn finish(self) -> Option<FlaggedRange<K>> {
+        self.open
+    }
+}
+
+impl From<FlaggedRange<SanityKind>> for SanityIssue {
+    fn from(range: FlaggedRange<SanityKind>) -> Self {
+        Self {
+            kind: range.kind,
+            start: range.start,
+            end: range.end,
+            start_time: range.start_time,
+            end_time: range.end_time,
+            peak: range.peak,
+        }
+    }
+}
+
+impl From<FlaggedRange<EnvelopeKind>> for EnvelopeViolation {
+    fn from(range: FlaggedRange<EnvelopeKind>) -> Self {
+        Self {
+            kind: range.kind,
+            start: range.start,
+            end: range.end,
+            start_time: range.start_time,
+            end_time: range.end_time,
+            peak: range.peak,
+        }
+    }
+}
+
+fn channel_value(sample: &AnalysisSample, channel: Channel) -> f64 {
+    match channel {
+        Channel::Vert => sample.vert,
+        Channel::Lat => sample.lat,
+        Channel::Roll => sample.roll_rate,
+    }
+}
+
+fn envelope_violations(
+    samples: &[AnalysisSample],
+    limits: &EnvelopeLimits,
+) -> Vec<EnvelopeViolation> {
+    let mut violations = Vec::new();
+    let l = &limits.limits;
+    // Over and under on the vertical can't overlap, so they share a builder
+    let mut vert = RangeBuilder::new();
+    let mut lat = RangeBuilder::new();
+    let mut roll = RangeBuilder::new();
+    let mut sustained: Vec<_> = limits
+        .sustained
+        .iter()
+        .map(|_| RangeBuilder::new())
+        .collect();
+    let mut keep_sustained = |range: FlaggedRange<EnvelopeKind>| {
+        let EnvelopeKind::Sustained(i) = range.kind else {
+            return None;
+        };
+        (range.end_time - range.start_time > limits.sustained[i].duration)
+            .then(|| EnvelopeViolation::from(range))
+    };
+
+    for sample in samples {
+        let (distance, time) = (sample.distance, sample.time);
+        let vert_flag = if sample.vert < l.min_vert {
+            Some((EnvelopeKind::MinVert, sample.vert))
+        } else if sample.vert > l.max_vert {
+            Some((EnvelopeKind::MaxVert, sample.vert))
+        } else {
+            None
+        };
+        let lat_flag = (sample.lat.abs() > l.max_lat).then_some((EnvelopeKind::Lat, sample.lat));
+        let roll_flag = (sample.roll_rate.abs() > l.max_roll_rate)
+            .then_some((EnvelopeKind::RollRate, sample.roll_rate));
+        violations.extend(
+            vert.push(vert_flag, distance, time)
+                .map(EnvelopeViolation::from),
+        );
+        violations.extend(
+            lat.push(lat_flag, distance, time)
+                .map(EnvelopeViolation::from),
+        );
+        violations.extend(
+            roll.push(roll_flag, distance, time)
+                .map(EnvelopeViolation::from),
+        );
+
+        for (i, (limit, builder)) in limits.sustained.iter().zip(&mut sustained).enumerate() {
+            let value = channel_value(sample, limit.channel);
+            let outside = limit.min.is_some_and(|min| value < min)
+                || limit.max.is_some_and(|max| value > max);
+            let flag = outside.then_some((EnvelopeKind::Sustained(i), value));
+            violations.extend(
+                builder
+                    .push(flag, distance, time)
+                    .and_then(&mut keep_sustained),
+            );
+        }
+    }
+    for builder in [vert, lat, roll] {
+        violations.extend(builder.finish().map(EnvelopeViolation::from));
+    }
+    for builder in sustained {
+        violations.extend(builder.finish().and_then(&mut keep_sustained));
+    }
+
+    violations.sort_by(|a, b| a.start.total_cmp(&b.start));
+    violations
+}
+
+impl TrackSpline {
+    // Every stretch where the forces leave the envelope, in order along the spline
+    pub fn check_envelope(&self, limits: &EnvelopeLimits) -> Vec<EnvelopeViolation> {
+        envelope_violations(&self.analysis(ENVELOPE_STEP), limits)
+    }
+
+    pub fn sanity_check(&self, limits: &SanityLimits) -> Vec<SanityIssue> {
+        let mut issues = Vec::new();
+        let mut speed = RangeBuilder::new();
+        let mut accel = RangeBuilder::new();
+        let mut distance = 0.0;
+
+        for (i, point) in self.points.iter().enumerate() {
+            let last = &self.points[i.saturating_sub(1)];
+            distance += (point.pos - last.pos).length();
+
+            let finite = point.pos.is_finite()
+                && point.rot.0.is_finite()
+                && point.velocity.is_finite()
+                && point.time.is_finite();
+            let speed_flag = if !finite {
+                Some((SanityKind::NonFinite, f64::NAN))
+            } else if point.velocity.abs() > limits.max_speed {
+                Some((SanityKind::Speed, point.velocity))
+            } else {
+                None
+            };
+            issues.extend(
+                speed
+                    .push(speed_flag, distance, point.time)
+                    .map(SanityIssue::from),
+            );
+
+            let dt = point.time - last.time;
+            let acceleration = if dt > 0.0 {
+                (point.velocity - last.velocity) / dt
+            } else {
+                0.0
+            };
+            let accel_flag = (acceleration.abs() > limits.max_acceleration)
+                .then_some((SanityKind::Acceleration, acceleration));
+            issues.extend(
+                accel
+                    .push(accel_flag, distance, point.time)
+                    .map(SanityIssue::from),
+            );
+        }
+        issues.extend(speed.finish().map(SanityIssue::from));
+        issues.extend(accel.finish().map(SanityIssue::from));
+
+        issues.sort_by(|a, b| a.start.total_cmp(&b.start));
+        issues
+    }
+}
+
+impl Track {
+    // Same as TrackSpline::check_envelope, with the track's roughness applied to the forces
+    pub fn check_envelope(&self, limits: &EnvelopeLimits) -> Vec<EnvelopeViolation> {
+        envelope_violations(&self.analysis(ENVELOPE_STEP), limits)
+    }
+}
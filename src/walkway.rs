@@ -0,0 +1,117 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{grade::grade, math::euler, track::Track};
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct WalkwayLimits {
+    // Percent
+    pub max_grade: f64,
+    // Degrees
+    pub max_bank: f64,
+    // Meters, shorter flat stretches aren't reported
+    pub min_length: f64,
+}
+
+impl Default for WalkwayLimits {
+    fn default() -> Self {
+        Self {
+            max_grade: 10.0,
+            max_bank: 5.0,
+            min_length: 3.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FlatSpot {
+    // Section the flat spot starts in
+    pub section: usize,
+    pub start: f64,
+    pub end: f64,
+    pub max_grade: f64,
+    pub max_bank: f64,
+}
+
+// Sections from the one after the previous brake up to and including the next brake
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct BlockFlatSpots {
+    pub first_section: usize,
+    pub last_section: usize,
+    pub start: f64,
+    pub end: f64,
+    pub flat_spots: Vec<FlatSpot>,
+}
+
+impl Track {
+    pub fn flat_spots(&self, limits: &WalkwayLimits) -> Vec<BlockFlatSpots> {
+        let splines = self.make_splines();
+        let mut blocks = Vec::new();
+        let mut block: Option<BlockFlatSpots> = None;
+        let mut open: Option<FlatSpot> = None;
+        let mut distance = 0.0;
+        let mut last_pos = self.initial_point().pos;
+
+        let close = |open: &mut Option<FlatSpot>, block: &mut BlockFlatSpots| {
+            if let Some(spot) = open.take() {
+                if spot.end - spot.start >= limits.min_length {
+                    block.flat_spots.push(spot);
+                }
+            }
+        };
+
+        for (i, (section, spline)) in self.sections.iter().zip(&splines).enumerate() {
+            let current = block.get_or_insert_with(|| BlockFlatSpots {
+                first_section: i,
+                last_section: i,
+                start: distance,
+                end: distance,
+                flat_spots: Vec::new(),
+            });
+
+            for point in &spline.points {
+                distance += (point.pos - last_pos).length();
+                last_pos = point.pos;
+
+                let grade = grade(point).abs();
+                let bank = euler(point).2.abs();
+                if grade > limits.max_grade || bank > limits.max_bank {
+                    close(&mut open, current);
+                    continue;
+                }
+                let spot = open.get_or_insert(FlatSpot {
+                    section: i,
+                    start: distance,
+                    end: distance,
+                    max_grade: 0.0,
+                    max_bank: 0.0,
+                });
+                spot.end = distance;
+                spot.max_grade = spot.max_grade.max(grade);
+                spot.max_bank = spot.max_bank.max(bank);
+            }
+
+            current.last_section = i;
+            current.end = distance;
+            if section.ends_block() {
+                close(&mut open, current);
+                blocks.extend(block.take());
+            }
+        }
+
+        if let Some(mut current) = block {
+            close(&mut open, &mut current);
+            blocks.push(current);
+        }
+        blocks
+    }
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{airtime::AirtimeOptions, track::Track};
+
+#[test]
+fn reports_airtime_and_sustained_g() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 20}, "sections": [
+            {"type": "force", "fixedSpeed": 20, "transitions": {
+                "vert": [
+                    {"curve": "cubic", "value": -1.5, "length": 0.5},
+                    {"curve": "linear", "value": 0, "length": 1},
+                    {"curve": "cubic", "value": 1.5, "length": 0.5},
+                    {"curve": "cubic", "value": 3, "length": 0.5},
+                    {"curve": "linear", "value": 0, "length": 1.5},
+                    {"curve": "cubic", "value": -3, "length": 0.5}
+                ],
+                "lat": [{"curve": "linear", "value": 0, "length": 4.5}],
+                "roll": [{"curve": "linear", "value": 0, "length": 4.5}]
+            }}
+        ]}"#,
+    )
+    .unwrap();
+    let report = track.airtime(&AirtimeOptions::default());
+
+    assert_eq!(report.airtime.len(), 1);
+    let air = report.airtime[0];
+    assert!((air.peak + 0.5).abs() < 0.01);
+    assert!(air.duration > 1.0 && air.duration < 1.5, "{air:?}");
+    assert_eq!(report.total_airtime, air.duration);
+
+    assert_eq!(report.sustained.len(), 1);
+    let held = report.sustained[0];
+    assert!((held.peak - 4.0).abs() < 0.01);
+    assert!(held.start_time > air.end_time);
+    assert!(held.duration > 1.5);
+}
@@ -0,0 +1,51 @@
+use fvd_rs::{
+    banking::Banking,
+    math::euler,
+    track::{Track, TrackSection},
+    transitions::TransitionCurve,
+};
+
+fn turn(banking: Option<Banking>) -> Track {
+    let mut track = Track::default();
+    track.anchor.velocity = 15.0;
+    track.sections.push(TrackSection::Curved {
+        fixed_speed: Some(15.0),
+        radius: 30.0,
+        direction: 90.0,
+        angle: 90.0,
+        banking,
+        spiral: 0.0,
+    });
+    track
+}
+
+#[test]
+fn banked_curve_rolls_in_and_out_on_the_same_path() {
+    let banking = Banking {
+        angle: 40.0,
+        curve: TransitionCurve::Sinusoidal,
+        length: 10.0,
+    };
+    let flat = turn(None).make_splines().remove(0);
+    let banked = turn(Some(banking)).make_splines().remove(0);
+    assert_eq!(flat.points.len(), banked.points.len());
+    for (a, b) in flat.points.iter().zip(&banked.points) {
+        assert!((a.pos - b.pos).length() < 1e-9);
+    }
+
+    let banks = banked.bank_angles();
+    assert!(
+        (banks[banks.len() / 2] - 40.0).abs() < 0.01,
+        "{}",
+        banks[banks.len() / 2]
+    );
+    assert!(banks.last().unwrap().abs() < 0.01);
+    // Ten meters into the 47 meter curve it's all the way over
+    let rolled_in = banked
+        .points
+        .iter()
+        .position(|p| p.pos.length() > 10.5)
+        .unwrap();
+    assert!((euler(&banked.points[rolled_in]).2 - 40.0).abs() < 0.5);
+    assert!(euler(&flat.points[rolled_in]).2.abs() < 0.01);
+}
@@ -0,0 +1,35 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{blocks::BlockOptions, track::Track};
+
+#[test]
+fn short_block_brake_cannot_stop_train() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 20}, "sections": [
+            {"type": "straight", "length": 50, "fixedSpeed": 20},
+            {"type": "brake", "length": 30, "deceleration": 4, "exitSpeed": 10, "brakeType": "friction"},
+            {"type": "straight", "length": 50},
+            {"type": "brake", "length": 70, "deceleration": 4, "exitSpeed": 2, "brakeType": "friction"}
+        ]}"#,
+    )
+    .unwrap();
+    let options = BlockOptions {
+        speed_margin: 1.0,
+        ..BlockOptions::default()
+    };
+    let spacing = track.block_spacing(&options);
+    assert_eq!(spacing.blocks.len(), 2);
+
+    // 20 m/s needs 50 m at 4 m/s^2
+    let first = &spacing.blocks[0];
+    assert!((first.stopping_distance.unwrap() - 50.0).abs() < 0.01);
+    assert!(!first.stops);
+    assert!(spacing.blocks[1].stops);
+
+    let slowest = spacing
+        .blocks
+        .iter()
+        .map(|b| b.clear_time)
+        .fold(0.0, f64::max);
+    assert_eq!(spacing.min_separation, slowest);
+}
@@ -0,0 +1,24 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{bobsled::TroughOptions, track::Track};
+
+#[test]
+fn slides_until_grip_holds_it() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 15}, "sections": [
+            {"type": "straight", "length": 20, "fixedSpeed": 15},
+            {"type": "curved", "radius": 25, "angle": 180, "direction": 90, "fixedSpeed": 15}
+        ]}"#,
+    )
+    .unwrap();
+    let options = TroughOptions::default();
+    let samples = track.bobsled(&options);
+
+    let straight = &samples[5];
+    assert_eq!(straight.offset, 0.0);
+
+    let curve = &samples[samples.len() - 10];
+    assert!(curve.offset.abs() > 1.0, "{curve:?}");
+    assert!((curve.lat.abs() - options.grip).abs() < 1e-6, "{curve:?}");
+    assert!(!curve.spill);
+}
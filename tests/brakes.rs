@@ -0,0 +1,59 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{brakes::BrakeType, track::Track};
+
+fn brake(brake: &str) -> Track {
+    Track::from_json(&format!(
+        r#"{{"anchor": {{"velocity": 20}}, "sections": [
+            {{"type": "straight", "length": 10}},
+            {}
+        ]}}"#,
+        brake
+    ))
+    .unwrap()
+}
+
+#[test]
+fn magnetic_brake_fades_at_low_speed() {
+    let track =
+        brake(r#"{"type": "brake", "length": 80, "deceleration": 5, "brakeType": "magnetic"}"#);
+    let violations = track.brake_violations();
+    assert_eq!(violations.len(), 1, "{violations:?}");
+    let violation = violations[0];
+    assert_eq!(violation.section, 1);
+    assert_eq!(violation.requested, 5.0);
+
+    // The highest speed where 5 m/s^2 is out of reach, between 3 and 10 m/s on the curve
+    let curve = BrakeType::Magnetic.curve();
+    assert!(
+        violation.speed > 3.0 && violation.speed < 10.0,
+        "{violation:?}"
+    );
+    assert_eq!(violation.available, curve.max_deceleration(violation.speed));
+    assert!(violation.available < 5.0 && violation.available > 4.9);
+}
+
+#[test]
+fn brakes_within_their_curve_pass() {
+    // Friction brakes hold right down to a stop
+    let friction =
+        brake(r#"{"type": "brake", "length": 80, "deceleration": 4, "brakeType": "friction"}"#);
+    assert!(friction.brake_violations().is_empty());
+
+    // Only the speeds above the exit speed are braked
+    let trim = brake(
+        r#"{"type": "brake", "length": 40, "deceleration": 5, "exitSpeed": 12, "brakeType": "magnetic"}"#,
+    );
+    assert!(trim.brake_violations().is_empty());
+}
+
+#[test]
+fn custom_curve_overrides_the_brake_type() {
+    let track = brake(
+        r#"{"type": "brake", "length": 80, "deceleration": 4, "brakeType": "friction",
+            "curve": {"points": [[0, 1], [15, 1], [16, 6]]}}"#,
+    );
+    let violations = track.brake_violations();
+    assert_eq!(violations.len(), 1, "{violations:?}");
+    assert!(violations[0].speed < 16.0 && violations[0].available < 4.0);
+}
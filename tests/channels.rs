@@ -0,0 +1,44 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::track::Track;
+
+fn vertical_loop() -> Track {
+    Track::from_json(
+        r#"{"anchor": {"velocity": 20}, "sections": [
+            {"type": "curved", "radius": 15, "angle": 360, "direction": 0, "fixedSpeed": 20}
+        ]}"#,
+    )
+    .unwrap()
+}
+
+// Euler roll flips by 180 going past vertical, an unbanked loop never banks
+#[test]
+fn loop_stays_unbanked() {
+    let banks = vertical_loop().get_spline().0.bank_angles();
+    assert!(banks.iter().all(|bank| bank.abs() < 1e-6), "{banks:?}");
+}
+
+#[test]
+fn loop_holds_its_heading() {
+    let headings = vertical_loop().get_spline().0.headings();
+    assert!(
+        headings.iter().all(|h| (h - headings[0]).abs() < 1e-6),
+        "{headings:?}"
+    );
+}
+
+#[test]
+fn flat_turns_add_up_past_a_full_circle() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 20}, "sections": [
+            {"type": "curved", "radius": 20, "angle": 270, "direction": 90, "fixedSpeed": 20},
+            {"type": "curved", "radius": 20, "angle": 270, "direction": 90, "fixedSpeed": 20}
+        ]}"#,
+    )
+    .unwrap();
+    let headings = track.get_spline().0.headings();
+    let turned = headings.last().unwrap() - headings[0];
+    assert!((turned.abs() - 540.0).abs() < 0.5, "{turned}");
+    // No jumps where the sections meet
+    assert!(headings.windows(2).all(|w| (w[1] - w[0]).abs() < 6.0));
+}
@@ -0,0 +1,35 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{glam::DVec3, track::Track};
+
+#[test]
+fn envelope_is_measured_from_the_rails() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 10}, "config": {"heartlineHeight": 1.1}, "sections": [
+            {"type": "straight", "length": 20}
+        ]}"#,
+    )
+    .unwrap();
+    let (spline, _) = track.get_spline();
+    let point = spline.evaluate(10.0).unwrap();
+    let up = point.rot.0 * DVec3::Y;
+    let rail = point.pos - up * 1.1;
+
+    // The sit down envelope runs from 0.6 m under the rails to 2.5 m over them
+    let obstacles = [
+        rail + up * 2.4,
+        rail + up * 2.6,
+        rail - up * 0.5,
+        rail - up * 0.7,
+    ];
+    let hit: Vec<usize> = track
+        .clearance_violations(&obstacles)
+        .iter()
+        .map(|v| v.obstacle)
+        .collect();
+    assert!(hit.contains(&0) && hit.contains(&2), "{hit:?}");
+    assert!(!hit.contains(&1) && !hit.contains(&3), "{hit:?}");
+
+    let violation = track.clearance_violations(&obstacles[..1])[0];
+    assert!((violation.distance - 10.0).abs() < 0.05);
+}
@@ -0,0 +1,36 @@
+#![cfg(feature = "json")]
+
+use std::{sync::Arc, thread};
+
+use fvd_rs::{compiled::CompiledTrack, track::Track, TrackSpline};
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn shared_between_threads() {
+    assert_send_sync::<CompiledTrack>();
+
+    let track = Track::from_json(include_str!("../week_11.json")).unwrap();
+    // Stats are of the full resolution spline
+    let expected = TrackSpline::concat(&track.make_splines()).total_distance();
+    let compiled = Arc::new(CompiledTrack::new(track));
+
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let compiled = compiled.clone();
+            thread::spawn(move || {
+                let stats = compiled.stats().unwrap();
+                let forces = compiled.forces(10.0 * i as f64 + 5.0).unwrap();
+                (stats.total.length, forces.is_some())
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        let (length, found) = handle.join().unwrap();
+        assert!((length - expected).abs() < 1e-9);
+        assert!(found);
+    }
+    let first = compiled.compiled().unwrap();
+    assert!(Arc::ptr_eq(&first, &compiled.compiled().unwrap()));
+}
@@ -0,0 +1,58 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{track::Track, TrackPoint, TrackSpline};
+
+// Every native and wasm32 build must reproduce this fixture. Regenerate it with
+// FVD_BLESS=1 cargo test --test conformance, only when a change is meant to alter the output.
+const FIXTURE: &str = include_str!("fixtures/week_11_conformance.json");
+const STRIDE: usize = 250;
+const TOLERANCE: f64 = 1e-9;
+
+fn sampled_spline() -> TrackSpline {
+    let track: Track = serde_json::from_str(include_str!("../week_11.json")).unwrap();
+    let (spline, _) = track.get_spline();
+    let mut points: Vec<TrackPoint> = spline.points.iter().step_by(STRIDE).copied().collect();
+    points.extend(spline.points.last());
+    TrackSpline { points }
+}
+
+fn assert_close(name: &str, index: usize, expected: f64, actual: f64) {
+    assert!(
+        (expected - actual).abs() <= TOLERANCE,
+        "point {index} {name}: expected {expected}, got {actual}"
+    );
+}
+
+#[test]
+fn week_11_matches_fixture() {
+    let actual = sampled_spline();
+    if std::env::var_os("FVD_BLESS").is_some() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/week_11_conformance.json"
+        );
+        std::fs::write(path, serde_json::to_string_pretty(&actual).unwrap()).unwrap();
+        return;
+    }
+
+    let expected: TrackSpline = serde_json::from_str(FIXTURE).unwrap();
+    assert_eq!(expected.points.len(), actual.points.len());
+    for (i, (e, a)) in expected.points.iter().zip(&actual.points).enumerate() {
+        for axis in 0..3 {
+            assert_close("pos", i, e.pos[axis], a.pos[axis]);
+        }
+        let (e_rot, a_rot) = (e.rot.0.to_array(), a.rot.0.to_array());
+        for axis in 0..4 {
+            assert_close("rot", i, e_rot[axis], a_rot[axis]);
+        }
+        assert_close("velocity", i, e.velocity, a.velocity);
+        assert_close("time", i, e.time, a.time);
+    }
+}
+
+#[test]
+fn repeated_runs_are_identical() {
+    let first = serde_json::to_string(&sampled_spline()).unwrap();
+    let second = serde_json::to_string(&sampled_spline()).unwrap();
+    assert_eq!(first, second);
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{output::Decimation, track::Track};
+
+#[test]
+fn curvature_policy_is_dense_in_curves() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 15}, "sections": [
+            {"type": "straight", "length": 50, "fixedSpeed": 15},
+            {"type": "curved", "radius": 10, "angle": 90, "direction": 90, "fixedSpeed": 15}
+        ]}"#,
+    )
+    .unwrap();
+    let splines = track.make_splines();
+    let straight = splines[0].decimate(
+        Decimation::Curvature {
+            max_angle: 2.0,
+            max_spacing: 5.0,
+        },
+        false,
+    );
+    let curve = splines[1].decimate(
+        Decimation::Curvature {
+            max_angle: 2.0,
+            max_spacing: 5.0,
+        },
+        false,
+    );
+
+    // 50 m at one point per 5 m, 90 degrees at a point every 2 degrees or a little over
+    assert_eq!(straight.points.len(), 11);
+    assert!(
+        (40..=47).contains(&curve.points.len()),
+        "{}",
+        curve.points.len()
+    );
+    assert_eq!(
+        curve.points.last().unwrap().pos,
+        splines[1].points.last().unwrap().pos
+    );
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{
+    get_spline_with_options,
+    output::{Decimation, OutputOptions},
+    track::Track,
+};
+
+const TRACK: &str = r#"{"anchor": {"velocity": 15}, "sections": [
+    {"type": "straight", "length": 30, "fixedSpeed": 15},
+    {"type": "curved", "radius": 10, "angle": 90, "direction": 90, "fixedSpeed": 15}
+]}"#;
+
+#[test]
+fn distance_is_arc_length_before_decimation() {
+    let track = Track::from_json(TRACK).unwrap();
+    let options = OutputOptions {
+        distance: true,
+        ..Default::default()
+    };
+    let (spline, section_start) = track.try_get_spline_with_options(&options).unwrap();
+    let full = track.get_spline_with_options(&OutputOptions {
+        decimation: Decimation::Every { n: 1 },
+        ..Default::default()
+    });
+
+    // Decimated points keep the distance along the full spline, not between kept points
+    let index = full.0.index();
+    for point in &spline.points {
+        let distance = point.distance.unwrap();
+        let expected = index.evaluate(distance).unwrap();
+        assert!((expected.pos - point.pos).length() < 1e-6, "at {distance}");
+    }
+    let curve = spline
+        .points
+        .iter()
+        .find(|p| p.distance.unwrap() > section_start[1])
+        .unwrap();
+    assert!(curve.distance.unwrap() - section_start[1] < 0.1);
+    assert!(spline
+        .points
+        .windows(2)
+        .all(|w| w[0].distance < w[1].distance));
+    assert!(spline.points.last().unwrap().distance.unwrap() <= index.total_distance());
+}
+
+#[test]
+fn distance_is_only_serialized_when_asked_for() {
+    let without = get_spline_with_options(TRACK, "{}").unwrap();
+    assert!(!without.contains("distance"));
+    let with = get_spline_with_options(TRACK, r#"{"distance": true}"#).unwrap();
+    assert!(with.contains("\"distance\""));
+}
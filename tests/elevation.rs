@@ -0,0 +1,45 @@
+use fvd_rs::{glam::DVec3, TrackPoint, TrackSpline};
+
+// One point per meter along x through the given heights, linearly in between
+fn profile(heights: &[f64]) -> TrackSpline {
+    let mut points = Vec::new();
+    for pair in heights.windows(2) {
+        for step in 0..10 {
+            let y = pair[0] + (pair[1] - pair[0]) * step as f64 / 10.0;
+            points.push(TrackPoint {
+                pos: DVec3::new(points.len() as f64, y, 0.0),
+                velocity: 10.0,
+                ..Default::default()
+            });
+        }
+    }
+    points.push(TrackPoint {
+        pos: DVec3::new(points.len() as f64, *heights.last().unwrap(), 0.0),
+        velocity: 10.0,
+        ..Default::default()
+    });
+    TrackSpline { points }
+}
+
+#[test]
+fn small_first_dip_counts_toward_the_hill() {
+    let report = profile(&[0.0, -4.0, 10.0, 0.0]).elevation_changes(5.0);
+    assert_eq!(report.hills.len(), 1, "{report:?}");
+    assert!((report.hills[0].height - 14.0).abs() < 1e-9);
+    assert_eq!(
+        report.hills[0].crest_distance,
+        report.drops[0].crest_distance
+    );
+}
+
+#[test]
+fn small_first_rise_counts_toward_the_drop() {
+    let report = profile(&[0.0, 4.0, -10.0]).elevation_changes(5.0);
+    assert_eq!(report.drops.len(), 1, "{report:?}");
+    let drop = report.drops[0];
+    assert!((drop.height - 14.0).abs() < 1e-9);
+    assert!((drop.crest_height - 4.0).abs() < 1e-9);
+    // Along the track, 10 m across and 4 m up then 10 m across and 14 m down
+    assert!((drop.crest_distance - 116f64.sqrt()).abs() < 1e-9);
+    assert!((drop.length - 296f64.sqrt()).abs() < 1e-9);
+}
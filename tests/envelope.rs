@@ -0,0 +1,48 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{
+    track::Track,
+    transitions::Channel,
+    validation::{EnvelopeKind, EnvelopeLimits, SustainedLimit},
+};
+
+#[test]
+fn violations_cover_the_overload() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 20}, "sections": [
+            {"type": "force", "fixedSpeed": 20, "transitions": {
+                "vert": [
+                    {"curve": "cubic", "value": 4.5, "length": 0.5},
+                    {"curve": "linear", "value": 0, "length": 2},
+                    {"curve": "cubic", "value": -4.5, "length": 0.5}
+                ],
+                "lat": [{"curve": "linear", "value": 0, "length": 3}],
+                "roll": [{"curve": "linear", "value": 0, "length": 3}]
+            }}
+        ]}"#,
+    )
+    .unwrap();
+    let sustained = |duration| SustainedLimit {
+        channel: Channel::Vert,
+        min: None,
+        max: Some(4.0),
+        duration,
+    };
+    let limits = EnvelopeLimits {
+        sustained: vec![sustained(1.0), sustained(5.0)],
+        ..Default::default()
+    };
+    let violations = track.check_envelope(&limits);
+
+    assert_eq!(violations.len(), 2, "{violations:?}");
+    let over = violations
+        .iter()
+        .find(|v| v.kind == EnvelopeKind::MaxVert)
+        .unwrap();
+    assert!((over.peak - 5.5).abs() < 0.01);
+    assert!(over.end_time - over.start_time > 2.0);
+    assert!(over.end > over.start);
+    assert!(violations
+        .iter()
+        .any(|v| v.kind == EnvelopeKind::Sustained(0)));
+}
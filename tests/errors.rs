@@ -0,0 +1,95 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{error::TrackError, get_spline, track::Track};
+
+#[test]
+fn parse_error_has_location() {
+    let err = Track::from_json("{\n  \"sections\": 3\n}").unwrap_err();
+    let TrackError::Parse { line, column, .. } = err else {
+        panic!("expected a parse error, got {err:?}");
+    };
+    assert_eq!((line, column), (2, 15));
+}
+
+#[test]
+fn empty_sections() {
+    let track = Track::from_json("{}").unwrap();
+    assert_eq!(
+        track.try_get_spline().unwrap_err(),
+        TrackError::EmptySections
+    );
+}
+
+#[test]
+fn invalid_config() {
+    let track = Track::from_json(
+        r#"{"config": {"parameter": -1}, "sections": [{"type": "straight", "length": 10}]}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        track.check().unwrap_err(),
+        TrackError::InvalidConfig {
+            field: "parameter",
+            value: -1.0
+        }
+    );
+}
+
+#[test]
+fn invalid_roughness() {
+    let roughness = |roughness: &str| {
+        Track::from_json(&format!(
+            r#"{{"config": {{"roughness": {roughness}}}, "sections": [{{"type": "straight", "length": 10}}]}}"#
+        ))
+        .unwrap()
+        .check()
+    };
+    assert_eq!(
+        roughness(r#"{"referenceSpeed": 0}"#).unwrap_err(),
+        TrackError::InvalidConfig {
+            field: "roughness.referenceSpeed",
+            value: 0.0
+        }
+    );
+    assert_eq!(
+        roughness(r#"{"wavelength": -1.5}"#).unwrap_err(),
+        TrackError::InvalidConfig {
+            field: "roughness.wavelength",
+            value: -1.5
+        }
+    );
+    assert_eq!(
+        roughness(r#"{"amplitude": -0.1}"#).unwrap_err(),
+        TrackError::InvalidConfig {
+            field: "roughness.amplitude",
+            value: -0.1
+        }
+    );
+    assert!(roughness(r#"{"amplitude": 0}"#).is_ok());
+}
+
+#[test]
+fn stall_is_reported_instead_of_panicking() {
+    let track = Track::from_json(
+        r#"{
+            "config": {"parameter": 0.03},
+            "anchor": {"velocity": 5},
+            "sections": [
+                {"type": "brake", "length": 20, "deceleration": 5},
+                {"type": "straight", "length": 10}
+            ]
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        track.try_get_spline().unwrap_err(),
+        TrackError::Stalled { section: 1 }
+    );
+    assert!(!track.get_spline().0.points.is_empty());
+}
+
+#[test]
+fn export_returns_error() {
+    assert!(get_spline("not json").is_err());
+    assert!(get_spline(include_str!("../week_11.json")).is_ok());
+}
@@ -0,0 +1,37 @@
+use fvd_rs::{first_drop::DropDesign, glam::DVec3, math::euler, track::Track};
+
+#[test]
+fn drop_meets_its_design() {
+    let mut track = Track::default();
+    track.anchor.pos = DVec3::new(0.0, 50.0, 0.0);
+    track.anchor.velocity = 4.0;
+    let design = DropDesign {
+        height: 45.0,
+        peak_vert: 3.5,
+        ..Default::default()
+    };
+    let section = track.first_drop(&design).unwrap();
+    track.sections.push(section);
+
+    let (spline, _) = track.get_spline();
+    let end = spline.points.last().unwrap();
+    assert!((end.pos.y - 5.0).abs() < 0.5, "{:?}", end.pos);
+    assert!(euler(end).1.abs() < 1.0);
+
+    let stats = spline.stats();
+    assert!((stats.max_vert - 3.5).abs() < 0.1, "{stats:?}");
+    assert!((stats.min_vert - 0.2).abs() < 0.1, "{stats:?}");
+    assert!(stats.lowest.y > 4.0);
+}
+
+#[test]
+fn drop_too_short_for_pullout() {
+    let mut track = Track::default();
+    track.anchor.pos = DVec3::new(0.0, 50.0, 0.0);
+    track.anchor.velocity = 4.0;
+    let design = DropDesign {
+        height: 3.0,
+        ..Default::default()
+    };
+    assert!(track.first_drop(&design).is_none());
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::track::Track;
+
+#[test]
+fn flat_turn_leans_the_force_sideways() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 15}, "sections": [
+            {"type": "straight", "length": 20, "fixedSpeed": 15},
+            {"type": "curved", "radius": 20, "angle": 90, "direction": 90, "fixedSpeed": 15}
+        ]}"#,
+    )
+    .unwrap();
+    let samples = track.analysis(1.0);
+
+    let straight = &samples[5];
+    assert!(straight.sagittal.abs() < 1e-3 && straight.coronal.abs() < 1e-3);
+
+    // 15 m/s round a 20 m radius with nothing holding the train up but the seat
+    let lean = (225.0 / 20.0 / 9.80665f64).atan().to_degrees();
+    let turn = &samples[samples.len() - 10];
+    assert!(turn.long.abs() < 1e-6);
+    assert!((turn.coronal.abs() - lean).abs() < 0.5);
+    assert_eq!(turn.coronal.signum(), turn.lat.signum());
+
+    let stats = track.stats().unwrap();
+    assert!((stats.total.max_coronal - lean).abs() < 0.5);
+    assert!(stats.total.max_sagittal < 1.0);
+}
@@ -0,0 +1,155 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{constants::G, math::euler, track::Track, TrackSpline};
+use glam::DVec3;
+
+// Straight up is where Euler angles lose the heading, the forces shouldn't notice.
+// With no curvature only gravity is left
+#[test]
+fn vertical_straight_only_feels_gravity() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 20}, "sections": [
+            {"type": "curved", "radius": 15, "angle": 90, "direction": 0, "fixedSpeed": 20},
+            {"type": "straight", "length": 10, "fixedSpeed": 20}
+        ]}"#,
+    )
+    .unwrap();
+    let splines = track.make_splines();
+    let vertical = &splines[1];
+
+    for i in 1..50 {
+        let distance = i as f64 * 0.19;
+        let forces = vertical.forces(distance).unwrap();
+        let (_, point) = vertical.eval_closest(distance).unwrap();
+        assert!(
+            (forces.vert - (point.rot.0 * DVec3::Y).y).abs() < 1e-9,
+            "{forces:?}"
+        );
+        assert!(
+            (forces.lat - (point.rot.0 * DVec3::NEG_X).y).abs() < 1e-9,
+            "{forces:?}"
+        );
+    }
+}
+
+#[test]
+fn loop_matches_centripetal_load() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 20}, "sections": [
+            {"type": "curved", "radius": 15, "angle": 180, "direction": 0, "fixedSpeed": 20}
+        ]}"#,
+    )
+    .unwrap();
+    let spline = &track.make_splines()[0];
+    let length = spline.total_distance();
+    let centripetal = 20.0 * 20.0 / 15.0 / G;
+
+    for i in 1..20 {
+        let distance = length * i as f64 / 20.0;
+        let forces = spline.forces(distance).unwrap();
+        let (_, point) = spline.eval_closest(distance).unwrap();
+        let gravity = (point.rot.0 * DVec3::Y).y;
+        assert!(
+            (forces.vert - centripetal - gravity).abs() < 0.05,
+            "{forces:?} at {distance}"
+        );
+    }
+}
+
+fn force_section_end(simulation: &str) -> DVec3 {
+    let track = Track::from_json(&format!(
+        r#"{{"anchor": {{"velocity": 15}}, "config": {{"simulation": {simulation}}}, "sections": [
+            {{"type": "force", "transitions": {{
+                "vert": [{{"curve": "cubic", "value": 3, "length": 2}}],
+                "lat": [{{"curve": "cubic", "value": 1.5, "length": 2}}],
+                "roll": [{{"curve": "linear", "value": 90, "length": 2}}]
+            }}}}
+        ]}}"#
+    ))
+    .unwrap();
+    track.make_splines()[0].points.last().unwrap().pos
+}
+
+#[test]
+fn rk4_converges_at_coarse_steps() {
+    let reference = force_section_end(r#"{"timeStep": 0.0005, "integrator": {"type": "rk4"}}"#);
+    let euler = force_section_end(r#"{"timeStep": 0.02}"#);
+    let rk4 = force_section_end(r#"{"timeStep": 0.02, "integrator": {"type": "rk4"}}"#);
+    let adaptive = force_section_end(
+        r#"{"timeStep": 0.02, "integrator": {"type": "adaptive", "tolerance": 1e-7}}"#,
+    );
+
+    let euler_error = (euler - reference).length();
+    assert!(
+        (rk4 - reference).length() < euler_error / 10.0,
+        "{rk4} {euler}"
+    );
+    assert!(
+        (adaptive - reference).length() < euler_error / 10.0,
+        "{adaptive}"
+    );
+}
+
+fn distance_section(speed: f64, simulation: &str) -> TrackSpline {
+    let track = Track::from_json(&format!(
+        r#"{{"anchor": {{"velocity": {speed}}}, "config": {{"simulation": {simulation}}},
+            "sections": [
+            {{"type": "force", "domain": "distance", "transitions": {{
+                "vert": [{{"curve": "linear", "value": 0, "length": 30}}],
+                "lat": [{{"curve": "cubic", "value": 0.5, "length": 30}}],
+                "roll": [{{"curve": "linear", "value": 0, "length": 30}}]
+            }}}}
+        ]}}"#
+    ))
+    .unwrap();
+    track.make_splines().remove(0)
+}
+
+#[test]
+fn distance_transitions_keep_their_length_of_track() {
+    for simulation in ["{}", r#"{"integrator": {"type": "rk4"}}"#] {
+        for speed in [10.0, 25.0] {
+            let spline = distance_section(speed, simulation);
+            let length = spline.total_distance();
+            assert!((length - 30.0).abs() < 0.1, "{length} at {speed}");
+        }
+    }
+
+    // Faster means less time over the same track
+    let duration = |speed| distance_section(speed, "{}").duration();
+    assert!((duration(10.0) / duration(25.0) - 2.5).abs() < 0.1);
+}
+
+fn banked_section(speed: f64, simulation: &str) -> TrackSpline {
+    let track = Track::from_json(&format!(
+        r#"{{"anchor": {{"velocity": {speed}}}, "config": {{"simulation": {simulation}}},
+            "sections": [
+            {{"type": "force", "rollMode": "bank", "transitions": {{
+                "vert": [{{"curve": "cubic", "value": 0.5, "length": 3}}],
+                "lat": [{{"curve": "cubic", "value": 0.4, "length": 3}}],
+                "roll": [
+                    {{"curve": "sinusoidal", "value": 60, "length": 1}},
+                    {{"curve": "linear", "value": 60, "length": 1}},
+                    {{"curve": "sinusoidal", "value": 0, "length": 1}}
+                ]
+            }}}}
+        ]}}"#
+    ))
+    .unwrap();
+    track.make_splines().remove(0)
+}
+
+#[test]
+fn bank_roll_lands_on_the_target_whatever_the_speed() {
+    for simulation in ["{}", r#"{"integrator": {"type": "rk4"}}"#] {
+        for speed in [10.0, 30.0] {
+            // Bank mode targets the roll relative to gravity, not the twist added up along
+            // the track that bank_angles reports
+            let spline = banked_section(speed, simulation);
+            let banks: Vec<f64> = spline.points.iter().map(|p| euler(p).2).collect();
+            let (held, exit) = (banks[banks.len() / 2], *banks.last().unwrap());
+            assert!((held - 60.0).abs() < 0.5, "{held} at {speed}");
+            assert!(exit.abs() < 0.01, "{exit} at {speed}");
+        }
+    }
+}
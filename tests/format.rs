@@ -0,0 +1,39 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{
+    format::{FormatOptions, Quantity},
+    track::Track,
+};
+
+#[test]
+fn formatted_stats_include_the_forces() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 15}, "sections": [
+            {"type": "straight", "length": 10},
+            {"type": "curved", "radius": 20, "angle": 90, "direction": 90}
+        ]}"#,
+    )
+    .unwrap();
+    let stats = track.stats().unwrap();
+    let options = FormatOptions {
+        precision: 2,
+        decimal_separator: ',',
+        ..Default::default()
+    };
+    let formatted = stats.format(&options);
+
+    assert_eq!(
+        formatted.max_vert,
+        options.format(stats.total.max_vert, Quantity::Force)
+    );
+    assert_eq!(
+        formatted.min_vert,
+        options.format(stats.total.min_vert, Quantity::Force)
+    );
+    assert_eq!(
+        formatted.max_lat,
+        options.format(stats.total.max_lat, Quantity::Force)
+    );
+    assert!(formatted.max_lat.ends_with(" g") && formatted.max_lat.contains(','));
+    assert_eq!(options.format(1.234, Quantity::Force), "1,23 g");
+}
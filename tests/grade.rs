@@ -0,0 +1,28 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{
+    grade::{GradeLimit, GradeLimits},
+    track::Track,
+};
+
+#[test]
+fn chain_lift_is_held_to_the_lift_limit() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 3}, "sections": [
+            {"type": "straight", "length": 10, "fixedSpeed": 3},
+            {"type": "lift", "length": 40, "pitch": 40, "chainSpeed": 3}
+        ]}"#,
+    )
+    .unwrap();
+    let report = track.grade_report(1.0, &GradeLimits::default());
+    assert!(report.violations.is_empty(), "{:?}", report.violations);
+
+    let steep = GradeLimits {
+        max_lift_grade: 50.0,
+        ..Default::default()
+    };
+    let report = track.grade_report(1.0, &steep);
+    assert_eq!(report.violations.len(), 1);
+    assert_eq!(report.violations[0].section, 1);
+    assert_eq!(report.violations[0].limit, GradeLimit::Lift);
+}
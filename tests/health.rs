@@ -0,0 +1,29 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{health::HealthCategory, track::Track};
+
+#[test]
+fn continuous_layout_scores_full_continuity() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 20}, "sections": [
+            {"type": "straight", "length": 10},
+            {"type": "curved", "radius": 60, "angle": 90, "direction": 90},
+            {"type": "straight", "length": 10},
+            {"type": "force", "transitions": {
+                "vert": [{"curve": "cubic", "value": 0.5, "length": 1}],
+                "lat": [{"curve": "cubic", "value": 0.3, "length": 1}],
+                "roll": [{"curve": "linear", "value": 20, "length": 1}]
+            }},
+            {"type": "curved", "radius": 40, "angle": 30, "direction": 0}
+        ]}"#,
+    )
+    .unwrap();
+    let report = track.health();
+    let continuity = report
+        .categories
+        .iter()
+        .find(|c| c.category == HealthCategory::Continuity)
+        .unwrap();
+    assert_eq!(continuity.issues, 0);
+    assert_eq!(continuity.score, 100.0);
+}
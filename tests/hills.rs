@@ -0,0 +1,60 @@
+use fvd_rs::{
+    first_drop::DropDesign,
+    glam::DVec3,
+    hills::HillDesign,
+    math::euler,
+    track::{Track, TrackSection},
+};
+
+// Down a drop and pulling back up at 30 degrees
+fn track() -> Track {
+    let mut track = Track::default();
+    track.anchor.pos = DVec3::new(0.0, 50.0, 0.0);
+    track.anchor.velocity = 4.0;
+    let design = DropDesign {
+        height: 45.0,
+        exit_pitch: 30.0,
+        ..Default::default()
+    };
+    let section = track.first_drop(&design).unwrap();
+    track.sections.push(section);
+    track
+}
+
+fn check(track: &Track, section: TrackSection, design: &HillDesign) {
+    let mut track = track.clone();
+    track.sections.push(section);
+    let (spline, _) = track.get_spline();
+    let (_, pitch, roll) = euler(spline.points.last().unwrap());
+    assert!((pitch + 30.0).abs() < 1.0, "{pitch}");
+    assert!(roll.abs() < 1.0, "{roll}");
+
+    let stats = track.stats().unwrap().sections[1].stats;
+    assert!((stats.min_vert - design.vert).abs() < 0.1, "{stats:?}");
+    assert!((stats.max_lat - design.lat).abs() < 0.1, "{stats:?}");
+}
+
+#[test]
+fn outerbank_hill_comes_over() {
+    let track = track();
+    let design = HillDesign::default();
+    check(&track, track.outerbank_hill(&design).unwrap(), &design);
+}
+
+#[test]
+fn s_hill_comes_over() {
+    let track = track();
+    let design = HillDesign {
+        bank: -60.0,
+        vert: 0.0,
+        lat: 0.3,
+        ..Default::default()
+    };
+    check(&track, track.s_hill(&design).unwrap(), &design);
+}
+
+#[test]
+fn hill_needs_a_climb() {
+    let track = Track::default();
+    assert!(track.outerbank_hill(&HillDesign::default()).is_none());
+}
@@ -0,0 +1,51 @@
+use fvd_rs::{
+    error::TrackError,
+    history::Change,
+    track::{Track, TrackSection},
+};
+
+fn straight(length: f64) -> TrackSection {
+    TrackSection::Straight {
+        length,
+        fixed_speed: None,
+    }
+}
+
+#[test]
+fn revisions_check_out_along_a_tree() {
+    let mut track = Track::default();
+    track.anchor.velocity = 10.0;
+    track.sections = vec![straight(10.0), straight(20.0)];
+    let first = track.commit("first", 1.0);
+
+    track.sections.insert(1, straight(5.0));
+    let second = track.commit("insert", 2.0);
+    assert_eq!(
+        track.history.revisions[second].changes,
+        vec![Change::Sections {
+            start: 1,
+            removed: 0,
+            inserted: vec![straight(5.0)],
+        }]
+    );
+
+    // Branch off the first revision
+    track.checkout(first).unwrap();
+    assert_eq!(track.sections, vec![straight(10.0), straight(20.0)]);
+    track.anchor.velocity = 12.0;
+    let branch = track.commit("faster", 3.0);
+    assert_eq!(track.history.revisions[branch].parent, Some(first));
+
+    let inserted = track.revision(second).unwrap();
+    assert_eq!(inserted.sections.len(), 3);
+    assert_eq!(inserted.anchor.velocity, 10.0);
+    assert!(inserted.history.is_empty());
+    let (spline, _) = inserted.try_get_spline().unwrap();
+    assert!((spline.total_distance() - 35.0).abs() < 0.1);
+
+    assert_eq!(track.revision(branch).unwrap().anchor.velocity, 12.0);
+    assert_eq!(
+        track.checkout(7).unwrap_err(),
+        TrackError::MissingRevision { revision: 7 }
+    );
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{
+    compiled::CompiledTrack,
+    constants::G,
+    math::euler,
+    output::{Decimation, OutputOptions},
+    track::Track,
+    validation::EnvelopeLimits,
+};
+
+#[test]
+fn holding_brake_drops_the_train_after_the_dwell() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 5}, "sections": [
+            {"type": "straight", "length": 10, "fixedSpeed": 5},
+            {"type": "holdingBrake", "dwell": 3, "length": 20}
+        ]}"#,
+    )
+    .unwrap();
+    let options = OutputOptions {
+        decimation: Decimation::Every { n: 1 },
+        ..Default::default()
+    };
+    let (spline, _) = track.try_get_spline_with_options(&options).unwrap();
+    let splines = track.make_splines();
+    let drop = &splines[1];
+    let entry = *splines[0].points.last().unwrap();
+    let (held, released, last) = (drop.points[0], drop.points[1], *drop.points.last().unwrap());
+
+    // Held where the train came in for the dwell, then released from rest
+    assert_eq!((held.pos, held.time), (entry.pos, entry.time));
+    assert_eq!(released.pos, entry.pos);
+    assert!((released.time - entry.time - 3.0).abs() < 1e-9);
+    assert_eq!(released.velocity, 0.0);
+    assert!((euler(&held).1 + 90.0).abs() < 1e-6);
+    assert!((euler(&drop.points[2]).1 + 90.0).abs() < 1e-6);
+
+    // Free fall the rest of the way
+    let fall = (2.0 * 20.0 / G).sqrt();
+    assert!((last.time - entry.time - 3.0 - fall).abs() < 0.02);
+    assert!((last.velocity - (2.0 * G * 20.0).sqrt()).abs() < 0.05);
+    assert!((entry.pos.y - last.pos.y - 20.0).abs() < 0.05);
+    assert_eq!(spline.points.last().unwrap().velocity, last.velocity);
+}
+
+#[test]
+fn pitching_onto_the_guide_carries_no_forces() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 5}, "sections": [
+            {"type": "straight", "length": 10, "fixedSpeed": 5},
+            {"type": "holdingBrake", "dwell": 3, "length": 20}
+        ]}"#,
+    )
+    .unwrap();
+    // Level until the brake, weightless in the fall
+    for sample in track.analysis(0.5) {
+        let expected = if sample.distance < 10.0 { 1.0 } else { 0.0 };
+        assert!((sample.vert - expected).abs() < 0.01, "{sample:?}");
+    }
+    let violations = track.check_envelope(&EnvelopeLimits::default());
+    assert!(violations.is_empty(), "{violations:?}");
+}
+
+#[test]
+fn forces_at_a_held_start_are_the_release() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 5}, "sections": [
+            {"type": "holdingBrake", "dwell": 3, "length": 20}
+        ]}"#,
+    )
+    .unwrap();
+    let (spline, _) = track.try_get_spline().unwrap();
+    let at_start = spline.forces(0.0).unwrap();
+    assert!(
+        at_start.vert.is_finite() && at_start.lat.is_finite(),
+        "{at_start:?}"
+    );
+
+    // The held pair has no length, so the start reads the first pair of the drop
+    let after = spline.forces(0.001).unwrap();
+    assert_eq!((at_start.vert, at_start.lat), (after.vert, after.lat));
+    let indexed = spline.index().forces(0.0).unwrap();
+    assert_eq!((indexed.vert, indexed.lat), (after.vert, after.lat));
+    let compiled = CompiledTrack::new(track).forces(0.0).unwrap().unwrap();
+    assert_eq!((compiled.vert, compiled.lat), (after.vert, after.lat));
+}
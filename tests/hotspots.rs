@@ -0,0 +1,43 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{get_hotspots, hotspots::HotspotOptions, track::Track};
+
+#[test]
+fn side_to_side_snaps_rank_first() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 20}, "sections": [
+            {"type": "force", "fixedSpeed": 20, "transitions": {
+                "vert": [{"curve": "linear", "value": 0, "length": 3.5}],
+                "lat": [
+                    {"curve": "linear", "value": 0, "length": 1},
+                    {"curve": "linear", "value": 0.6, "length": 0.1},
+                    {"curve": "linear", "value": 0, "length": 1},
+                    {"curve": "linear", "value": -1.2, "length": 0.25},
+                    {"curve": "linear", "value": 0, "length": 1.15}
+                ],
+                "roll": [{"curve": "linear", "value": 0, "length": 3.5}]
+            }}
+        ]}"#,
+    )
+    .unwrap();
+    let spots = track.hotspots(&HotspotOptions::default());
+
+    assert_eq!(spots.len(), 2, "{spots:?}");
+    let (reversal, snap) = (spots[0], spots[1]);
+    assert!(reversal.lat_reversal && !snap.lat_reversal);
+    assert!(reversal.start > snap.end);
+    // Picked up between samples half a meter apart, so it reads a little over the ramp
+    assert!(reversal.jerk > 4.8 && reversal.jerk < 6.0, "{reversal:?}");
+    assert!(snap.jerk > 6.0 && snap.jerk < 7.5, "{snap:?}");
+    assert!((reversal.duration - 0.25).abs() < 0.02);
+    assert!(reversal.severity > snap.severity);
+
+    let options = HotspotOptions {
+        count: 1,
+        ..Default::default()
+    };
+    assert_eq!(track.hotspots(&options).len(), 1);
+
+    let json = get_hotspots(&serde_json::to_string(&track).unwrap(), "{}").unwrap();
+    assert!(json.contains("\"latReversal\":true"));
+}
@@ -0,0 +1,36 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{track::Track, TrackPoint, TrackSpline};
+
+// The walk eval_closest used to do, kept here as the reference
+fn linear_closest(spline: &TrackSpline, distance: f64) -> Option<(&TrackPoint, &TrackPoint)> {
+    let mut total_dist = 0.0;
+    for i in 1..spline.points.len() {
+        total_dist += (spline.points[i].pos - spline.points[i - 1].pos).length();
+        if total_dist >= distance {
+            return Some((&spline.points[i - 1], &spline.points[i]));
+        }
+    }
+    None
+}
+
+#[test]
+fn index_agrees_with_the_linear_walk() {
+    let track = Track::from_json(include_str!("../week_11.json")).unwrap();
+    let (spline, _) = track.get_spline();
+    let index = spline.index();
+    let length = index.total_distance();
+
+    for i in -5..=1005 {
+        let distance = length * i as f64 / 1000.0;
+        let expected = linear_closest(&spline, distance);
+        assert_eq!(index.eval_closest(distance), expected, "at {distance}");
+        assert_eq!(spline.eval_closest(distance), expected, "at {distance}");
+        assert_eq!(
+            index.forces(distance),
+            spline.forces(distance),
+            "at {distance}"
+        );
+    }
+    assert!(index.forces(length + 1.0).is_none());
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{
+    inverse::FitTolerance,
+    track::{Track, TrackSection},
+};
+
+#[test]
+fn fitted_transitions_replay_the_spline() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 20}, "sections": [
+            {"type": "force", "fixedSpeed": 20, "transitions": {
+                "vert": [
+                    {"curve": "cubic", "value": 2, "length": 1},
+                    {"curve": "cubic", "value": -2, "length": 1}
+                ],
+                "lat": [{"curve": "linear", "value": 0, "length": 2}],
+                "roll": [
+                    {"curve": "cubic", "value": 90, "length": 1},
+                    {"curve": "cubic", "value": -90, "length": 1}
+                ]
+            }}
+        ]}"#,
+    )
+    .unwrap();
+    let (spline, _) = track.get_spline();
+    let tolerance = FitTolerance::default();
+
+    let mut fitted = spline.fit_track(&tolerance);
+    fitted.config = track.config.clone();
+    if let TrackSection::Force {
+        fixed_speed,
+        transitions,
+        ..
+    } = &mut fitted.sections[0]
+    {
+        *fixed_speed = Some(20.0);
+        assert!(transitions.vert.len() < spline.points.len() / 4);
+    }
+    let (replayed, _) = fitted.get_spline();
+
+    let end = spline.points.last().unwrap();
+    let replayed_end = replayed.points.last().unwrap();
+    assert!(
+        (end.pos - replayed_end.pos).length() < 0.5,
+        "{:?} vs {:?}",
+        end.pos,
+        replayed_end.pos
+    );
+    for (time, forces) in replayed.sampled_forces().iter().step_by(10) {
+        let original = spline
+            .sampled_forces()
+            .into_iter()
+            .min_by(|a, b| (a.0 - time).abs().total_cmp(&(b.0 - time).abs()))
+            .unwrap()
+            .1;
+        assert!((forces.vert - original.vert).abs() < 2.0 * tolerance.force);
+        assert!((forces.roll - original.roll).abs() < 2.0 * tolerance.roll);
+    }
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{
+    glam::DVec3,
+    join::{JoinOptions, JointError},
+    track::Track,
+    TrackSpline,
+};
+
+fn sections() -> Vec<TrackSpline> {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 10}, "sections": [
+            {"type": "straight", "length": 20, "fixedSpeed": 10},
+            {"type": "straight", "length": 30, "fixedSpeed": 10}
+        ]}"#,
+    )
+    .unwrap();
+    let mut splines = track.make_splines();
+    for spline in &mut splines {
+        spline.fill_distance();
+    }
+    splines
+}
+
+#[test]
+fn time_and_distance_carry_on_across_joints() {
+    let splines = sections();
+    let joined = TrackSpline::concat(&splines);
+    let mut whole = joined.clone();
+    whole.fill_distance();
+
+    for (point, expected) in joined.points.iter().zip(&whole.points) {
+        let (distance, expected) = (point.distance.unwrap(), expected.distance.unwrap());
+        assert!((distance - expected).abs() < 1e-9, "{distance} {expected}");
+    }
+    let last = joined.points.last().unwrap();
+    assert!((last.distance.unwrap() - 50.0).abs() < 0.05);
+    assert!((last.time - 5.0).abs() < 0.01);
+}
+
+#[test]
+fn gaps_are_rejected_or_blended() {
+    let mut splines = sections();
+    for point in &mut splines[1].points {
+        point.pos += DVec3::Z * 0.5;
+    }
+
+    let err = TrackSpline::concat_checked(&splines, &JoinOptions::default()).unwrap_err();
+    let JointError { index, gap, .. } = err;
+    assert_eq!(index, 1);
+    // Plus the step the next section starts on
+    assert!((gap - 0.51).abs() < 1e-6, "{gap}");
+
+    let options = JoinOptions {
+        max_gap: 1.0,
+        blend: true,
+        ..Default::default()
+    };
+    let joined = TrackSpline::concat_checked(&splines, &options).unwrap();
+    // The gap is filled in every centimeter, distance and time keep climbing through it
+    let spacing = joined
+        .points
+        .windows(2)
+        .map(|w| (w[1].pos - w[0].pos).length())
+        .fold(0.0, f64::max);
+    assert!(spacing < 0.02, "{spacing}");
+    assert!(joined
+        .points
+        .windows(2)
+        .all(|w| { w[1].distance.unwrap() > w[0].distance.unwrap() && w[1].time >= w[0].time }));
+    let last = joined.points.last().unwrap();
+    assert!((last.distance.unwrap() - 50.5).abs() < 0.05);
+}
@@ -0,0 +1,30 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{launch::HillClearance, track::Track};
+
+#[test]
+fn solved_launch_clears_hill_with_margin() {
+    let mut track = Track::from_json(
+        r#"{"anchor": {"velocity": 2}, "config": {"parameter": 0.027, "resistance": 1e-5}, "sections": [
+            {"type": "launch", "length": 40, "targetSpeed": 10},
+            {"type": "curved", "radius": 20, "angle": 90, "direction": 0}
+        ]}"#,
+    )
+    .unwrap();
+    let clearance = HillClearance {
+        height: 20.0,
+        distance: 20.0 * std::f64::consts::FRAC_PI_2,
+        margin: 8.0,
+    };
+    let solution = track.solve_launch(0, &clearance).unwrap();
+    assert!(track.solve_launch(1, &clearance).is_none());
+
+    let splines = track.make_splines();
+    let exit = splines[0].points.last().unwrap().velocity;
+    let crest = splines[1].points.last().unwrap().velocity;
+    assert!(
+        (exit - solution.exit_speed).abs() < 0.05,
+        "{solution:?} {exit}"
+    );
+    assert!((crest - clearance.margin).abs() < 0.5, "{crest}");
+}
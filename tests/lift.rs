@@ -0,0 +1,46 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::track::Track;
+
+#[test]
+fn cable_lift_accelerates_then_releases() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 2}, "sections": [
+            {"type": "lift", "length": 200, "pitch": 30, "chainSpeed": 20, "drive": {
+                "type": "cable", "acceleration": 4, "deceleration": 2, "releaseSpeed": 8
+            }}
+        ]}"#,
+    )
+    .unwrap();
+    let points = &track.make_splines()[0].points;
+    let top = points.iter().map(|p| p.velocity).fold(0.0, f64::max);
+    let crest = points.last().unwrap().velocity;
+    let early = points[points.len() / 20].velocity;
+
+    // 2 m/s + 4 m/s^2 over 10 m, held at the cable's top speed before slowing to release
+    assert!(
+        (early - (4.0 + 2.0 * 4.0 * 10.0_f64).sqrt()).abs() < 0.1,
+        "{early}"
+    );
+    assert!((top - 20.0).abs() < 1e-9);
+    assert!((crest - 8.0).abs() < 0.1, "{crest}");
+}
+
+#[test]
+fn rollback_ticks_follow_the_lift() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 3}, "sections": [
+            {"type": "straight", "length": 10},
+            {"type": "lift", "length": 30, "pitch": 35, "chainSpeed": 3}
+        ]}"#,
+    )
+    .unwrap();
+    let ticks = track.anti_rollback_ticks(0.5);
+
+    assert!((58..=60).contains(&ticks.len()), "{}", ticks.len());
+    assert!(ticks.iter().all(|t| t.section == 1));
+    assert!(ticks
+        .windows(2)
+        .all(|w| { (w[1].distance - w[0].distance - 0.5).abs() < 1e-9 && w[1].time > w[0].time }));
+    assert!(ticks[0].distance > 10.0 - 0.05);
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{
+    live::LiveSpline,
+    output::{Decimation, OutputOptions},
+    simplify::SimplifyTolerance,
+    stats::SplineStats,
+    track::Track,
+};
+
+fn hills() -> Track {
+    Track::from_json(
+        r#"{"anchor": {"velocity": 20}, "sections": [
+            {"type": "force", "fixedSpeed": 20, "transitions": {
+                "vert": [
+                    {"curve": "cubic", "value": -1.5, "length": 1},
+                    {"curve": "cubic", "value": 1.5, "length": 1},
+                    {"curve": "cubic", "value": -1.5, "length": 1},
+                    {"curve": "cubic", "value": 1.5, "length": 1}
+                ],
+                "lat": [{"curve": "linear", "value": 0, "length": 4}],
+                "roll": [{"curve": "linear", "value": 0, "length": 4}]
+            }}
+        ]}"#,
+    )
+    .unwrap()
+}
+
+fn assert_same(a: SplineStats, b: SplineStats) {
+    assert!((a.length - b.length).abs() < 1e-6, "{a:?} vs {b:?}");
+    assert!((a.duration - b.duration).abs() < 1e-9, "{a:?} vs {b:?}");
+    assert_eq!(a.max_speed, b.max_speed);
+    assert_eq!(a.min_speed, b.min_speed);
+    assert_eq!(a.max_vert, b.max_vert);
+    assert_eq!(a.min_vert, b.min_vert);
+    assert_eq!(a.lowest, b.lowest);
+    assert_eq!(a.highest, b.highest);
+}
+
+#[test]
+fn stats_follow_edits() {
+    let options = OutputOptions {
+        decimation: Decimation::Every { n: 1 },
+        ..Default::default()
+    };
+    let (spline, _) = hills().try_get_spline_with_options(&options).unwrap();
+    assert!(spline.points.len() > 3000);
+    let mut live = LiveSpline::new(spline.clone());
+
+    let stats = live.stats();
+    assert_same(stats.stats, spline.stats());
+    assert_eq!(stats.air_segments, 2);
+    assert!(stats.airtime > 0.5 && stats.airtime < 2.0);
+
+    // Cut the second hill off, then thin out the first
+    let half = spline.points.len() / 2;
+    live.slice(0..half);
+    assert_eq!(live.len(), half);
+    assert_eq!(live.stats().air_segments, 1);
+    live.simplify(100..half - 100, &SimplifyTolerance::default());
+    assert!(live.len() < half);
+
+    let edited = live.to_spline();
+    let fresh = LiveSpline::new(edited.clone()).stats();
+    let stats = live.stats();
+    assert_same(stats.stats, edited.stats());
+    assert_eq!(stats.air_segments, fresh.air_segments);
+    assert!((stats.airtime - fresh.airtime).abs() < 1e-9);
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{mesh::MeshOptions, track::Track};
+
+fn word(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+}
+
+#[test]
+fn glb_is_well_formed() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 10}, "sections": [
+            {"type": "curved", "radius": 20, "angle": 90, "direction": 90, "fixedSpeed": 10}
+        ]}"#,
+    )
+    .unwrap();
+    let options = MeshOptions::default();
+    let mesh = track.mesh(&options);
+    let rings = mesh.positions.len() / (3 * options.sides as usize);
+    assert_eq!(mesh.positions.len(), mesh.normals.len());
+    assert_eq!(
+        mesh.indices.len(),
+        3 * (rings - 1) * options.sides as usize * 6
+    );
+    assert!(mesh
+        .indices
+        .iter()
+        .all(|&i| (i as usize) < mesh.positions.len()));
+
+    let mut glb = Vec::new();
+    mesh.write_glb(&mut glb).unwrap();
+    assert_eq!(&glb[0..4], b"glTF");
+    assert_eq!(word(&glb, 4), 2);
+    assert_eq!(word(&glb, 8) as usize, glb.len());
+
+    let json_length = word(&glb, 12) as usize;
+    assert_eq!(&glb[16..20], b"JSON");
+    let document: serde_json::Value = serde_json::from_slice(&glb[20..20 + json_length]).unwrap();
+    let bin_length = word(&glb, 20 + json_length) as usize;
+    assert_eq!(&glb[24 + json_length..28 + json_length], b"BIN\0");
+    assert_eq!(28 + json_length + bin_length, glb.len());
+    assert_eq!(
+        document["buffers"][0]["byteLength"].as_u64().unwrap() as usize,
+        24 * mesh.positions.len() + 4 * mesh.indices.len()
+    );
+}
+
+#[test]
+fn obj_and_ply_list_every_vertex_and_face() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 10}, "sections": [
+            {"type": "straight", "length": 5, "fixedSpeed": 10}
+        ]}"#,
+    )
+    .unwrap();
+    let mesh = track.mesh(&MeshOptions::default());
+    let (vertices, faces) = (mesh.positions.len(), mesh.indices.len() / 3);
+
+    let mut obj = Vec::new();
+    mesh.write_obj(&mut obj).unwrap();
+    let obj = String::from_utf8(obj).unwrap();
+    assert_eq!(
+        obj.lines().filter(|l| l.starts_with("v ")).count(),
+        vertices
+    );
+    assert_eq!(obj.lines().filter(|l| l.starts_with("f ")).count(), faces);
+
+    let mut ply = Vec::new();
+    mesh.write_ply(&mut ply).unwrap();
+    let ply = String::from_utf8(ply).unwrap();
+    let body: Vec<&str> = ply.split("end_header\n").nth(1).unwrap().lines().collect();
+    assert!(ply.contains(&format!("element vertex {vertices}\n")));
+    assert_eq!(body.len(), vertices + faces);
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{
+    nl2::{Nl2CsvOptions, Nl2Friction, Nl2ImportOptions},
+    track::Track,
+    TrackSpline,
+};
+
+#[test]
+fn csv_has_one_frame_per_spacing() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 10}, "sections": [
+            {"type": "straight", "length": 10, "fixedSpeed": 10}
+        ]}"#,
+    )
+    .unwrap();
+    let (spline, _) = track.get_spline();
+    let mut csv = Vec::new();
+    spline
+        .write_nl2_csv(&Nl2CsvOptions { spacing: 2.5 }, &mut csv)
+        .unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert!(lines[0].starts_with("\"No.\"\t\"PosX\""));
+    assert_eq!(lines.len(), 6);
+    let last: Vec<f64> = lines[5].split('\t').map(|v| v.parse().unwrap()).collect();
+    assert_eq!(last.len(), 13);
+    assert_eq!(last[0], 5.0);
+    // Front along +z, left along +x, up along +y
+    assert!((last[3] - spline.points.last().unwrap().pos.z).abs() < 1e-9);
+    assert!((last[6] - 1.0).abs() < 1e-9);
+    assert!((last[7] - 1.0).abs() < 1e-9);
+    assert!((last[11] - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn imported_csv_keeps_forces() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 10}, "sections": [
+            {"type": "curved", "radius": 20, "angle": 90, "direction": 90, "fixedSpeed": 10}
+        ]}"#,
+    )
+    .unwrap();
+    let (spline, _) = track.get_spline();
+    let mut csv = Vec::new();
+    spline
+        .write_nl2_csv(&Nl2CsvOptions { spacing: 0.5 }, &mut csv)
+        .unwrap();
+
+    let options = Nl2ImportOptions {
+        friction: Nl2Friction {
+            parameter: 0.0,
+            resistance: 0.0,
+        },
+        ..Default::default()
+    };
+    let imported = TrackSpline::read_nl2_csv(csv.as_slice(), &options).unwrap();
+    let last = imported.points.last().unwrap();
+    assert!((last.pos - spline.points.last().unwrap().pos).length() < 1e-6);
+    assert!((last.velocity - 10.0).abs() < 1e-6);
+
+    let mid = imported.forces(15.0).unwrap();
+    let expected = spline.forces(15.0).unwrap();
+    assert!(
+        (mid.lat - expected.lat).abs() < 0.02,
+        "{mid:?} vs {expected:?}"
+    );
+    assert!(
+        (mid.vert - expected.vert).abs() < 0.02,
+        "{mid:?} vs {expected:?}"
+    );
+}
+
+#[test]
+fn import_reports_missing_columns() {
+    let csv = "\"No.\"\t\"PosX\"\t\"PosY\"\n1\t0\t0\n";
+    let err = TrackSpline::read_nl2_csv(csv.as_bytes(), &Nl2ImportOptions::default()).unwrap_err();
+    assert!(err.to_string().contains("PosZ"));
+}
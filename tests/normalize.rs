@@ -0,0 +1,48 @@
+use fvd_rs::{
+    normalize::{NormalizeOptions, RideFraction},
+    track::{Track, TrackSection},
+    TrackSpline,
+};
+
+fn straight(length: f64, speed: f64) -> TrackSpline {
+    let mut track = Track::default();
+    track.anchor.velocity = speed;
+    track.sections.push(TrackSection::Straight {
+        length,
+        fixed_speed: Some(speed),
+    });
+    track.get_spline().0
+}
+
+#[test]
+fn rides_line_up_by_fraction() {
+    let splines = [straight(50.0, 10.0), straight(100.0, 25.0)];
+    let options = NormalizeOptions {
+        samples: 11,
+        ..Default::default()
+    };
+    let comparison = TrackSpline::compare(&splines, &options);
+    assert_eq!(comparison.fractions.len(), 11);
+    assert_eq!(comparison.fractions[5], 0.5);
+    for (ride, length) in comparison.rides.iter().zip([50.0, 100.0]) {
+        assert_eq!(ride.len(), 11);
+        for (sample, fraction) in ride.iter().zip(&comparison.fractions) {
+            assert!(
+                (sample.distance - fraction * length).abs() < 0.2,
+                "{sample:?}"
+            );
+        }
+    }
+
+    let by_time = splines[1].normalized(&NormalizeOptions {
+        by: RideFraction::Time,
+        samples: 5,
+    });
+    let start = by_time[0].time;
+    let duration = by_time.last().unwrap().time - start;
+    assert!((duration - 4.0).abs() < 0.05);
+    for (i, sample) in by_time.iter().enumerate() {
+        assert!((sample.time - start - i as f64 * duration / 4.0).abs() < 1e-6);
+        assert!((sample.speed - 25.0).abs() < 1e-6);
+    }
+}
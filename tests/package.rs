@@ -0,0 +1,42 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{
+    error::TrackError,
+    package::{Package, PackageMetadata, PACKAGE_FORMAT_VERSION},
+    track::Track,
+};
+
+#[test]
+fn only_tracks_that_simulate_are_packaged() {
+    let empty = Track::from_json("{}").unwrap();
+    assert_eq!(
+        Package::new(empty, PackageMetadata::default()).unwrap_err(),
+        TrackError::EmptySections
+    );
+
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 10}, "sections": [{"type": "straight", "length": 20}]}"#,
+    )
+    .unwrap();
+    let stats = track.stats().unwrap();
+    let package = Package::new(track, PackageMetadata::default()).unwrap();
+    assert_eq!(package.format_version, PACKAGE_FORMAT_VERSION);
+    assert_eq!(package.stats.total.length, stats.total.length);
+}
+
+#[test]
+fn old_packages_are_read_as_current() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 10}, "sections": [{"type": "straight", "length": 20}]}"#,
+    )
+    .unwrap();
+    let mut package = Package::new(track, PackageMetadata::default()).unwrap();
+    package.format_version = 1;
+    package.stats = Default::default();
+    let mut bytes = Vec::new();
+    package.write(&mut bytes).unwrap();
+
+    let read = Package::read(bytes.as_slice()).unwrap();
+    assert_eq!(read.format_version, PACKAGE_FORMAT_VERSION);
+    assert!((read.stats.total.length - 20.0).abs() < 0.1);
+}
@@ -0,0 +1,19 @@
+use fvd_rs::prelude::*;
+
+#[test]
+fn prelude_builds_a_track() {
+    let track = Track {
+        sections: vec![TrackSection::Straight {
+            length: 10.0,
+            fixed_speed: Some(10.0),
+        }],
+        ..Track::default()
+    };
+    let (spline, _): (TrackSpline, _) = track.try_get_spline().unwrap();
+    let end: DVec3 = spline.points.last().unwrap().pos;
+    assert!(end.z > 9.9);
+
+    // The re-exported glam is the one the crate's types use
+    let rot: fvd_rs::glam::DQuat = DQuat::IDENTITY;
+    assert_eq!(rot * DVec3::Z, DVec3::Z);
+}
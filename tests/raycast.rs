@@ -0,0 +1,53 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{glam::DVec3, track::Track, TrackSpline};
+
+fn splines() -> TrackSpline {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 15}, "sections": [
+            {"type": "straight", "length": 200, "fixedSpeed": 15},
+            {"type": "curved", "radius": 20, "angle": 180, "direction": 90, "fixedSpeed": 15},
+            {"type": "straight", "length": 200, "fixedSpeed": 15}
+        ]}"#,
+    )
+    .unwrap();
+    TrackSpline::concat(&track.make_splines())
+}
+
+#[test]
+fn ray_picks_the_point_under_it() {
+    let spline = splines();
+    let target = spline.evaluate(150.0).unwrap();
+    let origin = target.pos + DVec3::Y * 10.0;
+
+    let hit = spline.raycast(origin, -DVec3::Y, 0.5).unwrap();
+    assert!((hit.point.pos - target.pos).length() < 0.5);
+    assert!((hit.ray_distance - 10.0).abs() < 0.5);
+    let (a, b) = (&spline.points[hit.index], &spline.points[hit.index + 1]);
+    assert!((a.pos - target.pos).length() < 1.0 && (b.pos - target.pos).length() < 1.0);
+
+    // Pointing away from the track or past it misses
+    assert!(spline.raycast(origin, DVec3::Y, 0.5).is_none());
+    assert!(spline
+        .raycast(origin + DVec3::X * 100.0, -DVec3::Y, 0.5)
+        .is_none());
+}
+
+#[test]
+fn ray_through_both_legs_picks_the_nearer() {
+    let spline = splines();
+    let near = spline.evaluate(100.0).unwrap().pos;
+    let far = spline
+        .points
+        .iter()
+        .map(|p| p.pos)
+        .filter(|p| (p.x - near.x).abs() > 10.0)
+        .min_by(|a, b| (a.z - near.z).abs().total_cmp(&(b.z - near.z).abs()))
+        .unwrap();
+    // From outside the turnaround, across the near leg and on to the far one
+    let dir = far - near;
+    let origin = near - dir;
+
+    let hit = spline.raycast(origin, dir, 0.5).unwrap();
+    assert!((hit.point.pos - near).length() < 1.0, "{:?}", hit.point.pos);
+}
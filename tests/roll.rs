@@ -0,0 +1,39 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::track::Track;
+
+fn heartline_roll(speed: f64) -> Track {
+    Track::from_json(&format!(
+        r#"{{"anchor": {{"velocity": {speed}}}, "sections": [
+            {{"type": "straight", "length": 10}},
+            {{"type": "roll", "length": 20, "angle": 180}}
+        ]}}"#
+    ))
+    .unwrap()
+}
+
+#[test]
+fn roll_ends_at_the_bank_whatever_the_speed() {
+    for speed in [5.0, 20.0] {
+        let track = heartline_roll(speed);
+        let splines = track.make_splines();
+        let banks = splines[1].bank_angles();
+        assert!((banks.last().unwrap().abs() - 180.0).abs() < 0.1);
+
+        // Half way along is half way round
+        let middle = banks[banks.len() / 2].abs();
+        assert!((middle - 90.0).abs() < 2.0, "{middle}");
+
+        // The heartline carries straight on
+        let (first, last) = (splines[1].points[0], *splines[1].points.last().unwrap());
+        assert!((last.pos - first.pos).length() > 19.5);
+        assert!((last.pos.x - first.pos.x).abs() < 1e-9 && (last.pos.y - first.pos.y).abs() < 1e-9);
+    }
+
+    // The roll rate scales with speed instead
+    let rate = |speed| {
+        let samples = heartline_roll(speed).analysis(1.0);
+        samples[20].roll_rate.abs()
+    };
+    assert!((rate(20.0) / rate(5.0) - 4.0).abs() < 0.1);
+}
@@ -0,0 +1,81 @@
+use fvd_rs::{
+    simulation::SimulationSettings,
+    track::{Track, TrackConfig, TrackSection},
+};
+
+// Curves up to vertical and climbs higher than the train has the speed for
+fn track(rollback: bool) -> Track {
+    let mut track = Track {
+        config: TrackConfig::new(0.02, 0.0, 1.1),
+        ..Default::default()
+    };
+    track.anchor.velocity = 15.0;
+    track.config.set_simulation(SimulationSettings {
+        rollback,
+        ..Default::default()
+    });
+    track.sections = vec![
+        TrackSection::Straight {
+            length: 10.0,
+            fixed_speed: None,
+        },
+        TrackSection::Curved {
+            fixed_speed: None,
+            radius: 8.0,
+            direction: 0.0,
+            angle: 90.0,
+            banking: None,
+            spiral: 0.0,
+        },
+        TrackSection::Straight {
+            length: 20.0,
+            fixed_speed: None,
+        },
+    ];
+    track
+}
+
+#[test]
+fn stalled_train_rolls_back_out_of_the_start() {
+    assert!(track(false).try_get_spline().is_err());
+
+    let (spline, _) = track(true).try_get_spline().unwrap();
+    let apex = spline.points.iter().position(|p| p.velocity < 0.0).unwrap();
+    assert!(spline.points[..apex].iter().all(|p| p.velocity >= 0.0));
+    assert!(spline.points[apex..].iter().all(|p| p.velocity < 0.0));
+
+    // Back past where it started at the speed it started with, less what friction took
+    let last = spline.points.last().unwrap();
+    assert!(last.pos.length() < 0.1, "{:?}", last.pos);
+    assert!(
+        -last.velocity > 10.0 && -last.velocity < 15.0,
+        "{}",
+        last.velocity
+    );
+}
+
+#[test]
+fn rolled_back_train_stops_short_of_a_higher_start() {
+    let mut track = track(true);
+    track.anchor.velocity = 1.0;
+    track.sections.splice(
+        0..0,
+        [180.0, 0.0].map(|direction| TrackSection::Curved {
+            fixed_speed: None,
+            radius: 30.0,
+            direction,
+            angle: 40.0,
+            banking: None,
+            spiral: 0.0,
+        }),
+    );
+
+    let (spline, _) = track.try_get_spline().unwrap();
+    let last = spline.points.last().unwrap();
+    assert!(
+        last.velocity < 0.0 && last.velocity > -2.0,
+        "{}",
+        last.velocity
+    );
+    assert!(last.pos.z > 1.0 && last.pos.y < 0.0, "{:?}", last.pos);
+}
@@ -0,0 +1,83 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{
+    track::Track,
+    validation::{SanityKind, SanityLimits},
+    TrackSpline,
+};
+
+fn launch() -> TrackSpline {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 2}, "sections": [
+            {"type": "launch", "length": 40, "targetSpeed": 30},
+            {"type": "straight", "length": 20}
+        ]}"#,
+    )
+    .unwrap();
+    TrackSpline::concat(&track.make_splines())
+}
+
+#[test]
+fn defaults_accept_a_normal_launch() {
+    assert!(launch().sanity_check(&SanityLimits::default()).is_empty());
+}
+
+#[test]
+fn ranges_cover_where_the_limits_are_exceeded() {
+    let spline = launch();
+    let limits = SanityLimits {
+        max_speed: 20.0,
+        max_acceleration: 8.0,
+    };
+    let issues = spline.sanity_check(&limits);
+    let speed: Vec<_> = issues
+        .iter()
+        .filter(|i| i.kind == SanityKind::Speed)
+        .collect();
+    let accel: Vec<_> = issues
+        .iter()
+        .filter(|i| i.kind == SanityKind::Acceleration)
+        .collect();
+    assert_eq!((speed.len(), accel.len()), (1, 1), "{issues:?}");
+
+    // From the first point over 20 m/s to the end of the track, peaking at the top speed
+    let distances = spline.index().distances().to_vec();
+    let first = spline
+        .points
+        .iter()
+        .position(|p| p.velocity > 20.0)
+        .unwrap();
+    let top = spline.points.iter().map(|p| p.velocity).fold(0.0, f64::max);
+    assert!(
+        (speed[0].start - distances[first]).abs() < 1e-9,
+        "{:?}",
+        speed[0]
+    );
+    assert!((speed[0].end - spline.total_distance()).abs() < 1e-9);
+    assert_eq!(speed[0].peak, top);
+
+    // Through the launch only, not the straight after it
+    assert!(
+        accel[0].start < 1.0 && accel[0].end <= 40.0 + 0.1,
+        "{:?}",
+        accel[0]
+    );
+    assert!(accel[0].end > 30.0 && accel[0].peak > 8.0);
+    assert!(accel[0].start_time < accel[0].end_time);
+}
+
+#[test]
+fn non_finite_points_are_flagged() {
+    let mut spline = launch();
+    let i = spline.points.len() / 2;
+    spline.points[i].velocity = f64::NAN;
+
+    let issues = spline.sanity_check(&SanityLimits::default());
+    let bad: Vec<_> = issues
+        .iter()
+        .filter(|i| i.kind == SanityKind::NonFinite)
+        .collect();
+    assert_eq!(bad.len(), 1, "{issues:?}");
+    let time = spline.points[i].time;
+    assert!(bad[0].start_time <= time && time <= bad[0].end_time);
+}
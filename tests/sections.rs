@@ -0,0 +1,53 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{
+    output::{Decimation, OutputOptions},
+    track::Track,
+};
+
+#[test]
+fn boundaries_survive_decimation() {
+    let track = Track::from_json(include_str!("../week_11.json")).unwrap();
+    let options = OutputOptions {
+        decimation: Decimation::Every { n: 7 },
+        ..OutputOptions::default()
+    };
+    let result = track.try_get_spline_with_sections(&options).unwrap();
+    assert_eq!(result.section.len(), result.spline.points.len());
+    assert_eq!(result.parameter.len(), result.spline.points.len());
+
+    let splines = track.make_splines();
+    for (i, spline) in splines.iter().enumerate() {
+        let (Some(first), Some(last)) = (spline.points.first(), spline.points.last()) else {
+            continue;
+        };
+        let kept: Vec<usize> = (0..result.section.len())
+            .filter(|&j| result.section[j] == i)
+            .collect();
+        let (start, end) = (kept[0], *kept.last().unwrap());
+        assert_eq!(result.spline.points[start].pos, first.pos);
+        assert_eq!(result.spline.points[end].pos, last.pos);
+        assert_eq!(result.parameter[start], 0.0);
+        assert!((result.parameter[end] - 1.0).abs() < 1e-12 || spline.points.len() == 1);
+        assert!(kept.windows(2).all(|w| w[1] == w[0] + 1));
+    }
+}
+
+#[test]
+fn hashes_change_from_the_edited_section_on() {
+    let track = Track::from_json(include_str!("../week_11.json")).unwrap();
+    let hashes = track.section_hashes();
+    assert_eq!(hashes, track.section_hashes());
+
+    let edited = 2;
+    let mut changed = track.clone();
+    changed
+        .sections
+        .insert(edited, changed.sections[edited].clone());
+    let changed = changed.section_hashes();
+    assert_eq!(hashes[..edited], changed[..edited]);
+    assert!(hashes[edited + 1..]
+        .iter()
+        .zip(&changed[edited + 2..])
+        .all(|(a, b)| a != b));
+}
@@ -0,0 +1,104 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{
+    preset::ConfigPreset,
+    track::{Track, TrackSection},
+};
+use serde_json::{json, Value};
+
+fn round_trip(value: Value) -> Value {
+    let track: Track = serde_json::from_value(value).unwrap();
+    let first = serde_json::to_value(&track).unwrap();
+    let track: Track = serde_json::from_value(first.clone()).unwrap();
+    let second = serde_json::to_value(&track).unwrap();
+    assert_eq!(first, second);
+    second
+}
+
+#[test]
+fn empty_document_uses_defaults() {
+    let value = round_trip(json!({}));
+    let track: Track = serde_json::from_value(value.clone()).unwrap();
+
+    assert_eq!(value["sections"], json!([]));
+    assert_eq!(value["config"], json!({}));
+    assert_eq!(track.config.parameter(), 0.0);
+    assert_eq!(track.config.heartline_height(), 0.0);
+    assert_eq!(value["anchor"]["rot"], json!([1.0, 0.0, 0.0, 0.0]));
+    assert_eq!(value["anchor"]["velocity"], json!(0.0));
+}
+
+#[test]
+fn partial_config_and_anchor() {
+    let value = round_trip(json!({
+        "config": { "parameter": 0.03 },
+        "anchor": { "pos": [0, 4, 0], "velocity": 10 },
+    }));
+
+    let track: Track = serde_json::from_value(value.clone()).unwrap();
+
+    assert_eq!(value["config"]["parameter"], json!(0.03));
+    assert_eq!(track.config.resistance(), 0.0);
+    assert_eq!(value["anchor"]["pos"], json!([0.0, 4.0, 0.0]));
+    assert_eq!(value["anchor"]["time"], json!(0.0));
+}
+
+#[test]
+fn preset_with_override() {
+    let value = round_trip(json!({
+        "config": { "preset": "wooden", "heartlineHeight": 1.3 },
+    }));
+    let track: Track = serde_json::from_value(value.clone()).unwrap();
+    let preset = ConfigPreset::Wooden.values();
+
+    assert_eq!(
+        value["config"],
+        json!({ "preset": "wooden", "heartlineHeight": 1.3 })
+    );
+    assert_eq!(track.config.parameter(), preset.parameter);
+    assert_eq!(track.config.heartline_height(), 1.3);
+    assert_eq!(track.config.limits(), Some(preset.limits));
+}
+
+#[test]
+fn sections_without_optional_fields() {
+    let track: Track = serde_json::from_value(json!({
+        "sections": [
+            { "type": "straight", "length": 10 },
+            { "type": "curved", "radius": 12, "direction": 0, "angle": 45 },
+            {
+                "type": "force",
+                "transitions": {
+                    "vert": [{ "curve": "cubic", "value": 1, "length": 2 }],
+                    "lat": [{ "curve": "linear", "value": 0, "length": 2 }],
+                    "roll": [{ "curve": "linear", "value": 0, "length": 2 }],
+                },
+            },
+            { "type": "holdingBrake", "length": 5 },
+        ],
+    }))
+    .unwrap();
+
+    match &track.sections[0] {
+        TrackSection::Straight { fixed_speed, .. } => assert!(fixed_speed.is_none()),
+        _ => panic!("expected straight section"),
+    }
+    match &track.sections[2] {
+        TrackSection::Force { transitions, .. } => {
+            assert_eq!(transitions.vert[0].center, 0.0);
+            assert_eq!(transitions.vert[0].tension, 0.0);
+        }
+        _ => panic!("expected force section"),
+    }
+    match &track.sections[3] {
+        TrackSection::HoldingBrake { dwell, .. } => assert_eq!(*dwell, 0.0),
+        _ => panic!("expected holding brake section"),
+    }
+
+    round_trip(serde_json::to_value(&track).unwrap());
+}
+
+#[test]
+fn bundled_track_round_trips() {
+    round_trip(serde_json::from_str(include_str!("../week_11.json")).unwrap());
+}
@@ -0,0 +1,25 @@
+#![cfg(feature = "server")]
+
+use fvd_rs::{server::check_budget, track::Track};
+
+fn force_track(integrator: &str) -> Track {
+    Track::from_json(&format!(
+        r#"{{"anchor": {{"velocity": 15}}, "config": {{"simulation": {{"integrator": {integrator}}}}}, "sections": [
+            {{"type": "force", "fixedSpeed": 15, "transitions": {{
+                "vert": [{{"curve": "linear", "value": 1, "length": 100}}],
+                "lat": [{{"curve": "linear", "value": 0, "length": 100}}],
+                "roll": [{{"curve": "linear", "value": 0, "length": 100}}]
+            }}}}
+        ]}}"#
+    ))
+    .unwrap()
+}
+
+#[test]
+fn adaptive_steps_count_at_their_finest() {
+    // 100 seconds is 100k steps at a fixed step, but an adaptive step can shrink 64 times
+    assert!(check_budget(&force_track(r#"{"type": "rk4"}"#)).is_ok());
+    let (status, message) =
+        check_budget(&force_track(r#"{"type": "adaptive", "tolerance": 1e-12}"#)).unwrap_err();
+    assert_eq!(status.as_u16(), 413, "{message}");
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{shuttle::SwingLaunch, track::Track};
+
+// A valley with a flat launch track in the middle, 60 m deep behind it and 45 m tall
+// ahead of it
+fn valley() -> Track {
+    Track::from_json(
+        r#"{"anchor": {"velocity": 30}, "sections": [
+            {"type": "straight", "length": 2},
+            {"type": "curved", "radius": 15, "angle": 90, "direction": 180},
+            {"type": "straight", "length": 30},
+            {"type": "curved", "radius": 15, "angle": 90, "direction": 0},
+            {"type": "straight", "length": 40},
+            {"type": "curved", "radius": 15, "angle": 90, "direction": 0},
+            {"type": "straight", "length": 30}
+        ]}"#,
+    )
+    .unwrap()
+}
+
+// Half way along the flat
+const LAUNCH: f64 = 52.0 + 15.0 * std::f64::consts::PI;
+
+#[test]
+fn passes_alternate_direction_through_the_launch() {
+    let track = valley();
+    let swing = SwingLaunch {
+        launch_distance: LAUNCH,
+        passes: vec![15.0, 18.0],
+    };
+    let run = swing.simulate(&track);
+    assert_eq!(run.completed_passes, 2);
+    assert_eq!(run.pass_start_times.len(), 2);
+    let (first, second) = (run.pass_start_times[0], run.pass_start_times[1]);
+    assert!(second > first);
+
+    // Out forwards and back, carrying on backwards through the launch and back again
+    let signs: Vec<bool> = run.spline.points.iter().map(|p| p.velocity > 0.0).collect();
+    let flips = signs.windows(2).filter(|w| w[0] != w[1]).count();
+    assert_eq!(flips, 2);
+    assert!(signs[0]);
+    let at_second = run
+        .spline
+        .points
+        .iter()
+        .position(|p| p.time > second)
+        .unwrap();
+    assert!(!signs[at_second]);
+    // Passing back through the launch starts the next pass
+    let end_of_first = &run.spline.points[at_second - 1];
+    assert!((end_of_first.time - second).abs() < 1e-9);
+}
+
+#[test]
+fn running_off_the_end_is_reported() {
+    let swing = SwingLaunch {
+        launch_distance: LAUNCH,
+        // Clears the back of the valley on the second pass
+        passes: vec![15.0, 40.0, 15.0],
+    };
+    let run = swing.simulate(&valley());
+    assert_eq!(run.pass_start_times.len(), 2);
+    assert_eq!(run.completed_passes, 1);
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{simplify::SimplifyTolerance, track::Track, TrackSpline};
+
+#[test]
+fn dropped_points_stay_within_tolerance() {
+    let track = Track::from_json(include_str!("../week_11.json")).unwrap();
+    let spline = TrackSpline::concat(&track.make_splines());
+    let tolerance = SimplifyTolerance::default();
+    let simple = spline.simplify(&tolerance);
+    assert!(simple.points.len() * 10 < spline.points.len());
+    assert_eq!(simple.points[0].pos, spline.points[0].pos);
+    assert_eq!(
+        simple.points.last().unwrap().pos,
+        spline.points.last().unwrap().pos
+    );
+
+    let mut kept = simple.points.windows(2);
+    let mut segment = kept.next().unwrap();
+    for point in &spline.points {
+        let (a, b) = (segment[0].pos, segment[1].pos);
+        let chord = b - a;
+        let along = ((point.pos - a).dot(chord) / chord.length_squared()).clamp(0.0, 1.0);
+        let offset = (point.pos - a - chord * along).length();
+        assert!(offset <= tolerance.position + 1e-9, "{offset}");
+        if point.pos == b {
+            if let Some(next) = kept.next() {
+                segment = next;
+            }
+        }
+    }
+}
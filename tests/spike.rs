@@ -0,0 +1,58 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{error::TrackError, track::Track};
+
+fn spike_after(pitch_up: &str, after: &str) -> Track {
+    Track::from_json(&format!(
+        r#"{{"anchor": {{"velocity": 25}}, "sections": [
+            {{"type": "straight", "length": 10}},
+            {pitch_up}
+            {{"type": "spike", "length": 60, "twist": 90}}
+            {after}
+        ]}}"#
+    ))
+    .unwrap()
+}
+
+const PITCH_UP: &str = r#"{"type": "curved", "radius": 15, "angle": 90, "direction": 0},"#;
+
+#[test]
+fn train_rolls_back_down_the_spike() {
+    let track = spike_after(PITCH_UP, "");
+    let (spline, _) = track.try_get_spline().unwrap();
+    let splines = track.make_splines();
+    let spike = &splines[2];
+
+    // Up and back down to the foot of the spike
+    let base = splines[1].points.last().unwrap();
+    let apex = spike
+        .points
+        .iter()
+        .map(|p| p.pos.y)
+        .fold(f64::MIN, f64::max);
+    assert!(apex > base.pos.y + 10.0);
+    assert!(spike.rolls_back());
+    let last = spike.points.last().unwrap();
+    assert!(last.velocity < 0.0);
+    assert!((last.pos - base.pos).length() < 0.05);
+    assert!(spline.rolls_back());
+}
+
+#[test]
+fn spikes_have_to_point_straight_up() {
+    let err = spike_after("", "").try_get_spline().unwrap_err();
+    let TrackError::SpikeEntry { section, pitch } = err else {
+        panic!("expected a spike entry error, got {err:?}");
+    };
+    assert_eq!(section, 1);
+    assert!(pitch.abs() < 1e-6);
+}
+
+#[test]
+fn nothing_comes_after_a_spike() {
+    let track = spike_after(PITCH_UP, r#", {"type": "straight", "length": 10}"#);
+    assert_eq!(
+        track.check().unwrap_err(),
+        TrackError::SpikeNotLast { section: 2 }
+    );
+}
@@ -0,0 +1,43 @@
+#![cfg(all(feature = "spill", feature = "json"))]
+
+use fvd_rs::{spill::SplineStore, track::Track, TrackPoint};
+
+#[test]
+fn store_spills_past_its_memory_cap() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 10}, "sections": [
+            {"type": "curved", "radius": 20, "angle": 90, "direction": 45, "fixedSpeed": 10}
+        ]}"#,
+    )
+    .unwrap();
+    let (mut spline, _) = track.get_spline();
+    spline.fill_distance();
+    let size = spline.points.len() * std::mem::size_of::<TrackPoint>();
+
+    let dir = std::env::temp_dir().join(format!("fvd-spill-{}", std::process::id()));
+    let mut store = SplineStore::new(&dir, size);
+    let first = store.push(spline.clone()).unwrap();
+    let second = store.push(spline.clone()).unwrap();
+    assert!(!store.is_spilled(first));
+    assert!(store.is_spilled(second));
+    assert_eq!(store.memory_used(), size);
+
+    let loaded = store.get(second).unwrap().unwrap();
+    assert_eq!(loaded.points.len(), spline.points.len());
+    for (a, b) in loaded.points.iter().zip(&spline.points) {
+        assert_eq!(a.pos, b.pos);
+        assert_eq!(a.rot.0, b.rot.0);
+        assert_eq!(
+            (a.velocity, a.time, a.distance),
+            (b.velocity, b.time, b.distance)
+        );
+        assert_eq!(a.enclosed, b.enclosed);
+    }
+
+    let mut count = 0;
+    store.for_each_point(second, |_| count += 1).unwrap();
+    assert_eq!(count, spline.points.len());
+
+    drop(store);
+    assert!(!dir.join("1.points").exists());
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::track::Track;
+
+fn turn(spiral: f64) -> Track {
+    Track::from_json(&format!(
+        r#"{{"anchor": {{"velocity": 15}}, "sections": [
+            {{"type": "straight", "length": 10, "fixedSpeed": 15}},
+            {{"type": "curved", "radius": 20, "angle": 90, "direction": 90, "fixedSpeed": 15,
+              "spiral": {spiral}}}
+        ]}}"#
+    ))
+    .unwrap()
+}
+
+#[test]
+fn spirals_ease_the_lateral_in_and_out() {
+    let full = 225.0 / 20.0 / 9.80665;
+    let plain = turn(0.0).analysis(0.5);
+    let eased = turn(15.0).analysis(0.5);
+
+    // Just into the curve, 1 m past the end of the straight
+    let entry = |samples: &[fvd_rs::analysis::AnalysisSample]| {
+        let sample = samples.iter().find(|s| s.distance >= 11.0).unwrap();
+        sample.lat.abs()
+    };
+    assert!((entry(&plain) - full).abs() < 0.05);
+    assert!(entry(&eased) < 0.15 * full);
+
+    let peak = eased.iter().map(|s| s.lat.abs()).fold(0.0, f64::max);
+    assert!((peak - full).abs() < 0.05);
+    assert!(eased.last().unwrap().lat.abs() < 0.15 * full);
+
+    // Both still turn the full 90 degrees, the spiral one over 15 m more track
+    for spiral in [0.0, 15.0] {
+        let headings = turn(spiral).get_spline().0.headings();
+        let turned = headings.last().unwrap() - headings[0];
+        assert!((turned.abs() - 90.0).abs() < 0.5, "{turned}");
+    }
+    let extra = eased.last().unwrap().distance - plain.last().unwrap().distance;
+    assert!((extra - 15.0).abs() < 0.6, "{extra}");
+}
@@ -0,0 +1,42 @@
+use fvd_rs::{
+    stall::SplineResult,
+    track::{Track, TrackConfig, TrackSection},
+};
+
+// Friction alone stops the train about 40m into the second straight
+fn track(velocity: f64) -> Track {
+    let mut track = Track {
+        config: TrackConfig::new(0.03, 0.0, 1.1),
+        ..Default::default()
+    };
+    track.anchor.velocity = velocity;
+    for length in [10.0, 100.0] {
+        track.sections.push(TrackSection::Straight {
+            length,
+            fixed_speed: None,
+        });
+    }
+    track
+}
+
+#[test]
+fn stall_reports_where_and_how_short() {
+    let result = track(5.0).try_make_splines();
+    let stall = *result.stall().unwrap();
+    assert_eq!(stall.section, 1);
+    assert!((stall.distance - 42.5).abs() < 1.0, "{stall:?}");
+    assert!((stall.position.z.abs() - stall.distance).abs() < 0.1);
+
+    // Whatever's left of the straight, at the friction's cost per meter
+    let deficit = stall.deficit_energy.unwrap();
+    assert!((deficit - (110.0 - stall.distance) * 0.03 * 9.80665).abs() < 0.5);
+
+    // With no air resistance the energy it's short by is the same entering the track
+    let with_energy = |energy: f64| track((25.0 + 2.0 * energy).sqrt()).try_make_splines();
+    assert!(matches!(
+        with_energy(1.05 * deficit),
+        SplineResult::Complete(_)
+    ));
+    assert!(with_energy(0.9 * deficit).stall().is_some());
+    assert!(stall.extra_speed.unwrap() > 0.0);
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{get_stats, track::Track};
+
+#[test]
+fn stats_break_down_by_section() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 10}, "sections": [
+            {"type": "straight", "length": 20, "fixedSpeed": 10},
+            {"type": "curved", "radius": 20, "angle": 90, "direction": 90, "fixedSpeed": 15}
+        ]}"#,
+    )
+    .unwrap();
+    let stats = track.stats().unwrap();
+
+    assert_eq!(stats.sections.len(), 2);
+    let (straight, curve) = (&stats.sections[0], &stats.sections[1]);
+    assert!((straight.stats.length - 20.0).abs() < 0.1);
+    assert!((curve.start - straight.stats.length).abs() < 1e-9);
+    assert!(straight.stats.max_lat < 1e-6);
+    // 15 m/s round a 20 m radius
+    assert!((curve.stats.max_lat - 225.0 / 20.0 / 9.80665).abs() < 0.05);
+
+    // The total also covers the step joining the sections
+    let sections = straight.stats.length + curve.stats.length;
+    assert!(stats.total.length >= sections && stats.total.length - sections < 0.5);
+    assert_eq!(stats.total.max_speed, 15.0);
+    assert_eq!(stats.total.min_speed, 10.0);
+    assert!((stats.total.max_vert - 1.0).abs() < 1e-6);
+
+    let json = get_stats(&serde_json::to_string(&track).unwrap()).unwrap();
+    assert!(json.contains("\"maxLat\"") && json.contains("\"enclosedLength\""));
+}
+
+#[test]
+fn stats_carry_launch_and_lift_loads() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 5}, "config": {"trainMass": 5000}, "sections": [
+            {"type": "launch", "length": 40, "targetSpeed": 25},
+            {"type": "lift", "length": 30, "pitch": 30, "chainSpeed": 5}
+        ]}"#,
+    )
+    .unwrap();
+    let stats = track.stats().unwrap();
+
+    assert_eq!(stats.launch_requirements.len(), 1);
+    let launch = &stats.launch_requirements[0];
+    assert_eq!(launch.section, 0);
+    // Kinetic energy gained from 5 to 25 m/s
+    let gained = 0.5 * 5000.0 * (25.0 * 25.0 - 5.0 * 5.0);
+    assert!(
+        (launch.energy - gained).abs() / gained < 0.01,
+        "{}",
+        launch.energy
+    );
+
+    assert_eq!(stats.lift_loads.len(), 1);
+    assert_eq!(stats.lift_loads[0].section, 1);
+    assert!(stats.lift_loads[0].peak_force > 0.0);
+}
+
+#[test]
+fn uphill_launches_carry_no_lift_load() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 10}, "config": {"trainMass": 5000}, "sections": [
+            {"type": "curved", "radius": 30, "angle": 20, "direction": 0},
+            {"type": "launch", "length": 40, "targetSpeed": 25},
+            {"type": "straight", "length": 20, "fixedSpeed": 20}
+        ]}"#,
+    )
+    .unwrap();
+    let splines = track.make_splines();
+    assert!(splines[1].points.last().unwrap().pos.y > splines[1].points[0].pos.y + 10.0);
+    assert!(splines[2].points.last().unwrap().pos.y > splines[2].points[0].pos.y);
+
+    let stats = track.stats().unwrap();
+    assert!(stats.lift_loads.is_empty(), "{:?}", stats.lift_loads);
+    assert_eq!(stats.launch_requirements[0].section, 1);
+}
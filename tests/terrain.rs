@@ -0,0 +1,69 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{error::TrackError, track::Track, TrackSpline};
+
+fn over_terrain(terrain: &str) -> Track {
+    Track::from_json(&format!(
+        r#"{{"anchor": {{"velocity": 10, "pos": [0, 5, 0]}}, "terrain": {terrain},
+            "config": {{"heartlineHeight": 1}},
+            "enclosures": [{{"start": 14, "end": 16}}],
+            "sections": [{{"type": "straight", "length": 30}}]}}"#
+    ))
+    .unwrap()
+}
+
+#[test]
+fn heights_follow_the_heightmap() {
+    // Ground rises 0.1 m per meter along z, the direction of travel
+    let ramp: Vec<String> = (0..82)
+        .map(|i| format!("{}", (i / 2) as f64 * 0.1))
+        .collect();
+    let track = over_terrain(&format!(
+        r#"{{"type": "heightmap", "origin": [-1, 0], "spacing": 1, "columns": 2,
+            "heights": [{}]}}"#,
+        ramp.join(",")
+    ));
+    track.check().unwrap();
+    let spline = TrackSpline::concat(&track.make_splines());
+    let heights = track.heights_above_ground();
+    assert_eq!(heights.len(), spline.points.len());
+    for (point, height) in spline.points.iter().zip(&heights) {
+        // Rails sit 4 m up at the anchor
+        assert!((height - (4.0 - point.pos.z * 0.1)).abs() < 1e-6);
+    }
+
+    // Below 2 m from 20 m on, except where the tunnel is
+    let clearance = track.ground_clearance(2.0);
+    assert!((clearance.min_height - 1.0).abs() < 0.05);
+    assert!((clearance.distance - 30.0).abs() < 0.05);
+    assert_eq!(clearance.violations.len(), 1);
+    assert!((clearance.violations[0].start - 20.0).abs() < 0.05);
+    assert!(track.ground_clearance(3.0).violations.len() == 2);
+}
+
+#[test]
+fn malformed_heightmaps_are_rejected() {
+    let track = over_terrain(
+        r#"{"type": "heightmap", "origin": [0, 0], "spacing": 1, "columns": 0, "heights": [1, 2]}"#,
+    );
+    assert_eq!(
+        track.check().unwrap_err(),
+        TrackError::InvalidConfig {
+            field: "terrain.columns",
+            value: 0.0
+        }
+    );
+    // Falls back to flat ground instead of panicking
+    assert!(track.heights_above_ground().iter().all(|&h| h == 4.0));
+
+    let track = over_terrain(
+        r#"{"type": "heightmap", "origin": [0, 0], "spacing": 1, "columns": 2, "heights": [1, 2, 3]}"#,
+    );
+    assert_eq!(
+        track.check().unwrap_err(),
+        TrackError::InvalidConfig {
+            field: "terrain.heights",
+            value: 3.0
+        }
+    );
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{constants::G, math::euler, track::Track};
+
+#[test]
+fn tilt_releases_down_the_tilted_track_after_the_tilt() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 5}, "config": {"parameter": 0, "resistance": 0},
+            "sections": [
+            {"type": "straight", "length": 10, "fixedSpeed": 5},
+            {"type": "tilt", "length": 20, "angle": 30, "duration": 2}
+        ]}"#,
+    )
+    .unwrap();
+    let splines = track.make_splines();
+    let entry = *splines[0].points.last().unwrap();
+    let tilt = &splines[1];
+    let (first, last) = (tilt.points[0], *tilt.points.last().unwrap());
+
+    // Released from rest once the track has tilted 30 degrees down
+    assert!(first.time - entry.time > 2.0 && first.time - entry.time < 2.1);
+    assert!((euler(&first).1 + 30.0).abs() < 1e-6);
+    assert!(first.velocity < 0.5);
+
+    // Rolls the 20 m down the slope, 10 m drop
+    let accel = G * 0.5;
+    let roll = (2.0 * 20.0 / accel).sqrt();
+    assert!((last.time - entry.time - 2.0 - roll).abs() < 0.02);
+    assert!((last.velocity - (2.0 * G * 10.0).sqrt()).abs() < 0.05);
+    assert!((entry.pos.y - last.pos.y - 10.0).abs() < 0.05);
+    assert!(tilt.points.iter().all(|p| p.section_time >= 2.0));
+
+    // The piece tilts over the same two seconds
+    let animation = &track.animations()[0];
+    assert_eq!(animation.keyframes[0].time, entry.time);
+    let end = animation.keyframes.last().unwrap();
+    assert!((end.time - entry.time - 2.0).abs() < 1e-9);
+    assert!((end.rot.0.angle_between(entry.rot.0).to_degrees() - 30.0).abs() < 1e-6);
+}
@@ -0,0 +1,58 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::track::Track;
+
+#[test]
+fn times_and_speeds_between_points_along_the_track() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 10}, "sections": [
+            {"type": "straight", "length": 50, "fixedSpeed": 10},
+            {"type": "straight", "length": 50, "fixedSpeed": 20}
+        ]}"#,
+    )
+    .unwrap();
+    let (spline, _) = track.get_spline();
+    let index = spline.index();
+
+    assert!((index.time_between(10.0, 30.0).unwrap() - 2.0).abs() < 0.01);
+    assert!((index.time_between(30.0, 10.0).unwrap() + 2.0).abs() < 0.01);
+    // 40 m at 10 m/s then 40 m at 20 m/s
+    assert!((spline.time_between(10.0, 90.0).unwrap() - 6.0).abs() < 0.05);
+    assert!(index
+        .time_between(0.0, index.total_distance() + 1.0)
+        .is_none());
+
+    assert_eq!(spline.speed_at(20.0), Some(10.0));
+    assert_eq!(index.speed_at(80.0), Some(20.0));
+    assert!(index.speed_at(-1.0).is_none());
+}
+
+#[test]
+fn every_section_keeps_global_and_local_time() {
+    let track = Track::from_json(
+        r#"{"anchor": {"velocity": 10}, "sections": [
+            {"type": "straight", "length": 20, "fixedSpeed": 10},
+            {"type": "force", "fixedSpeed": 10, "transitions": {
+                "vert": [{"curve": "linear", "value": 0, "length": 2}],
+                "lat": [{"curve": "linear", "value": 0, "length": 2}],
+                "roll": [{"curve": "linear", "value": 0, "length": 2}]
+            }},
+            {"type": "curved", "radius": 20, "angle": 90, "direction": 90, "fixedSpeed": 10}
+        ]}"#,
+    )
+    .unwrap();
+    let splines = track.make_splines();
+    let mut section_start = 0.0;
+    for spline in &splines {
+        let (first, last) = (spline.points[0], *spline.points.last().unwrap());
+        // Each section picks up the clock one step after the last one stopped
+        assert!(first.time > section_start && first.time - section_start < 0.02);
+        for point in &spline.points {
+            assert!((point.time - point.section_time - section_start).abs() < 1e-9);
+        }
+        section_start = last.time;
+    }
+    // 20 m, 2 s then a quarter of a 20 m circle, all at 10 m/s
+    let quarter = 20.0 * std::f64::consts::FRAC_PI_2 / 10.0;
+    assert!((section_start - 4.0 - quarter).abs() < 0.02);
+}
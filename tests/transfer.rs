@@ -0,0 +1,49 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{error::TrackError, glam::DVec3, track::Track};
+
+fn transfer(exit_speed: f64) -> Track {
+    Track::from_json(&format!(
+        r#"{{"anchor": {{"velocity": 5}}, "sections": [
+            {{"type": "straight", "length": 10, "fixedSpeed": 5}},
+            {{"type": "transfer", "length": 20, "offset": [5, 0, 0], "duration": 4,
+                "exitSpeed": {exit_speed}}}
+        ]}}"#
+    ))
+    .unwrap()
+}
+
+#[test]
+fn train_leaves_the_moved_piece_after_the_move() {
+    let track = transfer(2.0);
+    track.check().unwrap();
+    let splines = track.make_splines();
+    let entry = *splines[0].points.last().unwrap();
+    let (first, last) = (splines[1].points[0], *splines[1].points.last().unwrap());
+
+    // Shifted across with the piece, then driven off at the exit speed
+    assert!((first.pos - entry.pos - DVec3::new(5.0, 0.0, 0.01)).length() < 1e-9);
+    assert!((first.time - entry.time - 4.0 - 0.01 / 2.0).abs() < 1e-9);
+    assert!((last.time - entry.time - 4.0 - 20.0 / 2.0).abs() < 0.01);
+    assert!(splines[1].points.iter().all(|p| p.velocity == 2.0));
+
+    // The piece moves while the train waits on it
+    let animations = track.animations();
+    assert_eq!(animations.len(), 1);
+    let keyframes = &animations[0].keyframes;
+    assert_eq!(keyframes[0].time, entry.time);
+    let end = keyframes.last().unwrap();
+    assert!((end.time - entry.time - 4.0).abs() < 1e-9);
+    assert!((end.translation - DVec3::new(5.0, 0.0, 0.0)).length() < 1e-9);
+}
+
+#[test]
+fn transfers_need_an_exit_speed() {
+    assert_eq!(
+        transfer(0.0).check().unwrap_err(),
+        TrackError::InvalidConfig {
+            field: "transfer.exitSpeed",
+            value: 0.0
+        }
+    );
+}
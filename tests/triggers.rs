@@ -0,0 +1,48 @@
+#![cfg(feature = "json")]
+
+use fvd_rs::{error::TrackError, get_trigger_csv, track::Track, triggers::TriggerAt};
+
+const TRACK: &str = r#"{"anchor": {"velocity": 10}, "sections": [
+    {"type": "straight", "length": 20, "fixedSpeed": 10},
+    {"type": "straight", "length": 20, "fixedSpeed": 20}
+], "triggers": [
+    {"name": "gate", "at": {"type": "distance", "distance": 15}},
+    {"name": "boost", "at": {"type": "sectionStart", "section": 1}, "offset": 5},
+    {"name": "end, block", "at": {"type": "sectionEnd", "section": 1}, "offset": -1}
+]}"#;
+
+#[test]
+fn triggers_are_placed_along_the_ride() {
+    let track = Track::from_json(TRACK).unwrap();
+    let manifest = track.trigger_manifest().unwrap();
+
+    assert_eq!(manifest.len(), 3);
+    let (gate, boost, end) = (&manifest[0], &manifest[1], &manifest[2]);
+    assert!((gate.time - 1.5).abs() < 0.01 && gate.speed == 10.0);
+    assert!((gate.pos.z.abs() - 15.0).abs() < 0.05);
+    assert!((boost.distance - 25.0).abs() < 0.05 && boost.speed == 20.0);
+    assert!((end.distance - 39.0).abs() < 0.05);
+    // 2 s over the first section then 19 m at 20 m/s
+    assert!((end.time - 2.95).abs() < 0.01);
+
+    let csv = get_trigger_csv(TRACK).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("name,distance,time,speed,x,y,z"));
+    assert!(lines[3].starts_with("\"end, block\","));
+}
+
+#[test]
+fn triggers_off_the_track_are_errors() {
+    let mut track = Track::from_json(TRACK).unwrap();
+    track.triggers[1].at = TriggerAt::SectionStart { section: 2 };
+    assert_eq!(
+        track.trigger_manifest().unwrap_err(),
+        TrackError::MissingTrigger {
+            name: "boost".to_string()
+        }
+    );
+
+    track.triggers[1].at = TriggerAt::Distance { distance: 100.0 };
+    assert!(track.trigger_manifest().is_err());
+}
@@ -0,0 +1,42 @@
+use fvd_rs::{glam::DVec3, math::euler, track::Track, turnaround::TurnaroundDesign};
+
+fn track() -> Track {
+    let mut track = Track::default();
+    track.anchor.pos = DVec3::new(0.0, 10.0, 0.0);
+    track.anchor.velocity = 20.0;
+    track
+}
+
+#[test]
+fn turnaround_turns_round_level() {
+    for heading in [180.0, -135.0] {
+        let mut track = track();
+        let design = TurnaroundDesign {
+            heading,
+            ..Default::default()
+        };
+        track.sections.push(track.turnaround(&design).unwrap());
+
+        let (spline, _) = track.get_spline();
+        let headings = spline.headings();
+        let turned = headings.first().unwrap() - headings.last().unwrap();
+        assert!((turned - heading).abs() < 1.5, "{turned}");
+        let (_, pitch, roll) = euler(spline.points.last().unwrap());
+        assert!(pitch.abs() < 1.0, "{pitch}");
+        assert!(roll.abs() < 1.0, "{roll}");
+        let stats = spline.stats();
+        assert!(stats.max_lat < design.max_lat + 0.05, "{stats:?}");
+        assert!(stats.highest.y - stats.lowest.y < 0.5, "{stats:?}");
+    }
+}
+
+#[test]
+fn turnaround_needs_enough_vert() {
+    let track = track();
+    let design = TurnaroundDesign {
+        vert: 0.9,
+        max_lat: 0.0,
+        ..Default::default()
+    };
+    assert!(track.turnaround(&design).is_none());
+}